@@ -0,0 +1,298 @@
+//!
+//! A module for Grover's search and amplitude amplification.
+//!
+//! The marked states are given by a [PhaseOracle](trait.PhaseOracle.html), which flips
+//! the phases of them. An oracle can be a closure deciding whether a bitstring is marked,
+//! or a recorded [Circuit](../../circuit/struct.Circuit.html).
+//!
+//! The qubits are interpreted as an integer in little-endian, i.e. `qubits[0]` is the least
+//! significant bit.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::algorithms::grover;
+//!
+//! let mut sim = QuantumSimulator::new(2);
+//! let qubits = sim.get_qubits();
+//!
+//! // Marks 0b10 = 2.
+//! let oracle = |bits: &[bool]| bits == [false, true];
+//!
+//! // A single iteration finds the marked state for sure out of four states.
+//! assert_eq!(grover::search(&mut sim, &qubits, &oracle, 1), 2);
+//! ```
+//!
+//! Note that the operations in this module are implemented up to global phases.
+//!
+
+use circuit::Circuit;
+use gates::double::DoubleGateApplicator;
+use gates::multi::{self, MultiGateApplicator};
+use gates::single::SingleGateApplicator;
+use gates::triple::TripleGateApplicator;
+use std::f64::consts::PI;
+use {Endianness, QuantumMachine, Qubit, QubitRegister};
+
+///
+/// A trait for the oracles which flip the phases of the marked basis states.
+///
+pub trait PhaseOracle<M> {
+    ///
+    /// Multiplies $-1$ to the marked basis states of `qubits`.
+    ///
+    fn apply_oracle(&self, machine: &mut M, qubits: &[Qubit]);
+}
+
+///
+/// A closure deciding whether the bitstring is marked is an oracle.
+/// The `k`-th bit corresponds to `qubits[k]`.
+///
+/// The oracle is built by flipping the phase of each marked basis state separately, so
+/// the closure is called for all the $2^n$ bitstrings.
+///
+impl<M, F> PhaseOracle<M> for F
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+    F: Fn(&[bool]) -> bool,
+{
+    fn apply_oracle(&self, machine: &mut M, qubits: &[Qubit]) {
+        let n = qubits.len();
+        for x in 0..1usize << n {
+            let bits = (0..n).map(|k| (x >> k) & 1 == 1).collect::<Vec<_>>();
+            if self(&bits) {
+                flip_zeros(machine, qubits, &bits);
+                multi::mcz(machine, qubits);
+                flip_zeros(machine, qubits, &bits);
+            }
+        }
+    }
+}
+
+///
+/// A circuit is an oracle, whose qubit with index `i` is mapped to `qubits[i]`.
+///
+impl<M> PhaseOracle<M> for Circuit
+where
+    M: QuantumMachine
+        + SingleGateApplicator
+        + DoubleGateApplicator
+        + TripleGateApplicator
+        + MultiGateApplicator,
+{
+    fn apply_oracle(&self, machine: &mut M, qubits: &[Qubit]) {
+        self.run_on(machine, qubits);
+    }
+}
+
+///
+/// A trait for the operations preparing the initial state of amplitude amplification from
+/// $|0\cdots0\rangle$.
+///
+pub trait StatePreparation<M> {
+    /// Applies the operation.
+    fn prepare(&self, machine: &mut M, qubits: &[Qubit]);
+
+    /// Applies the inverse of the operation.
+    fn unprepare(&self, machine: &mut M, qubits: &[Qubit]);
+}
+
+///
+/// The preparation of the uniform superposition by the Hadamard gates, used in Grover's search.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Uniform;
+
+impl<M: SingleGateApplicator> StatePreparation<M> for Uniform {
+    fn prepare(&self, machine: &mut M, qubits: &[Qubit]) {
+        for qubit in qubits {
+            machine.H(qubit);
+        }
+    }
+
+    fn unprepare(&self, machine: &mut M, qubits: &[Qubit]) {
+        self.prepare(machine, qubits);
+    }
+}
+
+///
+/// A circuit without measurements is a preparation, whose inverse is
+/// [Circuit::inverse](../../circuit/struct.Circuit.html#method.inverse).
+///
+impl<M> StatePreparation<M> for Circuit
+where
+    M: QuantumMachine
+        + SingleGateApplicator
+        + DoubleGateApplicator
+        + TripleGateApplicator
+        + MultiGateApplicator,
+{
+    fn prepare(&self, machine: &mut M, qubits: &[Qubit]) {
+        self.run_on(machine, qubits);
+    }
+
+    fn unprepare(&self, machine: &mut M, qubits: &[Qubit]) {
+        self.inverse().run_on(machine, qubits);
+    }
+}
+
+///
+/// A pair of closures applying an operation and its inverse is a preparation.
+///
+impl<M, F, G> StatePreparation<M> for (F, G)
+where
+    F: Fn(&mut M, &[Qubit]),
+    G: Fn(&mut M, &[Qubit]),
+{
+    fn prepare(&self, machine: &mut M, qubits: &[Qubit]) {
+        (self.0)(machine, qubits);
+    }
+
+    fn unprepare(&self, machine: &mut M, qubits: &[Qubit]) {
+        (self.1)(machine, qubits);
+    }
+}
+
+fn flip_zeros<M: SingleGateApplicator>(machine: &mut M, qubits: &[Qubit], bits: &[bool]) {
+    for (qubit, &bit) in qubits.iter().zip(bits) {
+        if !bit {
+            machine.X(qubit);
+        }
+    }
+}
+
+///
+/// Flips the phase of $|0\cdots0\rangle$, i.e. applies $1 - 2|0\cdots0\rangle\langle0\cdots0|$.
+///
+/// This is the reflection about $|0\cdots0\rangle$ up to the global phase $-1$, which makes no
+/// difference.
+///
+fn reflect_zero<M>(machine: &mut M, qubits: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    flip_zeros(machine, qubits, &vec![false; qubits.len()]);
+    multi::mcz(machine, qubits);
+    flip_zeros(machine, qubits, &vec![false; qubits.len()]);
+}
+
+///
+/// Applies the diffusion operator $2|s\rangle\langle s| - 1$ up to the global phase, where
+/// $|s\rangle$ is the uniform superposition.
+///
+pub fn diffusion<M>(machine: &mut M, qubits: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    Uniform.unprepare(machine, qubits);
+    reflect_zero(machine, qubits);
+    Uniform.prepare(machine, qubits);
+}
+
+///
+/// Applies a Grover iteration, i.e. the oracle followed by the diffusion operator.
+///
+pub fn grover_iteration<M, O>(machine: &mut M, qubits: &[Qubit], oracle: &O)
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+    O: PhaseOracle<M> + ?Sized,
+{
+    oracle.apply_oracle(machine, qubits);
+    diffusion(machine, qubits);
+}
+
+///
+/// Returns the number of iterations maximizing the probability to find a marked state
+/// when the initial state finds it with `probability`.
+///
+/// # Panics
+///
+/// Panics if `probability` is not in $(0, 1]$.
+///
+pub fn optimal_iterations_for_probability(probability: f64) -> usize {
+    assert!(
+        probability > 0. && probability <= 1.,
+        "the probability must be in (0, 1], but {} is given",
+        probability
+    );
+
+    let theta = probability.sqrt().asin();
+    (PI / (4. * theta)).floor() as usize
+}
+
+///
+/// Returns the optimal number of Grover iterations for `solutions` marked states
+/// out of $2^n$ states of `qubits_len` qubits.
+///
+/// # Examples
+///
+/// ```
+/// use rusq::algorithms::grover;
+///
+/// assert_eq!(grover::optimal_iterations(2, 1), 1);
+/// assert_eq!(grover::optimal_iterations(10, 1), 25);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `solutions` is zero or larger than $2^n$.
+///
+pub fn optimal_iterations(qubits_len: usize, solutions: usize) -> usize {
+    let size = 1usize << qubits_len;
+    assert!(
+        solutions > 0 && solutions <= size,
+        "the number of solutions must be in [1, {}], but {} is given",
+        size,
+        solutions
+    );
+
+    optimal_iterations_for_probability(solutions as f64 / size as f64)
+}
+
+///
+/// Performs amplitude amplification.
+///
+/// Starting from $|0\cdots0\rangle$, the state $A|0\cdots0\rangle$ is prepared by `preparation`
+/// and then the operator $-A S_0 A^\dagger S_\chi$ is applied `iterations` times, where
+/// $S_\chi$ is `oracle` and $S_0$ flips the phase of $|0\cdots0\rangle$.
+/// If the initial state has the probability $\sin^2\theta$ to find a marked state,
+/// the resulting state has the probability $\sin^2((2k + 1)\theta)$ for $k$ iterations.
+///
+pub fn amplify<M, P, O>(
+    machine: &mut M,
+    qubits: &[Qubit],
+    preparation: &P,
+    oracle: &O,
+    iterations: usize,
+) where
+    M: SingleGateApplicator + DoubleGateApplicator,
+    P: StatePreparation<M> + ?Sized,
+    O: PhaseOracle<M> + ?Sized,
+{
+    preparation.prepare(machine, qubits);
+    for _ in 0..iterations {
+        oracle.apply_oracle(machine, qubits);
+        preparation.unprepare(machine, qubits);
+        reflect_zero(machine, qubits);
+        preparation.prepare(machine, qubits);
+    }
+}
+
+///
+/// Searches a marked state with Grover's algorithm and returns the measured integer.
+///
+/// The qubits must be initially $|0\cdots0\rangle$.
+/// The optimal number of iterations is used for `solutions` marked states, but the result
+/// is not guaranteed to be marked.
+///
+pub fn search<M, O>(machine: &mut M, qubits: &[Qubit], oracle: &O, solutions: usize) -> usize
+where
+    M: QuantumMachine + SingleGateApplicator + DoubleGateApplicator,
+    O: PhaseOracle<M> + ?Sized,
+{
+    let iterations = optimal_iterations(qubits.len(), solutions);
+    amplify(machine, qubits, &Uniform, oracle, iterations);
+
+    QubitRegister::new(qubits.to_vec()).measure_integer(machine, Endianness::Little)
+}
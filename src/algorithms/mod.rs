@@ -0,0 +1,15 @@
+//!
+//! A module for quantum algorithms built on the quantum gates.
+//!
+//! The routines are generic over the machine, so that they can be run on a
+//! [QuantumSimulator](../simulator/simulator/struct.QuantumSimulator.html) as well as recorded into
+//! a [Circuit](../circuit/struct.Circuit.html).
+//! The variational algorithms run on a QuantumSimulator, which gives the expectation values.
+//!
+
+pub mod grover;
+pub mod phase_estimation;
+pub mod qaoa;
+pub mod qft;
+pub mod shor;
+pub mod vqe;
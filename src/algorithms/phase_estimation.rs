@@ -0,0 +1,204 @@
+//!
+//! A module for quantum phase estimation.
+//!
+//! For a unitary $U$ and its eigenstate $|\psi\rangle$ with
+//! $U|\psi\rangle = e^{2\pi i\phi}|\psi\rangle$, the phase $\phi \in [0, 1)$ is estimated with
+//! $t$ counting qubits to the precision of $2^{-t}$.
+//!
+//! The unitary is given as a [ControlledUnitary](trait.ControlledUnitary.html), which is
+//! implemented for a [UnitaryMatrix](struct.UnitaryMatrix.html) and for closures applying
+//! the controlled unitary.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::algorithms::phase_estimation::{self, UnitaryMatrix};
+//! use std::f64::consts::PI;
+//!
+//! let mut sim = QuantumSimulator::new(4);
+//! let qubits = sim.get_qubits();
+//! let (counting, eigenstate) = qubits.split_at(3);
+//!
+//! // |1> is an eigenstate of the phase gate P(2π * 5/8).
+//! let unitary = |machine: &mut QuantumSimulator, control: &Qubit, targets: &[Qubit]| {
+//!     machine.cphase(2. * PI * 5. / 8., control, &targets[0]);
+//! };
+//! sim.X(&eigenstate[0]);
+//!
+//! let phase = phase_estimation::estimate_phase(&mut sim, &unitary, counting, eigenstate);
+//! assert_eq!(phase, 5. / 8.);
+//! ```
+//!
+
+use algorithms::qft;
+use gates::double::DoubleGateApplicator;
+use gates::multi::MultiGateApplicator;
+use gates::single::SingleGateApplicator;
+use gates::triple::TripleGateApplicator;
+use ndarray::prelude::*;
+use num::complex::Complex;
+use {Endianness, QuantumMachine, Qubit, QubitRegister};
+
+///
+/// A trait for the unitary operations which can be controlled by a qubit.
+///
+pub trait ControlledUnitary<M> {
+    ///
+    /// Applies the unitary to `targets` if `control` is $|1\rangle$.
+    ///
+    fn apply_controlled(&self, machine: &mut M, control: &Qubit, targets: &[Qubit]);
+
+    ///
+    /// Applies the unitary to the power of $2^k$ to `targets` if `control` is $|1\rangle$.
+    ///
+    /// By default, the unitary is applied $2^k$ times.
+    ///
+    fn apply_controlled_power(&self, machine: &mut M, control: &Qubit, targets: &[Qubit], k: u32) {
+        for _ in 0..1u64 << k {
+            self.apply_controlled(machine, control, targets);
+        }
+    }
+}
+
+///
+/// A closure applying the controlled unitary is a controlled unitary.
+///
+impl<M, F> ControlledUnitary<M> for F
+where
+    F: Fn(&mut M, &Qubit, &[Qubit]),
+{
+    fn apply_controlled(&self, machine: &mut M, control: &Qubit, targets: &[Qubit]) {
+        self(machine, control, targets);
+    }
+}
+
+///
+/// A unitary given as a matrix.
+///
+/// As in the gate applicators, the first target qubit corresponds to the most significant bit
+/// of the indices of the matrix. The controlled unitary is applied by
+/// [apply_double](../../gates/double/trait.DoubleGateApplicator.html#tymethod.apply_double) for
+/// a single target qubit, by
+/// [apply_triple](../../gates/triple/trait.TripleGateApplicator.html#tymethod.apply_triple) for
+/// two target qubits and by
+/// [apply_multi](../../gates/multi/trait.MultiGateApplicator.html#tymethod.apply_multi) otherwise.
+/// The powers of the unitary are computed by repeated squaring.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitaryMatrix {
+    matrix: Array2<Complex<f64>>,
+}
+
+impl UnitaryMatrix {
+    ///
+    /// Creates a new instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` is not square or its size is not a power of two.
+    ///
+    pub fn new(matrix: Array2<Complex<f64>>) -> UnitaryMatrix {
+        let (rows, columns) = matrix.dim();
+        assert!(
+            rows == columns && rows.is_power_of_two(),
+            "expected a square matrix of a power of two size but got a {}x{} matrix",
+            rows,
+            columns
+        );
+        UnitaryMatrix { matrix }
+    }
+
+    /// Returns the matrix.
+    pub fn matrix(&self) -> &Array2<Complex<f64>> {
+        &self.matrix
+    }
+
+    fn apply_controlled_matrix<M>(
+        machine: &mut M,
+        matrix: &Array2<Complex<f64>>,
+        control: &Qubit,
+        targets: &[Qubit],
+    ) where
+        M: DoubleGateApplicator + TripleGateApplicator + MultiGateApplicator,
+    {
+        let size = matrix.dim().0;
+        let mut controlled = Array2::eye(2 * size);
+        controlled.slice_mut(s![size.., size..]).assign(matrix);
+
+        match targets.len() {
+            1 => machine.apply_double(&controlled, control, &targets[0]),
+            2 => machine.apply_triple(&controlled, control, &targets[0], &targets[1]),
+            _ => {
+                let mut qubits = vec![*control];
+                qubits.extend_from_slice(targets);
+                machine.apply_multi(&controlled, &qubits);
+            }
+        }
+    }
+}
+
+impl<M> ControlledUnitary<M> for UnitaryMatrix
+where
+    M: DoubleGateApplicator + TripleGateApplicator + MultiGateApplicator,
+{
+    fn apply_controlled(&self, machine: &mut M, control: &Qubit, targets: &[Qubit]) {
+        UnitaryMatrix::apply_controlled_matrix(machine, &self.matrix, control, targets);
+    }
+
+    fn apply_controlled_power(&self, machine: &mut M, control: &Qubit, targets: &[Qubit], k: u32) {
+        let mut matrix = self.matrix.clone();
+        for _ in 0..k {
+            matrix = matrix.dot(&matrix);
+        }
+        UnitaryMatrix::apply_controlled_matrix(machine, &matrix, control, targets);
+    }
+}
+
+///
+/// Applies the phase estimation circuit without measurements.
+///
+/// The `counting` qubits must be initially $|0\cdots0\rangle$, and end up in the state
+/// peaked at the integer $2^t\phi$ in little-endian.
+///
+pub fn phase_estimation<M, U>(
+    machine: &mut M,
+    unitary: &U,
+    counting: &[Qubit],
+    eigenstate: &[Qubit],
+) where
+    M: SingleGateApplicator + DoubleGateApplicator,
+    U: ControlledUnitary<M> + ?Sized,
+{
+    for qubit in counting {
+        machine.H(qubit);
+    }
+    for (k, control) in counting.iter().enumerate() {
+        unitary.apply_controlled_power(machine, control, eigenstate, k as u32);
+    }
+    qft::iqft(machine, counting);
+}
+
+///
+/// Estimates the phase of the eigenvalue of `unitary` for the state of `eigenstate`, and returns
+/// it as a number in $[0, 1)$.
+///
+/// The `counting` qubits must be initially $|0\cdots0\rangle$ and are measured.
+/// If the phase is exactly representable with the counting qubits, the result is exact.
+/// Otherwise, one of the two nearest values is obtained with the probability of at least
+/// $8/\pi^2$.
+///
+pub fn estimate_phase<M, U>(
+    machine: &mut M,
+    unitary: &U,
+    counting: &[Qubit],
+    eigenstate: &[Qubit],
+) -> f64
+where
+    M: QuantumMachine + SingleGateApplicator + DoubleGateApplicator,
+    U: ControlledUnitary<M> + ?Sized,
+{
+    phase_estimation(machine, unitary, counting, eigenstate);
+    let value = QubitRegister::new(counting.to_vec()).measure_integer(machine, Endianness::Little);
+    value as f64 / (1u64 << counting.len()) as f64
+}
@@ -0,0 +1,298 @@
+//!
+//! A module for the quantum approximate optimization algorithm (QAOA).
+//!
+//! The cost function is given as an [Ising](struct.Ising.html) model
+//!
+//! $$C(s) = c + \sum_i h_i s_i + \sum_{i < j} J_{ij} s_i s_j,$$
+//!
+//! where $s_i = 1$ for the bit $0$ and $s_i = -1$ for the bit $1$, i.e. the eigenvalue of $Z$.
+//! The QAOA state with $p$ layers is
+//!
+//! $$|\gamma, \beta\rangle = e^{-i\beta_p B} e^{-i\gamma_p C} \cdots e^{-i\beta_1 B} e^{-i\gamma_1 C} |+\rangle^{\otimes n}$$
+//!
+//! with the mixer $B = \sum_i X_i$, and the parameters are ordered as
+//! $(\gamma_1, \beta_1, \ldots, \gamma_p, \beta_p)$.
+//! The cost is minimized, so that MaxCut is encoded as the minus of the cut.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::algorithms::qaoa::{self, Ising};
+//! use rusq::optimize::NelderMead;
+//!
+//! // A ring of four vertices, whose maximum cut is 4.
+//! let edges = [(0, 1, 1.), (1, 2, 1.), (2, 3, 1.), (3, 0, 1.)];
+//! let problem = Ising::maxcut(4, &edges);
+//!
+//! let mut sim = QuantumSimulator::new(4);
+//! let result = qaoa::solve(&mut sim, &problem, &mut NelderMead::default(), &[0.5, 0.5]);
+//!
+//! // One layer achieves three quarters of the maximum cut.
+//! assert!((result.energy + 3.).abs() < 1e-4);
+//! assert_eq!(problem.energy(&result.solution), -4.);
+//! ```
+//!
+
+use algorithms::vqe::{self, Estimation};
+use gates::double::DoubleGateApplicator;
+use gates::single::SingleGateApplicator;
+use observable::{Hamiltonian, Pauli, PauliString};
+use optimize::Optimizer;
+use simulator::QuantumSimulator;
+use Qubit;
+
+///
+/// A type for the Ising cost functions.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Ising {
+    qubits_len: usize,
+    offset: f64,
+    linear: Vec<(usize, f64)>,
+    quadratic: Vec<(usize, usize, f64)>,
+}
+
+impl Ising {
+    /// Creates the cost function of `qubits_len` spins which is identically zero.
+    pub fn new(qubits_len: usize) -> Ising {
+        Ising {
+            qubits_len,
+            offset: 0.,
+            linear: vec![],
+            quadratic: vec![],
+        }
+    }
+
+    ///
+    /// Creates the cost function of MaxCut for the weighted graph with `edges`,
+    /// which is the minus of the total weight of the cut edges.
+    ///
+    pub fn maxcut(vertices: usize, edges: &[(usize, usize, f64)]) -> Ising {
+        let mut ising = Ising::new(vertices);
+        // An edge is cut iff s_i s_j = -1, so that it adds -w (1 - s_i s_j) / 2.
+        for &(i, j, weight) in edges {
+            ising.add_offset(-weight / 2.);
+            ising.add_quadratic(i, j, weight / 2.);
+        }
+        ising
+    }
+
+    /// Returns the number of the spins.
+    pub fn qubits_len(&self) -> usize {
+        self.qubits_len
+    }
+
+    /// Adds the constant `c`.
+    pub fn add_offset(&mut self, c: f64) {
+        self.offset += c;
+    }
+
+    /// Adds the term $h s_i$.
+    pub fn add_linear(&mut self, i: usize, h: f64) {
+        self.check(i);
+        self.linear.push((i, h));
+    }
+
+    /// Adds the term $J s_i s_j$.
+    pub fn add_quadratic(&mut self, i: usize, j: usize, coupling: f64) {
+        self.check(i);
+        self.check(j);
+        assert_ne!(i, j, "a coupling needs two different spins");
+        self.quadratic.push((i, j, coupling));
+    }
+
+    fn check(&self, i: usize) {
+        assert!(
+            i < self.qubits_len,
+            "spin {} is out of range for {} spins",
+            i,
+            self.qubits_len
+        );
+    }
+
+    /// Returns the cost for the bits, where `true` is $s_i = -1$.
+    pub fn energy(&self, bits: &[bool]) -> f64 {
+        let spin = |i: usize| if bits[i] { -1. } else { 1. };
+        self.offset
+            + self.linear.iter().map(|&(i, h)| h * spin(i)).sum::<f64>()
+            + self
+                .quadratic
+                .iter()
+                .map(|&(i, j, coupling)| coupling * spin(i) * spin(j))
+                .sum::<f64>()
+    }
+
+    /// Returns the cost function as a Hamiltonian with $Z$ for the spins.
+    pub fn hamiltonian(&self) -> Hamiltonian {
+        let mut hamiltonian = Hamiltonian::new();
+        if self.offset != 0. {
+            hamiltonian.push(PauliString {
+                coefficient: self.offset,
+                paulis: vec![],
+            });
+        }
+        for &(i, h) in &self.linear {
+            hamiltonian.push(PauliString {
+                coefficient: h,
+                paulis: vec![(i, Pauli::Z)],
+            });
+        }
+        for &(i, j, coupling) in &self.quadratic {
+            hamiltonian.push(PauliString {
+                coefficient: coupling,
+                paulis: vec![(i, Pauli::Z), (j, Pauli::Z)],
+            });
+        }
+        hamiltonian
+    }
+}
+
+///
+/// A type for the result of the QAOA.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct QaoaResult {
+    /// The expected cost at the optimal parameters
+    pub energy: f64,
+    /// The optimal parameters
+    pub parameters: Vec<f64>,
+    /// The most probable bits in the optimal state
+    pub solution: Vec<bool>,
+    /// The number of the evaluations of the expected cost
+    pub evaluations: usize,
+}
+
+///
+/// Applies $e^{-i\gamma C}$ up to the global phase by the offset.
+///
+pub fn cost_layer<M>(machine: &mut M, problem: &Ising, gamma: f64, qubits: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    for &(i, h) in &problem.linear {
+        machine.rz(2. * gamma * h, &qubits[i]);
+    }
+    for &(i, j, coupling) in &problem.quadratic {
+        machine.rzz(2. * gamma * coupling, &qubits[i], &qubits[j]);
+    }
+}
+
+///
+/// Applies the mixer $e^{-i\beta \sum_i X_i}$.
+///
+pub fn mixer_layer<M>(machine: &mut M, beta: f64, qubits: &[Qubit])
+where
+    M: SingleGateApplicator,
+{
+    for qubit in qubits {
+        machine.rx(2. * beta, qubit);
+    }
+}
+
+///
+/// Prepares the QAOA state for `parameters` from $|0\rangle$ on `qubits`.
+///
+/// # Panics
+///
+/// Panics if the number of `parameters` is odd.
+///
+pub fn prepare<M>(machine: &mut M, problem: &Ising, parameters: &[f64], qubits: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    assert!(
+        parameters.len().is_multiple_of(2),
+        "the parameters must be pairs of gamma and beta, but {} are given",
+        parameters.len()
+    );
+
+    for qubit in &qubits[..problem.qubits_len] {
+        machine.H(qubit);
+    }
+    for layer in parameters.chunks(2) {
+        cost_layer(machine, problem, layer[0], qubits);
+        mixer_layer(machine, layer[1], &qubits[..problem.qubits_len]);
+    }
+}
+
+///
+/// Prepares the QAOA state for `parameters` on `sim` and returns the expected cost.
+///
+pub fn expectation(sim: &mut QuantumSimulator, problem: &Ising, parameters: &[f64]) -> f64 {
+    let ansatz = ansatz(problem);
+    vqe::energy(
+        sim,
+        &ansatz,
+        &problem.hamiltonian(),
+        parameters,
+        Estimation::Exact,
+    )
+}
+
+///
+/// Prepares the QAOA state for `parameters` on `sim` and returns the expected weight of the
+/// cut of the graph with `edges`.
+///
+pub fn expected_cut(
+    sim: &mut QuantumSimulator,
+    vertices: usize,
+    edges: &[(usize, usize, f64)],
+    parameters: &[f64],
+) -> f64 {
+    -expectation(sim, &Ising::maxcut(vertices, edges), parameters)
+}
+
+///
+/// Minimizes the expected cost starting from the parameters `initial`,
+/// whose length is twice the number of the layers.
+///
+/// `sim` is left in the optimal QAOA state.
+///
+pub fn solve<O>(
+    sim: &mut QuantumSimulator,
+    problem: &Ising,
+    optimizer: &mut O,
+    initial: &[f64],
+) -> QaoaResult
+where
+    O: Optimizer,
+{
+    let ansatz = ansatz(problem);
+    let result = vqe::solve(
+        sim,
+        &ansatz,
+        &problem.hamiltonian(),
+        optimizer,
+        initial,
+        Estimation::Exact,
+    );
+
+    let (most_probable, _) =
+        sim.states()
+            .iter()
+            .enumerate()
+            .fold((0, 0.), |(best, max), (i, amplitude)| {
+                if amplitude.norm_sqr() > max {
+                    (i, amplitude.norm_sqr())
+                } else {
+                    (best, max)
+                }
+            });
+    let solution = (0..problem.qubits_len)
+        .map(|i| most_probable >> i & 1 == 1)
+        .collect();
+
+    QaoaResult {
+        energy: result.energy,
+        parameters: result.parameters,
+        solution,
+        evaluations: result.evaluations,
+    }
+}
+
+fn ansatz<'a>(problem: &'a Ising) -> impl Fn(&mut QuantumSimulator, &[Qubit], &[f64]) + 'a {
+    move |sim: &mut QuantumSimulator, qubits: &[Qubit], parameters: &[f64]| {
+        prepare(sim, problem, parameters, qubits)
+    }
+}
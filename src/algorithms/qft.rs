@@ -0,0 +1,143 @@
+//!
+//! A module for the quantum Fourier transform.
+//!
+//! The qubits are interpreted as an integer in little-endian, i.e. `qubits[0]` is the least
+//! significant bit. For $N = 2^n$, the QFT maps
+//!
+//! $$
+//! |x\rangle \mapsto \frac{1}{\sqrt{N}} \sum_{y=0}^{N-1} e^{2\pi i x y / N} |y\rangle.
+//! $$
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::algorithms::qft;
+//!
+//! let mut sim = QuantumSimulator::new(3);
+//! let register = sim.get_register();
+//! register.set_integer(&mut sim, 5, Endianness::Little);
+//!
+//! qft::qft(&mut sim, &register);
+//! qft::iqft(&mut sim, &register);
+//!
+//! assert_eq!(register.measure_integer(&mut sim, Endianness::Little), 5);
+//! ```
+//!
+
+use gates::double::DoubleGateApplicator;
+use gates::single::SingleGateApplicator;
+use std::f64::consts::PI;
+use Qubit;
+
+///
+/// Options of the QFT.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QftOptions {
+    ///
+    /// If `Some(m)`, only the controlled rotations by the angles $2\pi / 2^k$ with $k \le m$
+    /// are applied, which gives the approximate QFT.
+    /// The dropped rotations are by the angles of at most $\pi / 2^m$.
+    ///
+    pub approximation: Option<usize>,
+    ///
+    /// Whether the final swaps reversing the order of the qubits are applied.
+    /// Without them, the result is obtained in big-endian.
+    ///
+    pub swaps: bool,
+}
+
+impl Default for QftOptions {
+    fn default() -> QftOptions {
+        QftOptions {
+            approximation: None,
+            swaps: true,
+        }
+    }
+}
+
+impl QftOptions {
+    /// Returns the angle of the rotation between `qubits[control]` and `qubits[target]` if applied.
+    fn rotation(&self, control: usize, target: usize) -> Option<f64> {
+        let k = target - control + 1;
+        if self.approximation.is_some_and(|m| k > m) {
+            None
+        } else {
+            Some(2. * PI / (1u64 << k) as f64)
+        }
+    }
+}
+
+///
+/// Applies the QFT to `qubits`.
+///
+pub fn qft<M>(machine: &mut M, qubits: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    qft_with(machine, qubits, &QftOptions::default());
+}
+
+///
+/// Applies the inverse QFT to `qubits`.
+///
+pub fn iqft<M>(machine: &mut M, qubits: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    iqft_with(machine, qubits, &QftOptions::default());
+}
+
+///
+/// Applies the QFT to `qubits` with the given options.
+///
+pub fn qft_with<M>(machine: &mut M, qubits: &[Qubit], options: &QftOptions)
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    let n = qubits.len();
+    for target in (0..n).rev() {
+        machine.H(&qubits[target]);
+        for control in (0..target).rev() {
+            if let Some(phi) = options.rotation(control, target) {
+                machine.cphase(phi, &qubits[control], &qubits[target]);
+            }
+        }
+    }
+
+    if options.swaps {
+        swap_all(machine, qubits);
+    }
+}
+
+///
+/// Applies the inverse QFT to `qubits` with the given options.
+///
+/// This is the exact inverse of [qft_with](fn.qft_with.html) with the same options.
+///
+pub fn iqft_with<M>(machine: &mut M, qubits: &[Qubit], options: &QftOptions)
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    if options.swaps {
+        swap_all(machine, qubits);
+    }
+
+    let n = qubits.len();
+    for target in 0..n {
+        for control in 0..target {
+            if let Some(phi) = options.rotation(control, target) {
+                machine.cphase(-phi, &qubits[control], &qubits[target]);
+            }
+        }
+        machine.H(&qubits[target]);
+    }
+}
+
+fn swap_all<M: DoubleGateApplicator>(machine: &mut M, qubits: &[Qubit]) {
+    let n = qubits.len();
+    for i in 0..n / 2 {
+        machine.SWAP(&qubits[i], &qubits[n - 1 - i]);
+    }
+}
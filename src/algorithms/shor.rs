@@ -0,0 +1,423 @@
+//!
+//! A module for Shor's factoring algorithm.
+//!
+//! The modular arithmetic follows S. Beauregard, "Circuit for Shor's algorithm using 2n+3
+//! qubits" ([arXiv:quant-ph/0205095](https://arxiv.org/abs/quant-ph/0205095)): the additions
+//! of constants are done in the Fourier space by controlled phases, and the order is found by
+//! the phase estimation with a single control qubit, which is measured and recycled for each
+//! bit of the phase.
+//!
+//! The integers in the registers are little-endian, i.e. the first qubit is the least
+//! significant bit.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::algorithms::shor;
+//!
+//! assert_eq!(shor::factor(15, 1), Some((3, 5)));
+//! ```
+//!
+
+use algorithms::qft;
+use arithmetic::adder::{self, FOURIER};
+use circuit::{Circuit, Operation};
+use gates::double::DoubleGateApplicator;
+use gates::multi::MultiGateApplicator;
+use gates::single::SingleGateApplicator;
+use gates::triple::TripleGateApplicator;
+use rand::Rng;
+use simulator::simulator::seeded_rng;
+use simulator::QuantumSimulator;
+use std::f64::consts::PI;
+use {MeasuredResult, QuantumMachine, Qubit};
+
+/// The number of random bases tried in [factor](fn.factor.html).
+const MAX_ATTEMPTS: usize = 32;
+
+///
+/// Adds `value` modulo `modulus` to `b` in the Fourier space if all `controls` are $|1\rangle$.
+///
+/// `b` has one more qubit than needed for `modulus` and holds an integer less than `modulus`.
+/// `flag` must be $|0\rangle$ and is restored.
+///
+fn add_mod_fourier<M>(
+    machine: &mut M,
+    value: u64,
+    modulus: u64,
+    b: &[Qubit],
+    controls: &[Qubit],
+    flag: &Qubit,
+) where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    let msb = &b[b.len() - 1];
+
+    adder::phase_add_constant(machine, value as i64, b, controls);
+    adder::phase_add_constant(machine, -(modulus as i64), b, &[]);
+    // The sign bit tells whether the modulus must be added back.
+    qft::iqft_with(machine, b, &FOURIER);
+    machine.CNOT(msb, flag);
+    qft::qft_with(machine, b, &FOURIER);
+    adder::phase_add_constant(machine, modulus as i64, b, &[*flag]);
+
+    // Uncomputes the flag, which is set iff the result is less than `value`.
+    adder::phase_add_constant(machine, -(value as i64), b, controls);
+    qft::iqft_with(machine, b, &FOURIER);
+    machine.X(msb);
+    machine.CNOT(msb, flag);
+    machine.X(msb);
+    qft::qft_with(machine, b, &FOURIER);
+    adder::phase_add_constant(machine, value as i64, b, controls);
+}
+
+///
+/// Adds `a * x` modulo `modulus` to `b` if `control` is $|1\rangle$.
+///
+fn multiply_add_mod<M>(
+    machine: &mut M,
+    a: u64,
+    modulus: u64,
+    control: &Qubit,
+    x: &[Qubit],
+    b: &[Qubit],
+    flag: &Qubit,
+) where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    qft::qft_with(machine, b, &FOURIER);
+    let mut term = a % modulus;
+    for qubit in x {
+        add_mod_fourier(machine, term, modulus, b, &[*control, *qubit], flag);
+        term = term * 2 % modulus;
+    }
+    qft::iqft_with(machine, b, &FOURIER);
+}
+
+///
+/// Multiplies `x` by `a` modulo `modulus` if `control` is $|1\rangle$.
+///
+/// `x` must hold an integer less than `modulus`, and `ancillas` must be $n + 2$ qubits
+/// in $|0\rangle$, where $n$ is the length of `x`. The ancillas are restored.
+///
+/// # Panics
+///
+/// Panics if `modulus` is larger than $2^n$, `a` is not coprime to `modulus`
+/// or the number of the ancillas is wrong.
+///
+pub fn modular_multiplication<M>(
+    machine: &mut M,
+    a: u64,
+    modulus: u64,
+    control: &Qubit,
+    x: &[Qubit],
+    ancillas: &[Qubit],
+) where
+    M: SingleGateApplicator + DoubleGateApplicator + TripleGateApplicator + MultiGateApplicator,
+{
+    let n = x.len();
+    assert!(
+        modulus > 1 && modulus <= 1 << n,
+        "the modulus {} does not fit in {} qubits",
+        modulus,
+        n
+    );
+    assert_eq!(ancillas.len(), n + 2, "{} ancillas are required", n + 2);
+    let inverse = mod_inverse(a, modulus)
+        .unwrap_or_else(|| panic!("{} is not coprime to the modulus {}", a, modulus));
+
+    let (b, flag) = ancillas.split_at(n + 1);
+    let flag = &flag[0];
+
+    // |x>|0> -> |x>|ax> -> |ax>|x> -> |ax>|0>
+    multiply_add_mod(machine, a, modulus, control, x, b, flag);
+    for (qubit1, qubit2) in x.iter().zip(b) {
+        machine.CSWAP(control, qubit1, qubit2);
+    }
+
+    let mut circuit = Circuit::new(2 * n + 3, 0);
+    let qubits = circuit.get_qubits();
+    multiply_add_mod(
+        &mut circuit,
+        inverse,
+        modulus,
+        &qubits[0],
+        &qubits[1..n + 1],
+        &qubits[n + 1..2 * n + 2],
+        &qubits[2 * n + 2],
+    );
+    let mut targets = vec![*control];
+    targets.extend_from_slice(x);
+    targets.extend_from_slice(ancillas);
+    for instruction in circuit.inverse().instructions() {
+        if let Operation::Gate(ref gate) = instruction.operation {
+            gate.apply(machine, &targets);
+        }
+    }
+}
+
+///
+/// Multiplies `x` by $a^e$ modulo `modulus`, where $e$ is the integer in `exponent`.
+///
+/// The requirements on `x` and `ancillas` are the same as
+/// [modular_multiplication](fn.modular_multiplication.html).
+///
+pub fn modular_exponentiation<M>(
+    machine: &mut M,
+    a: u64,
+    modulus: u64,
+    exponent: &[Qubit],
+    x: &[Qubit],
+    ancillas: &[Qubit],
+) where
+    M: SingleGateApplicator + DoubleGateApplicator + TripleGateApplicator + MultiGateApplicator,
+{
+    let mut power = a % modulus;
+    for qubit in exponent {
+        modular_multiplication(machine, power, modulus, qubit, x, ancillas);
+        power = power * power % modulus;
+    }
+}
+
+///
+/// Finds the multiplicative order of `a` modulo `modulus` on a quantum simulator.
+///
+/// A simulator with $2n + 3$ qubits is used, where $n$ is the number of bits of `modulus`,
+/// and its measurements are reproducible by `seed`.
+/// The phase is estimated to $2n$ bits, and the order is obtained from it by
+/// [order_from_phase](fn.order_from_phase.html).
+/// Returns `None` if the measured phase does not tell the order.
+///
+/// # Panics
+///
+/// Panics if `a` is not coprime to `modulus`.
+///
+pub fn find_order(a: u64, modulus: u64, seed: u64) -> Option<u64> {
+    let n = 64 - modulus.leading_zeros() as usize;
+    let precision = 2 * n;
+
+    let mut sim = QuantumSimulator::with_seed(2 * n + 3, seed);
+    let qubits = sim.get_qubits();
+    let control = &qubits[0];
+    let x = &qubits[1..n + 1];
+    let ancillas = &qubits[n + 1..];
+    sim.X(&x[0]);
+
+    // The inverse QFT is done semiclassically from the least significant bit,
+    // which is given by the largest power of the unitary.
+    let mut measured = 0u64;
+    for j in 0..precision {
+        sim.H(control);
+        let power = pow_mod(a, 1 << (precision - 1 - j), modulus);
+        // The multiplication by one is the identity.
+        if power != 1 {
+            modular_multiplication(&mut sim, power, modulus, control, x, ancillas);
+        }
+
+        let correction = (0..j)
+            .filter(|l| (measured >> l) & 1 == 1)
+            .map(|l| 2. * PI / (1u64 << (j - l + 1)) as f64)
+            .sum::<f64>();
+        sim.phase(-correction, control);
+        sim.H(control);
+
+        if sim.measure(control) == MeasuredResult::One {
+            measured |= 1 << j;
+            sim.X(control);
+        }
+    }
+
+    order_from_phase(measured, precision as u32, a, modulus)
+}
+
+///
+/// Finds the order of `a` modulo `modulus` from the phase `measured` / $2^{precision}$
+/// by the continued fraction expansion.
+///
+/// Returns `None` if no convergent gives the order.
+///
+pub fn order_from_phase(measured: u64, precision: u32, a: u64, modulus: u64) -> Option<u64> {
+    // The convergents with the denominator 1 tell nothing.
+    for (_, denominator) in convergents(measured, 1 << precision)
+        .into_iter()
+        .filter(|&(_, denominator)| denominator > 1)
+    {
+        if denominator >= modulus {
+            break;
+        }
+
+        // The denominator may be a divisor of the order if the numerator shares a factor.
+        let mut candidate = denominator;
+        while candidate < modulus {
+            if pow_mod(a, candidate, modulus) == 1 {
+                return Some(minimize_order(a, candidate, modulus));
+            }
+            candidate += denominator;
+        }
+    }
+
+    None
+}
+
+///
+/// Returns the convergents of the continued fraction of `numerator` / `denominator`
+/// as the pairs of the numerators and the denominators.
+///
+/// # Examples
+///
+/// ```
+/// use rusq::algorithms::shor;
+///
+/// // 13 / 8 = 1 + 1 / (1 + 1 / (1 + 1 / (1 + 1 / 2)))
+/// assert_eq!(
+///     shor::convergents(13, 8),
+///     vec![(1, 1), (2, 1), (3, 2), (5, 3), (13, 8)]
+/// );
+/// ```
+///
+pub fn convergents(numerator: u64, denominator: u64) -> Vec<(u64, u64)> {
+    let mut res = vec![];
+    let (mut p, mut q) = (numerator, denominator);
+    let (mut h0, mut h1) = (0u64, 1u64);
+    let (mut k0, mut k1) = (1u64, 0u64);
+
+    while q != 0 {
+        let a = p / q;
+        let h = a * h1 + h0;
+        let k = a * k1 + k0;
+        res.push((h, k));
+
+        h0 = h1;
+        h1 = h;
+        k0 = k1;
+        k1 = k;
+        let r = p % q;
+        p = q;
+        q = r;
+    }
+
+    res
+}
+
+///
+/// Factors `n` into two non-trivial factors with Shor's algorithm.
+///
+/// The even numbers and the prime powers are factored classically. Otherwise, random bases
+/// are tried until the order finding succeeds. All the random choices, including
+/// the measurements on the simulators, are determined by `seed`.
+///
+/// Returns the factors in ascending order, or `None` if `n` is less than 4, a prime,
+/// or the factoring fails too many times.
+///
+pub fn factor(n: u64, seed: u64) -> Option<(u64, u64)> {
+    if n < 4 || is_prime(n) {
+        return None;
+    }
+    if n.is_multiple_of(2) {
+        return Some((2, n / 2));
+    }
+    if let Some(base) = perfect_power_base(n) {
+        return Some((base, n / base));
+    }
+
+    let mut rng = seeded_rng(seed);
+    for _ in 0..MAX_ATTEMPTS {
+        let a = rng.gen_range(2, n);
+        let divisor = gcd(a, n);
+        if divisor > 1 {
+            return Some(sorted(divisor, n / divisor));
+        }
+
+        let order = match find_order(a, n, rng.gen()) {
+            Some(order) if order.is_multiple_of(2) => order,
+            _ => continue,
+        };
+        let half = pow_mod(a, order / 2, n);
+        if half == n - 1 {
+            continue;
+        }
+
+        let divisor = gcd(half - 1, n);
+        if divisor > 1 && divisor < n {
+            return Some(sorted(divisor, n / divisor));
+        }
+    }
+
+    None
+}
+
+fn sorted(a: u64, b: u64) -> (u64, u64) {
+    (a.min(b), a.max(b))
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn pow_mod(base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut res = 1 % modulus;
+    let mut base = base % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            res = res * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    res
+}
+
+fn mod_inverse(a: u64, modulus: u64) -> Option<u64> {
+    let (mut r0, mut r1) = (modulus as i64, (a % modulus) as i64);
+    let (mut t0, mut t1) = (0i64, 1i64);
+    while r1 != 0 {
+        let q = r0 / r1;
+        let r = r0 - q * r1;
+        r0 = r1;
+        r1 = r;
+        let t = t0 - q * t1;
+        t0 = t1;
+        t1 = t;
+    }
+
+    if r0 == 1 {
+        Some(t0.rem_euclid(modulus as i64) as u64)
+    } else {
+        None
+    }
+}
+
+/// Reduces a multiple of the order of `a` to the order itself.
+fn minimize_order(a: u64, mut order: u64, modulus: u64) -> u64 {
+    let mut p = 2;
+    let mut rest = order;
+    while rest > 1 {
+        if rest.is_multiple_of(p) {
+            rest /= p;
+            if pow_mod(a, order / p, modulus) == 1 {
+                order /= p;
+            }
+        } else {
+            p += 1;
+        }
+    }
+    order
+}
+
+fn is_prime(n: u64) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+}
+
+fn perfect_power_base(n: u64) -> Option<u64> {
+    (2..64u32).take_while(|&k| 1u64 << k <= n).find_map(|k| {
+        let root = (n as f64).powf(1. / k as f64).round() as u64;
+        (root.saturating_sub(1)..=root + 1).find(|&b| b > 1 && b.checked_pow(k) == Some(n))
+    })
+}
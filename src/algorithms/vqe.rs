@@ -0,0 +1,144 @@
+//!
+//! A module for the variational quantum eigensolver (VQE).
+//!
+//! The VQE minimizes the energy $\langle\psi(\theta)|H|\psi(\theta)\rangle$ of the trial state
+//! prepared by an [Ansatz](trait.Ansatz.html) over the parameters $\theta$ with a classical
+//! [Optimizer](../../optimize/trait.Optimizer.html).
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::algorithms::vqe::{self, Estimation};
+//! use rusq::observable::Hamiltonian;
+//! use rusq::optimize::NelderMead;
+//!
+//! // The ground energy of Z + X is -√2.
+//! let hamiltonian = Hamiltonian::from_terms(&[(1., "Z"), (1., "X")]);
+//! let ansatz = |sim: &mut QuantumSimulator, qubits: &[Qubit], theta: &[f64]| {
+//!     sim.ry(theta[0], &qubits[0]);
+//! };
+//!
+//! let mut sim = QuantumSimulator::new(1);
+//! let result = vqe::solve(
+//!     &mut sim,
+//!     &ansatz,
+//!     &hamiltonian,
+//!     &mut NelderMead::default(),
+//!     &[0.],
+//!     Estimation::Exact,
+//! );
+//!
+//! assert!((result.energy + 2f64.sqrt()).abs() < 1e-6);
+//! ```
+//!
+
+use circuit::parameterized::ParameterizedCircuit;
+use observable::Hamiltonian;
+use optimize::Optimizer;
+use simulator::QuantumSimulator;
+use {QuantumMachine, Qubit};
+
+///
+/// A trait for the parameterized preparations of the trial states.
+///
+pub trait Ansatz {
+    ///
+    /// Prepares the trial state for `parameters` on `qubits`, which are all $|0\rangle$.
+    ///
+    fn prepare(&self, sim: &mut QuantumSimulator, qubits: &[Qubit], parameters: &[f64]);
+}
+
+impl<F> Ansatz for F
+where
+    F: Fn(&mut QuantumSimulator, &[Qubit], &[f64]),
+{
+    fn prepare(&self, sim: &mut QuantumSimulator, qubits: &[Qubit], parameters: &[f64]) {
+        self(sim, qubits, parameters);
+    }
+}
+
+impl Ansatz for ParameterizedCircuit {
+    fn prepare(&self, sim: &mut QuantumSimulator, qubits: &[Qubit], parameters: &[f64]) {
+        self.run_on(sim, qubits, parameters);
+    }
+}
+
+///
+/// A type for how the energy is estimated.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Estimation {
+    /// The exact expectation value from the amplitudes
+    Exact,
+    /// The estimate from the given number of measurements for each term
+    Shots(usize),
+}
+
+///
+/// A type for the result of the VQE.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct VqeResult {
+    /// The energy at the optimal parameters
+    pub energy: f64,
+    /// The optimal parameters
+    pub parameters: Vec<f64>,
+    /// The number of the evaluations of the energy
+    pub evaluations: usize,
+}
+
+///
+/// Prepares the trial state for `parameters` on all the qubits of `sim` from $|0\rangle$,
+/// and returns its energy.
+///
+pub fn energy<A>(
+    sim: &mut QuantumSimulator,
+    ansatz: &A,
+    hamiltonian: &Hamiltonian,
+    parameters: &[f64],
+    estimation: Estimation,
+) -> f64
+where
+    A: Ansatz + ?Sized,
+{
+    sim.reset();
+    let qubits = sim.get_qubits();
+    ansatz.prepare(sim, &qubits, parameters);
+
+    match estimation {
+        Estimation::Exact => hamiltonian.expectation(sim, &qubits),
+        Estimation::Shots(shots) => hamiltonian.sample_expectation(sim, &qubits, shots),
+    }
+}
+
+///
+/// Minimizes the energy of `hamiltonian` starting from the parameters `initial`.
+///
+/// The energy in the result is evaluated again at the optimal parameters, so that it is not
+/// biased by the noise of the shots, and `sim` is left in the optimal trial state.
+///
+pub fn solve<A, O>(
+    sim: &mut QuantumSimulator,
+    ansatz: &A,
+    hamiltonian: &Hamiltonian,
+    optimizer: &mut O,
+    initial: &[f64],
+    estimation: Estimation,
+) -> VqeResult
+where
+    A: Ansatz + ?Sized,
+    O: Optimizer,
+{
+    let minimum = optimizer.minimize(
+        |parameters: &[f64]| energy(sim, ansatz, hamiltonian, parameters, estimation),
+        initial,
+    );
+    let energy = energy(sim, ansatz, hamiltonian, &minimum.parameters, estimation);
+
+    VqeResult {
+        energy,
+        parameters: minimum.parameters,
+        evaluations: minimum.evaluations + 1,
+    }
+}
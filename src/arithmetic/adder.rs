@@ -0,0 +1,307 @@
+//!
+//! A module for adders and incrementers.
+//!
+//! The ripple-carry adder is the one by S. A. Cuccaro et al.,
+//! "A new quantum ripple-carry addition circuit"
+//! ([arXiv:quant-ph/0410184](https://arxiv.org/abs/quant-ph/0410184)), and
+//! the Fourier-space adder is the one by T. G. Draper,
+//! "Addition on a quantum computer" ([arXiv:quant-ph/0008033](https://arxiv.org/abs/quant-ph/0008033)).
+//!
+
+use algorithms::qft::{self, QftOptions};
+use gates::double::DoubleGateApplicator;
+use gates::multi;
+use gates::single::SingleGateApplicator;
+use gates::triple::TripleGateApplicator;
+use std::f64::consts::PI;
+use Qubit;
+
+/// The QFT for the Fourier-space arithmetic, where the swaps are not needed.
+pub(crate) const FOURIER: QftOptions = QftOptions {
+    approximation: None,
+    swaps: false,
+};
+
+/// The majority gate of the Cuccaro adder
+fn majority<M>(machine: &mut M, c: &Qubit, b: &Qubit, a: &Qubit)
+where
+    M: DoubleGateApplicator + TripleGateApplicator,
+{
+    machine.CNOT(a, b);
+    machine.CNOT(a, c);
+    machine.CCNOT(c, b, a);
+}
+
+/// The inverse of [majority](fn.majority.html)
+fn unmajority<M>(machine: &mut M, c: &Qubit, b: &Qubit, a: &Qubit)
+where
+    M: DoubleGateApplicator + TripleGateApplicator,
+{
+    machine.CCNOT(c, b, a);
+    machine.CNOT(a, c);
+    machine.CNOT(a, b);
+}
+
+/// The "unmajority and add" gate of the Cuccaro adder
+fn unmajority_add<M>(machine: &mut M, c: &Qubit, b: &Qubit, a: &Qubit)
+where
+    M: DoubleGateApplicator + TripleGateApplicator,
+{
+    machine.CCNOT(c, b, a);
+    machine.CNOT(a, c);
+    machine.CNOT(c, b);
+}
+
+///
+/// Computes the carries of `a + b` in place of `a`, so that the last qubit of `a` holds
+/// the carry out.
+///
+pub(crate) fn compute_carries<M>(machine: &mut M, a: &[Qubit], b: &[Qubit], ancilla: &Qubit)
+where
+    M: DoubleGateApplicator + TripleGateApplicator,
+{
+    majority(machine, ancilla, &b[0], &a[0]);
+    for i in 1..a.len() {
+        majority(machine, &a[i - 1], &b[i], &a[i]);
+    }
+}
+
+/// The inverse of [compute_carries](fn.compute_carries.html)
+pub(crate) fn uncompute_carries<M>(machine: &mut M, a: &[Qubit], b: &[Qubit], ancilla: &Qubit)
+where
+    M: DoubleGateApplicator + TripleGateApplicator,
+{
+    for i in (1..a.len()).rev() {
+        unmajority(machine, &a[i - 1], &b[i], &a[i]);
+    }
+    unmajority(machine, ancilla, &b[0], &a[0]);
+}
+
+fn assert_same_length(a: &[Qubit], b: &[Qubit]) {
+    assert!(
+        !a.is_empty() && a.len() == b.len(),
+        "the registers must have the same non-zero length, but have {} and {} qubits",
+        a.len(),
+        b.len()
+    );
+}
+
+///
+/// Adds `a` to `b` with the ripple-carry adder.
+///
+/// If `carry` is given, the carry out is XORed into it.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+///
+pub fn ripple_add<M>(
+    machine: &mut M,
+    a: &[Qubit],
+    b: &[Qubit],
+    ancilla: &Qubit,
+    carry: Option<&Qubit>,
+) where
+    M: DoubleGateApplicator + TripleGateApplicator,
+{
+    assert_same_length(a, b);
+
+    let n = a.len();
+    compute_carries(machine, a, b, ancilla);
+    if let Some(carry) = carry {
+        machine.CNOT(&a[n - 1], carry);
+    }
+    for i in (1..n).rev() {
+        unmajority_add(machine, &a[i - 1], &b[i], &a[i]);
+    }
+    unmajority_add(machine, ancilla, &b[0], &a[0]);
+}
+
+///
+/// Subtracts `a` from `b` with the ripple-carry adder.
+///
+/// If `borrow` is given, whether `a` is larger than `b` is XORed into it.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+///
+pub fn ripple_subtract<M>(
+    machine: &mut M,
+    a: &[Qubit],
+    b: &[Qubit],
+    ancilla: &Qubit,
+    borrow: Option<&Qubit>,
+) where
+    M: SingleGateApplicator + DoubleGateApplicator + TripleGateApplicator,
+{
+    // b - a = ~(~b + a), and ~b + a overflows iff a > b.
+    for qubit in b {
+        machine.X(qubit);
+    }
+    ripple_add(machine, a, b, ancilla, borrow);
+    for qubit in b {
+        machine.X(qubit);
+    }
+}
+
+///
+/// Adds `a` to `b` in the Fourier space.
+///
+/// `a` may be shorter than `b`. No ancillas are needed.
+///
+/// # Panics
+///
+/// Panics if `a` is longer than `b`.
+///
+pub fn draper_add<M>(machine: &mut M, a: &[Qubit], b: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    draper(machine, a, b, 1.);
+}
+
+///
+/// Subtracts `a` from `b` in the Fourier space.
+///
+/// # Panics
+///
+/// Panics if `a` is longer than `b`.
+///
+pub fn draper_subtract<M>(machine: &mut M, a: &[Qubit], b: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    draper(machine, a, b, -1.);
+}
+
+fn draper<M>(machine: &mut M, a: &[Qubit], b: &[Qubit], sign: f64)
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    assert!(
+        a.len() <= b.len(),
+        "{} qubits cannot be added to {} qubits",
+        a.len(),
+        b.len()
+    );
+
+    qft::qft_with(machine, b, &FOURIER);
+    // The qubit `j` in the Fourier space carries the phase of b / 2^(j + 1).
+    for (j, target) in b.iter().enumerate() {
+        for (k, control) in a.iter().enumerate().take(j + 1) {
+            let angle = 2. * PI / (1u64 << (j - k + 1)) as f64;
+            machine.cphase(sign * angle, control, target);
+        }
+    }
+    qft::iqft_with(machine, b, &FOURIER);
+}
+
+///
+/// Adds `value` to `b`, which is in the Fourier space by the QFT without swaps,
+/// if all `controls` are $|1\rangle$.
+///
+/// This is the building block of the Fourier-space arithmetic and can be combined with
+/// [qft_with](../../algorithms/qft/fn.qft_with.html).
+///
+pub fn phase_add_constant<M>(machine: &mut M, value: i64, b: &[Qubit], controls: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    let rotations = b
+        .iter()
+        .enumerate()
+        .map(|(j, qubit)| {
+            let period = 1i64 << (j + 1);
+            (
+                qubit,
+                2. * PI * value.rem_euclid(period) as f64 / period as f64,
+            )
+        })
+        .filter(|&(_, angle)| angle != 0.)
+        .collect::<Vec<_>>();
+
+    match controls.len() {
+        0 => {
+            for (qubit, angle) in rotations {
+                machine.phase(angle, qubit);
+            }
+        }
+        1 => {
+            for (qubit, angle) in rotations {
+                machine.cphase(angle, &controls[0], qubit);
+            }
+        }
+        2 => {
+            // The doubly-controlled phases share the CNOTs between the controls.
+            for &(qubit, angle) in &rotations {
+                machine.cphase(angle / 2., &controls[1], qubit);
+            }
+            machine.CNOT(&controls[0], &controls[1]);
+            for &(qubit, angle) in &rotations {
+                machine.cphase(-angle / 2., &controls[1], qubit);
+            }
+            machine.CNOT(&controls[0], &controls[1]);
+            for &(qubit, angle) in &rotations {
+                machine.cphase(angle / 2., &controls[0], qubit);
+            }
+        }
+        _ => {
+            for (qubit, angle) in rotations {
+                let mut qubits = controls.to_vec();
+                qubits.push(*qubit);
+                multi::mcphase(machine, angle, &qubits);
+            }
+        }
+    }
+}
+
+///
+/// Adds `value` to `b` if all `controls` are $|1\rangle$.
+///
+/// A negative `value` subtracts. No ancillas are needed.
+///
+pub fn add_constant<M>(machine: &mut M, value: i64, b: &[Qubit], controls: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    qft::qft_with(machine, b, &FOURIER);
+    phase_add_constant(machine, value, b, controls);
+    qft::iqft_with(machine, b, &FOURIER);
+}
+
+///
+/// Adds one to `register` if all `controls` are $|1\rangle$.
+///
+/// This is built from multi-controlled NOT gates without ancillas.
+///
+pub fn increment<M>(machine: &mut M, register: &[Qubit], controls: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    // The bit k flips iff all the lower bits are one.
+    for k in (0..register.len()).rev() {
+        flip_if_lower_ones(machine, register, k, controls);
+    }
+}
+
+///
+/// Subtracts one from `register` if all `controls` are $|1\rangle$.
+///
+pub fn decrement<M>(machine: &mut M, register: &[Qubit], controls: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    for k in 0..register.len() {
+        flip_if_lower_ones(machine, register, k, controls);
+    }
+}
+
+fn flip_if_lower_ones<M>(machine: &mut M, register: &[Qubit], k: usize, controls: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    let mut all_controls = controls.to_vec();
+    all_controls.extend_from_slice(&register[..k]);
+    multi::mcx(machine, &all_controls, &register[k]);
+}
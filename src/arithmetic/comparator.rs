@@ -0,0 +1,97 @@
+//!
+//! A module for comparators.
+//!
+//! The results are XORed into the given qubit, so that the comparators can be undone by
+//! applying them again.
+//!
+
+use algorithms::qft;
+use arithmetic::adder::{self, FOURIER};
+use gates::double::DoubleGateApplicator;
+use gates::single::SingleGateApplicator;
+use gates::triple::TripleGateApplicator;
+use Qubit;
+
+///
+/// Flips `result` if `a` is greater than `b`.
+///
+/// This is the carry part of the ripple-carry adder and needs one ancilla.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+///
+pub fn greater_than<M>(machine: &mut M, a: &[Qubit], b: &[Qubit], result: &Qubit, ancilla: &Qubit)
+where
+    M: SingleGateApplicator + DoubleGateApplicator + TripleGateApplicator,
+{
+    assert!(
+        !a.is_empty() && a.len() == b.len(),
+        "the registers must have the same non-zero length, but have {} and {} qubits",
+        a.len(),
+        b.len()
+    );
+
+    // a + ~b overflows iff a > b.
+    for qubit in b {
+        machine.X(qubit);
+    }
+    adder::compute_carries(machine, a, b, ancilla);
+    machine.CNOT(&a[a.len() - 1], result);
+    adder::uncompute_carries(machine, a, b, ancilla);
+    for qubit in b {
+        machine.X(qubit);
+    }
+}
+
+///
+/// Flips `result` if `a` is less than `b`.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+///
+pub fn less_than<M>(machine: &mut M, a: &[Qubit], b: &[Qubit], result: &Qubit, ancilla: &Qubit)
+where
+    M: SingleGateApplicator + DoubleGateApplicator + TripleGateApplicator,
+{
+    greater_than(machine, b, a, result, ancilla);
+}
+
+///
+/// Flips `result` if `a` is less than `value`.
+///
+/// The comparison is done by a subtraction in the Fourier space, where `ancilla` works as
+/// the sign bit of `a`.
+///
+/// # Panics
+///
+/// Panics if `value` is larger than $2^n$ for the register of $n$ qubits.
+///
+pub fn less_than_constant<M>(
+    machine: &mut M,
+    a: &[Qubit],
+    value: u64,
+    result: &Qubit,
+    ancilla: &Qubit,
+) where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    assert!(
+        a.len() < 64 && value <= 1 << a.len(),
+        "{} cannot be compared with a register of {} qubits",
+        value,
+        a.len()
+    );
+
+    let mut extended = a.to_vec();
+    extended.push(*ancilla);
+
+    qft::qft_with(machine, &extended, &FOURIER);
+    adder::phase_add_constant(machine, -(value as i64), &extended, &[]);
+    qft::iqft_with(machine, &extended, &FOURIER);
+    machine.CNOT(ancilla, result);
+    qft::qft_with(machine, &extended, &FOURIER);
+    adder::phase_add_constant(machine, value as i64, &extended, &[]);
+    qft::iqft_with(machine, &extended, &FOURIER);
+}
@@ -0,0 +1,37 @@
+//!
+//! A module for reversible arithmetic on qubit registers.
+//!
+//! The registers are little-endian, i.e. the first qubit is the least significant bit, and
+//! the arithmetic on a register of $n$ qubits is modulo $2^n$.
+//! The ancilla qubits must be $|0\rangle$ and are restored.
+//!
+//! Two families of operations are provided:
+//!
+//! * The ripple-carry operations are built from X, CNOT and CCNOT, so that they also work as
+//!   classical reversible logic on the computational basis.
+//! * The Fourier-space operations are built from the QFT and controlled phases, and need
+//!   fewer ancillas.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::arithmetic::adder;
+//!
+//! let mut sim = QuantumSimulator::new(7);
+//! let register = sim.get_register();
+//! let a = register.slice(0..3);
+//! let b = register.slice(3..6);
+//! let ancilla = &register[6];
+//! a.set_integer(&mut sim, 3, Endianness::Little);
+//! b.set_integer(&mut sim, 6, Endianness::Little);
+//!
+//! adder::ripple_add(&mut sim, &a, &b, ancilla, None);
+//!
+//! assert_eq!(b.measure_integer(&mut sim, Endianness::Little), (3 + 6) % 8);
+//! ```
+//!
+
+pub mod adder;
+pub mod comparator;
+pub mod multiplier;
@@ -0,0 +1,59 @@
+//!
+//! A module for multipliers.
+//!
+//! The products are accumulated into the output register in the Fourier space, so that
+//! no ancillas are needed.
+//!
+
+use algorithms::qft;
+use arithmetic::adder::{self, FOURIER};
+use gates::double::DoubleGateApplicator;
+use gates::multi;
+use gates::single::SingleGateApplicator;
+use std::f64::consts::PI;
+use Qubit;
+
+///
+/// Adds `a * b` to `product`.
+///
+/// `a` and `b` must be different registers.
+///
+pub fn multiply<M>(machine: &mut M, a: &[Qubit], b: &[Qubit], product: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    qft::qft_with(machine, product, &FOURIER);
+    // The bits a_i b_k add 2^(i + k), which only affects the qubits j >= i + k.
+    for (i, x) in a.iter().enumerate() {
+        for (k, y) in b.iter().enumerate() {
+            for (j, target) in product.iter().enumerate().skip(i + k) {
+                let angle = 2. * PI / (1u64 << (j - i - k + 1)) as f64;
+                multi::mcphase(machine, angle, &[*x, *y, *target]);
+            }
+        }
+    }
+    qft::iqft_with(machine, product, &FOURIER);
+}
+
+///
+/// Adds `value * x` to `product` if all `controls` are $|1\rangle$.
+///
+pub fn multiply_constant<M>(
+    machine: &mut M,
+    value: u64,
+    x: &[Qubit],
+    product: &[Qubit],
+    controls: &[Qubit],
+) where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    qft::qft_with(machine, product, &FOURIER);
+    for (i, qubit) in x.iter().enumerate().take(product.len()) {
+        let mut all_controls = controls.to_vec();
+        all_controls.push(*qubit);
+        // Only the residue modulo 2^len(product) matters, so the wrapping is harmless.
+        let shifted = value.wrapping_shl(i as u32) as i64;
+        adder::phase_add_constant(machine, shifted, product, &all_controls);
+    }
+    qft::iqft_with(machine, product, &FOURIER);
+}
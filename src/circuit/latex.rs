@@ -0,0 +1,142 @@
+//!
+//! A module for exporting circuits as LaTeX.
+//!
+//! The output is a `quantikz` environment, which requires the
+//! [quantikz](https://ctan.org/pkg/quantikz) package of version 1.0 or later.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::circuit::{latex, Circuit};
+//!
+//! let mut circuit = Circuit::new(2, 1);
+//! let qubits = circuit.get_qubits();
+//! let clbits = circuit.get_clbits();
+//! circuit.H(&qubits[0]);
+//! circuit.CNOT(&qubits[0], &qubits[1]);
+//! circuit.measure(&qubits[1], &clbits[0]);
+//!
+//! assert_eq!(
+//!     latex::quantikz(&circuit),
+//!     [
+//!         r"\begin{quantikz}",
+//!         r"\lstick{$q_{0}$} & \gate{H} & \ctrl{1} & & \\",
+//!         r"\lstick{$q_{1}$} & & \targ{} & \meter{} \wire[d][1]{c} & \\",
+//!         r"\lstick{$c_{0}$} \setwiretype{c} & & & &",
+//!         r"\end{quantikz}",
+//!     ].join("\n")
+//! );
+//! ```
+//!
+
+use super::layout::{self, ClassicalElement, Element};
+use super::Circuit;
+use MeasuredResult;
+
+///
+/// Exports the circuit as a `quantikz` environment.
+///
+pub fn quantikz(circuit: &Circuit) -> String {
+    let qubits_len = circuit.qubits_len;
+    let rows = qubits_len + circuit.clbits_len;
+    let mut table = (0..rows)
+        .map(|row| {
+            if row < qubits_len {
+                vec![format!(r"\lstick{{$q_{{{}}}$}}", row)]
+            } else {
+                vec![format!(
+                    r"\lstick{{$c_{{{}}}$}} \setwiretype{{c}}",
+                    row - qubits_len
+                )]
+            }
+        })
+        .collect::<Vec<_>>();
+
+    for instructions in layout::columns(circuit) {
+        let mut cells = vec![String::new(); rows];
+
+        for instruction in instructions {
+            let (_, last, last_qubit) = layout::span(circuit, instruction);
+            let elements = layout::elements(&instruction.operation);
+            let first_qubit = elements.iter().map(|&(row, _)| row).min().unwrap();
+            let quantum_length = last_qubit - first_qubit;
+
+            for (row, element) in elements {
+                let down = if row == first_qubit && quantum_length > 0 {
+                    Some(quantum_length)
+                } else {
+                    None
+                };
+
+                cells[row] = match (element, down) {
+                    (Element::Control, Some(d)) => format!(r"\ctrl{{{}}}", d),
+                    (Element::Control, None) => r"\control{}".to_owned(),
+                    (Element::Swap, Some(d)) => format!(r"\swap{{{}}}", d),
+                    (Element::Swap, None) => r"\targX{}".to_owned(),
+                    (Element::Target, d) => with_wire(r"\targ{}".to_owned(), d),
+                    (Element::Box(label), d) => {
+                        with_wire(format!(r"\gate{{{}}}", latex_label(&label)), d)
+                    }
+                    (Element::Meter, d) => with_wire(r"\meter{}".to_owned(), d),
+                };
+            }
+
+            if last > last_qubit {
+                cells[last_qubit].push_str(&format!(r" \wire[d][{}]{{c}}", last - last_qubit));
+            }
+
+            for (clbit, element) in layout::classical_elements(instruction) {
+                cells[qubits_len + clbit] = match element {
+                    ClassicalElement::Write => String::new(),
+                    ClassicalElement::Condition(MeasuredResult::One) => r"\control{}".to_owned(),
+                    ClassicalElement::Condition(MeasuredResult::Zero) => r"\ocontrol{}".to_owned(),
+                };
+            }
+        }
+
+        for (row, cell) in cells.into_iter().enumerate() {
+            table[row].push(cell);
+        }
+    }
+
+    let mut lines = vec![r"\begin{quantikz}".to_owned()];
+    for (row, cells) in table.iter().enumerate() {
+        let mut line = cells[0].clone();
+        // An empty cell at the end extends the wire after the last gate.
+        for cell in cells[1..].iter().chain(Some(&String::new())) {
+            line.push_str(" &");
+            if !cell.is_empty() {
+                line.push(' ');
+                line.push_str(cell);
+            }
+        }
+        if row + 1 < rows {
+            line.push_str(r" \\");
+        }
+        lines.push(line);
+    }
+    lines.push(r"\end{quantikz}".to_owned());
+
+    lines.join("\n")
+}
+
+fn with_wire(cell: String, down: Option<usize>) -> String {
+    match down {
+        Some(d) => format!(r"{} \wire[d][{}]{{q}}", cell, d),
+        None => cell,
+    }
+}
+
+fn latex_label(label: &str) -> String {
+    // The square roots of the gates, e.g. √SWAP
+    if let Some(name) = label.strip_prefix('√') {
+        return format!(r"\sqrt{{\mathrm{{{}}}}}", name);
+    }
+    label
+        .replace("Rzz(", "R_{zz}(")
+        .replace("Rx(", "R_x(")
+        .replace("Ry(", "R_y(")
+        .replace("Rz(", "R_z(")
+        .replace("π", r"\pi")
+}
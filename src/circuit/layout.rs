@@ -0,0 +1,198 @@
+//!
+//! Layout shared by the circuit renderers.
+//!
+//! The rows of a diagram are the qubits followed by the classical bits.
+//!
+
+use super::{Circuit, Condition, Gate, Instruction, Operation};
+use gates::single::SQNOT;
+use std::f64::consts::PI;
+use MeasuredResult;
+
+/// A symbol drawn on a qubit wire.
+pub(super) enum Element {
+    /// A box with the label
+    Box(String),
+    /// A control dot
+    Control,
+    /// The target of a NOT gate
+    Target,
+    /// An end of a SWAP gate
+    Swap,
+    /// A measurement meter
+    Meter,
+}
+
+/// A symbol drawn on a classical wire.
+pub(super) enum ClassicalElement {
+    /// The result of a measurement is written.
+    Write,
+    /// The operation is conditioned on the bit having the value.
+    Condition(MeasuredResult),
+}
+
+/// Returns the symbols of the operation on the qubit rows.
+pub(super) fn elements(operation: &Operation) -> Vec<(usize, Element)> {
+    let gate = match *operation {
+        Operation::Gate(ref gate) => gate,
+        Operation::Measure(ref qubit, _) => return vec![(qubit.index, Element::Meter)],
+    };
+
+    let label = |s: &str| Element::Box(s.to_owned());
+    // The qubits of a unitary gate are numbered from the most significant one of the matrix.
+    let unitary = |k: usize| Element::Box(format!("U[{}]", k));
+    match *gate {
+        Gate::H(q) => vec![(q.index, label("H"))],
+        Gate::X(q) => vec![(q.index, label("X"))],
+        Gate::Y(q) => vec![(q.index, label("Y"))],
+        Gate::Z(q) => vec![(q.index, label("Z"))],
+        Gate::ID(q) => vec![(q.index, label("I"))],
+        Gate::Phase(phi, q) => vec![(q.index, Element::Box(phase_label(phi)))],
+        Gate::RX(theta, q) => vec![(q.index, Element::Box(rotation_label("Rx", theta)))],
+        Gate::RY(theta, q) => vec![(q.index, Element::Box(rotation_label("Ry", theta)))],
+        Gate::RZ(theta, q) => vec![(q.index, Element::Box(rotation_label("Rz", theta)))],
+        Gate::Single(ref m, q) if *m == SQNOT.matrix => vec![(q.index, label("√NOT"))],
+        Gate::Single(_, q) => vec![(q.index, label("U"))],
+        Gate::CNOT(c, t) => vec![(c.index, Element::Control), (t.index, Element::Target)],
+        Gate::SWAP(q1, q2) => vec![(q1.index, Element::Swap), (q2.index, Element::Swap)],
+        Gate::SQSWAP(q1, q2) => vec![(q1.index, label("√SWAP")), (q2.index, label("√SWAP"))],
+        Gate::RZZ(theta, q1, q2) => {
+            let label = rotation_label("Rzz", theta);
+            vec![(q1.index, Element::Box(label.clone())), (q2.index, Element::Box(label))]
+        }
+        Gate::CPhase(phi, c, t) => vec![
+            (c.index, Element::Control),
+            (t.index, Element::Box(phase_label(phi))),
+        ],
+        Gate::Double(_, q1, q2) => vec![(q1.index, unitary(0)), (q2.index, unitary(1))],
+        Gate::CCNOT(c1, c2, t) => vec![
+            (c1.index, Element::Control),
+            (c2.index, Element::Control),
+            (t.index, Element::Target),
+        ],
+        Gate::CSWAP(c, q1, q2) => vec![
+            (c.index, Element::Control),
+            (q1.index, Element::Swap),
+            (q2.index, Element::Swap),
+        ],
+        Gate::Triple(_, q1, q2, q3) => vec![
+            (q1.index, unitary(0)),
+            (q2.index, unitary(1)),
+            (q3.index, unitary(2)),
+        ],
+        Gate::Multi(_, ref qs) => qs
+            .iter()
+            .enumerate()
+            .map(|(k, q)| (q.index, unitary(k)))
+            .collect(),
+    }
+}
+
+/// Returns the symbols of the instruction on the classical rows, indexed by the classical bits.
+pub(super) fn classical_elements(instruction: &Instruction) -> Vec<(usize, ClassicalElement)> {
+    let mut res = vec![];
+    if let Operation::Measure(_, ref clbit) = instruction.operation {
+        res.push((clbit.index, ClassicalElement::Write));
+    }
+
+    match instruction.condition {
+        Some(Condition::Bit(ref clbit, result)) => {
+            res.push((clbit.index, ClassicalElement::Condition(result)))
+        }
+        Some(Condition::Register(ref clbits, value)) => {
+            for (k, clbit) in clbits.iter().enumerate() {
+                let result = if (value >> k) & 1 == 1 {
+                    MeasuredResult::One
+                } else {
+                    MeasuredResult::Zero
+                };
+                res.push((clbit.index, ClassicalElement::Condition(result)));
+            }
+        }
+        None => {}
+    }
+
+    res
+}
+
+///
+/// Returns the range of the rows occupied by the instruction and
+/// the last row of the qubits in it.
+///
+pub(super) fn span(circuit: &Circuit, instruction: &Instruction) -> (usize, usize, usize) {
+    let qubit_rows = elements(&instruction.operation)
+        .into_iter()
+        .map(|(row, _)| row)
+        .collect::<Vec<_>>();
+    let last_qubit = *qubit_rows.iter().max().unwrap();
+    let rows = qubit_rows.into_iter().chain(
+        classical_elements(instruction)
+            .into_iter()
+            .map(|(clbit, _)| circuit.qubits_len + clbit),
+    );
+
+    let (first, last) = rows.fold((usize::MAX, 0), |(first, last), row| {
+        (first.min(row), last.max(row))
+    });
+    (first, last, last_qubit)
+}
+
+///
+/// Packs the instructions into columns.
+///
+/// Each instruction is put into the first column after all the instructions
+/// sharing any row in its span, so that the order of the operations is kept.
+///
+pub(super) fn columns(circuit: &Circuit) -> Vec<Vec<&Instruction>> {
+    let rows = circuit.qubits_len + circuit.clbits_len;
+    let mut next_column = vec![0; rows];
+    let mut columns: Vec<Vec<&Instruction>> = vec![];
+
+    for instruction in &circuit.instructions {
+        let (first, last, _) = span(circuit, instruction);
+        let column = next_column[first..=last].iter().cloned().max().unwrap();
+        if column == columns.len() {
+            columns.push(vec![]);
+        }
+        columns[column].push(instruction);
+        for c in &mut next_column[first..=last] {
+            *c = column + 1;
+        }
+    }
+
+    columns
+}
+
+fn phase_label(phi: f64) -> String {
+    rotation_label("P", phi)
+}
+
+fn rotation_label(name: &str, theta: f64) -> String {
+    format!("{}({})", name, format_angle(theta))
+}
+
+///
+/// Formats an angle, as a fraction of π if possible.
+///
+pub(super) fn format_angle(phi: f64) -> String {
+    let ratio = phi / PI;
+    for denominator in 1..17i64 {
+        let numerator = ratio * denominator as f64;
+        if (numerator - numerator.round()).abs() > 1e-9 {
+            continue;
+        }
+
+        let numerator = numerator.round() as i64;
+        let sign = if numerator < 0 { "-" } else { "" };
+        let numerator = numerator.abs();
+        return match (numerator, denominator) {
+            (0, _) => "0".to_owned(),
+            (1, 1) => format!("{}π", sign),
+            (n, 1) => format!("{}{}π", sign, n),
+            (1, d) => format!("{}π/{}", sign, d),
+            (n, d) => format!("{}{}π/{}", sign, n, d),
+        };
+    }
+
+    format!("{:.4}", phi)
+}
@@ -0,0 +1,606 @@
+//!
+//! A module for recorded quantum circuits.
+//!
+//! A [Circuit](struct.Circuit.html) is an ordered list of operations on its qubits and
+//! classical bits. Since a circuit implements the gate applicator traits, the gates are recorded
+//! in the same way as they are applied to a quantum machine.
+//! A recorded circuit can be executed later on any quantum machine.
+//!
+//! # Examples
+//!
+//! Quantum teleportation as a circuit:
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::circuit::{Circuit, Condition};
+//!
+//! let mut circuit = Circuit::new(3, 2);
+//! let qubits = circuit.get_qubits();
+//! let clbits = circuit.get_clbits();
+//!
+//! // The state to be sent
+//! circuit.X(&qubits[0]);
+//!
+//! circuit.H(&qubits[1]);
+//! circuit.CNOT(&qubits[1], &qubits[2]);
+//! circuit.CNOT(&qubits[0], &qubits[1]);
+//! circuit.H(&qubits[0]);
+//! circuit.measure(&qubits[0], &clbits[0]);
+//! circuit.measure(&qubits[1], &clbits[1]);
+//! circuit.c_if(Condition::Bit(clbits[1], MeasuredResult::One), |c| c.X(&qubits[2]));
+//! circuit.c_if(Condition::Bit(clbits[0], MeasuredResult::One), |c| c.Z(&qubits[2]));
+//!
+//! let mut sim = QuantumSimulator::new(3);
+//! circuit.run(&mut sim);
+//! assert_eq!(sim.measure(&sim.get_qubits()[2]), MeasuredResult::One);
+//! ```
+//!
+
+// Records the named gates by `push_gate`. This is defined before the submodules so that the
+// recorders of the parameterized circuits share it.
+macro_rules! record_gates {
+    ($($gate: ident($($q: ident),*)),*) => {
+        $(
+            #[allow(non_snake_case)]
+            fn $gate(&mut self, $($q: &Qubit),*) {
+                self.push_gate(Gate::$gate($(*$q),*));
+            }
+        )*
+    };
+}
+
+mod layout;
+pub mod latex;
+pub mod parameterized;
+pub mod svg;
+pub mod text;
+
+use gates::double::{DoubleGateApplicator, SQSWAP};
+use gates::multi::MultiGateApplicator;
+use gates::single::SingleGateApplicator;
+use gates::triple::TripleGateApplicator;
+use ndarray::prelude::*;
+use num::complex::Complex;
+use {new_machine_id, Endianness, MeasuredResult, QuantumMachine, Qubit, RusqError};
+
+///
+/// A type for a classical bit in a circuit.
+///
+/// Like [Qubit](../struct.Qubit.html), a classical bit can be obtained only from a circuit.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Clbit {
+    pub(crate) index: usize,
+    pub(crate) circuit: usize,
+}
+
+impl Clbit {
+    /// Returns the index in the circuit.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+///
+/// A type for the gates in a circuit.
+///
+/// For the controlled gates, the control qubits come first as in the gate applicator traits.
+///
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, PartialEq, Clone)]
+pub enum Gate {
+    H(Qubit),
+    X(Qubit),
+    Y(Qubit),
+    Z(Qubit),
+    ID(Qubit),
+    Phase(f64, Qubit),
+    RX(f64, Qubit),
+    RY(f64, Qubit),
+    RZ(f64, Qubit),
+    CNOT(Qubit, Qubit),
+    SWAP(Qubit, Qubit),
+    SQSWAP(Qubit, Qubit),
+    CPhase(f64, Qubit, Qubit),
+    RZZ(f64, Qubit, Qubit),
+    CCNOT(Qubit, Qubit, Qubit),
+    CSWAP(Qubit, Qubit, Qubit),
+    /// An arbitrary operation on a single qubit
+    Single(Array2<Complex<f64>>, Qubit),
+    /// An arbitrary operation on two qubits
+    Double(Array2<Complex<f64>>, Qubit, Qubit),
+    /// An arbitrary operation on three qubits
+    Triple(Array2<Complex<f64>>, Qubit, Qubit, Qubit),
+    /// An arbitrary operation on any number of qubits
+    Multi(Array2<Complex<f64>>, Vec<Qubit>),
+}
+
+impl Gate {
+    /// Returns the qubits on which the gate operates.
+    pub fn qubits(&self) -> Vec<Qubit> {
+        match *self {
+            Gate::H(q)
+            | Gate::X(q)
+            | Gate::Y(q)
+            | Gate::Z(q)
+            | Gate::ID(q)
+            | Gate::Phase(_, q)
+            | Gate::RX(_, q)
+            | Gate::RY(_, q)
+            | Gate::RZ(_, q)
+            | Gate::Single(_, q) => vec![q],
+            Gate::CNOT(q1, q2)
+            | Gate::SWAP(q1, q2)
+            | Gate::SQSWAP(q1, q2)
+            | Gate::CPhase(_, q1, q2)
+            | Gate::RZZ(_, q1, q2)
+            | Gate::Double(_, q1, q2) => vec![q1, q2],
+            Gate::CCNOT(q1, q2, q3) | Gate::CSWAP(q1, q2, q3) | Gate::Triple(_, q1, q2, q3) => {
+                vec![q1, q2, q3]
+            }
+            Gate::Multi(_, ref qs) => qs.clone(),
+        }
+    }
+
+    ///
+    /// Applies the gate to `machine`.
+    ///
+    /// The qubit with index `i` in the gate is mapped to `qubits[i]`.
+    ///
+    pub fn apply<M>(&self, machine: &mut M, qubits: &[Qubit])
+    where
+        M: SingleGateApplicator
+            + DoubleGateApplicator
+            + TripleGateApplicator
+            + MultiGateApplicator,
+    {
+        let q = |qubit: &Qubit| &qubits[qubit.index];
+        match *self {
+            Gate::H(ref q1) => machine.H(q(q1)),
+            Gate::X(ref q1) => machine.X(q(q1)),
+            Gate::Y(ref q1) => machine.Y(q(q1)),
+            Gate::Z(ref q1) => machine.Z(q(q1)),
+            Gate::ID(ref q1) => machine.ID(q(q1)),
+            Gate::Phase(phi, ref q1) => machine.phase(phi, q(q1)),
+            Gate::RX(theta, ref q1) => machine.rx(theta, q(q1)),
+            Gate::RY(theta, ref q1) => machine.ry(theta, q(q1)),
+            Gate::RZ(theta, ref q1) => machine.rz(theta, q(q1)),
+            Gate::CNOT(ref q1, ref q2) => machine.CNOT(q(q1), q(q2)),
+            Gate::SWAP(ref q1, ref q2) => machine.SWAP(q(q1), q(q2)),
+            Gate::SQSWAP(ref q1, ref q2) => machine.SQSWAP(q(q1), q(q2)),
+            Gate::CPhase(phi, ref q1, ref q2) => machine.cphase(phi, q(q1), q(q2)),
+            Gate::RZZ(theta, ref q1, ref q2) => machine.rzz(theta, q(q1), q(q2)),
+            Gate::CCNOT(ref q1, ref q2, ref q3) => machine.CCNOT(q(q1), q(q2), q(q3)),
+            Gate::CSWAP(ref q1, ref q2, ref q3) => machine.CSWAP(q(q1), q(q2), q(q3)),
+            Gate::Single(ref m, ref q1) => machine.apply_single(m, q(q1)),
+            Gate::Double(ref m, ref q1, ref q2) => machine.apply_double(m, q(q1), q(q2)),
+            Gate::Triple(ref m, ref q1, ref q2, ref q3) => {
+                machine.apply_triple(m, q(q1), q(q2), q(q3))
+            }
+            Gate::Multi(ref m, ref qs) => {
+                machine.apply_multi(m, &qs.iter().map(|q1| *q(q1)).collect::<Vec<_>>())
+            }
+        }
+    }
+
+    /// Returns the inverse of the gate.
+    pub fn inverse(&self) -> Gate {
+        match *self {
+            Gate::Phase(phi, q1) => Gate::Phase(-phi, q1),
+            Gate::RX(theta, q1) => Gate::RX(-theta, q1),
+            Gate::RY(theta, q1) => Gate::RY(-theta, q1),
+            Gate::RZ(theta, q1) => Gate::RZ(-theta, q1),
+            Gate::SQSWAP(q1, q2) => Gate::Double(dagger(&SQSWAP.matrix), q1, q2),
+            Gate::CPhase(phi, q1, q2) => Gate::CPhase(-phi, q1, q2),
+            Gate::RZZ(theta, q1, q2) => Gate::RZZ(-theta, q1, q2),
+            Gate::Single(ref m, q1) => Gate::Single(dagger(m), q1),
+            Gate::Double(ref m, q1, q2) => Gate::Double(dagger(m), q1, q2),
+            Gate::Triple(ref m, q1, q2, q3) => Gate::Triple(dagger(m), q1, q2, q3),
+            Gate::Multi(ref m, ref qs) => Gate::Multi(dagger(m), qs.clone()),
+            ref gate => gate.clone(),
+        }
+    }
+}
+
+/// Returns the conjugate transpose of the matrix.
+fn dagger(matrix: &Array2<Complex<f64>>) -> Array2<Complex<f64>> {
+    matrix.t().map(|c| c.conj())
+}
+
+///
+/// A type for the conditions on classical bits to apply an operation.
+///
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Condition {
+    /// Satisfied if the classical bit has the given value.
+    Bit(Clbit, MeasuredResult),
+    ///
+    /// Satisfied if the classical bits, interpreted as a little-endian integer,
+    /// have the given value.
+    ///
+    Register(Vec<Clbit>, usize),
+}
+
+impl Condition {
+    /// Returns `true` if the condition is satisfied by `bits`.
+    pub fn is_satisfied(&self, bits: &ClassicalBits) -> bool {
+        match *self {
+            Condition::Bit(ref clbit, result) => bits.get(clbit) == result,
+            Condition::Register(ref clbits, value) => {
+                bits.value(clbits, Endianness::Little) == value
+            }
+        }
+    }
+
+    fn clbits(&self) -> Vec<Clbit> {
+        match *self {
+            Condition::Bit(clbit, _) => vec![clbit],
+            Condition::Register(ref clbits, _) => clbits.clone(),
+        }
+    }
+}
+
+///
+/// A type for the operations in a circuit.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub enum Operation {
+    /// A unitary gate
+    Gate(Gate),
+    /// A measurement of the qubit whose result is written into the classical bit
+    Measure(Qubit, Clbit),
+}
+
+///
+/// A type for an operation with an optional classical condition.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Instruction {
+    /// The operation
+    pub operation: Operation,
+    /// The operation is performed only if this condition is satisfied.
+    pub condition: Option<Condition>,
+}
+
+///
+/// A type for the values of the classical bits after running a circuit.
+///
+/// All the classical bits are initially [Zero](../enum.MeasuredResult.html#variant.Zero).
+///
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ClassicalBits {
+    bits: Vec<MeasuredResult>,
+}
+
+impl ClassicalBits {
+    fn new(len: usize) -> ClassicalBits {
+        ClassicalBits {
+            bits: vec![MeasuredResult::Zero; len],
+        }
+    }
+
+    /// Returns the value of the classical bit.
+    pub fn get(&self, clbit: &Clbit) -> MeasuredResult {
+        self.bits[clbit.index]
+    }
+
+    /// Interprets the classical bits as an integer.
+    pub fn value(&self, clbits: &[Clbit], endianness: Endianness) -> usize {
+        (0..clbits.len()).fold(0, |acc, k| {
+            let clbit = match endianness {
+                Endianness::Little => &clbits[k],
+                Endianness::Big => &clbits[clbits.len() - 1 - k],
+            };
+            if self.get(clbit) == MeasuredResult::One {
+                acc | (1 << k)
+            } else {
+                acc
+            }
+        })
+    }
+
+    /// Returns the values of all the classical bits in the order of their indices.
+    pub fn as_slice(&self) -> &[MeasuredResult] {
+        &self.bits
+    }
+}
+
+///
+/// A type for a recorded quantum circuit.
+///
+/// See [the module document](index.html) for an example.
+///
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    id: usize,
+    qubits_len: usize,
+    clbits_len: usize,
+    instructions: Vec<Instruction>,
+}
+
+impl Circuit {
+    ///
+    /// Creates a new empty circuit with the given numbers of qubits and classical bits.
+    ///
+    pub fn new(qubits_len: usize, clbits_len: usize) -> Circuit {
+        Circuit {
+            id: new_machine_id(),
+            qubits_len,
+            clbits_len,
+            instructions: vec![],
+        }
+    }
+
+    /// Returns all the qubits in the circuit.
+    pub fn get_qubits(&self) -> Vec<Qubit> {
+        (0..self.qubits_len)
+            .map(|i| Qubit::new(i, self.id))
+            .collect()
+    }
+
+    /// Returns all the classical bits in the circuit.
+    pub fn get_clbits(&self) -> Vec<Clbit> {
+        (0..self.clbits_len)
+            .map(|i| Clbit {
+                index: i,
+                circuit: self.id,
+            })
+            .collect()
+    }
+
+    /// Returns the number of the qubits.
+    pub fn qubits_len(&self) -> usize {
+        self.qubits_len
+    }
+
+    /// Returns the number of the classical bits.
+    pub fn clbits_len(&self) -> usize {
+        self.clbits_len
+    }
+
+    /// Returns the recorded instructions.
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    ///
+    /// Appends an instruction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the instruction refers to a qubit or a classical bit of another circuit.
+    ///
+    pub fn push(&mut self, instruction: Instruction) {
+        match instruction.operation {
+            Operation::Gate(ref gate) => {
+                for qubit in gate.qubits() {
+                    self.check_qubit(&qubit);
+                }
+            }
+            Operation::Measure(ref qubit, ref clbit) => {
+                self.check_qubit(qubit);
+                self.check_clbit(clbit);
+            }
+        }
+        if let Some(ref condition) = instruction.condition {
+            for clbit in condition.clbits() {
+                self.check_clbit(&clbit);
+            }
+        }
+
+        self.instructions.push(instruction);
+    }
+
+    /// Appends an unconditional gate.
+    pub fn push_gate(&mut self, gate: Gate) {
+        self.push(Instruction {
+            operation: Operation::Gate(gate),
+            condition: None,
+        });
+    }
+
+    /// Appends a measurement of `qubit` whose result is written into `clbit`.
+    pub fn measure(&mut self, qubit: &Qubit, clbit: &Clbit) {
+        self.push(Instruction {
+            operation: Operation::Measure(*qubit, *clbit),
+            condition: None,
+        });
+    }
+
+    ///
+    /// Records the operations in `f` so that they are performed only if `condition` is
+    /// satisfied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an operation in `f` already has a condition.
+    ///
+    pub fn c_if<F: FnOnce(&mut Circuit)>(&mut self, condition: Condition, f: F) {
+        for clbit in condition.clbits() {
+            self.check_clbit(&clbit);
+        }
+
+        let start = self.instructions.len();
+        f(self);
+        for instruction in &mut self.instructions[start..] {
+            assert!(
+                instruction.condition.is_none(),
+                "nested conditions are not supported"
+            );
+            instruction.condition = Some(condition.clone());
+        }
+    }
+
+    ///
+    /// Runs the circuit on `machine` and returns the values of the classical bits.
+    ///
+    /// The qubit with index `i` in the circuit is mapped to the `i`-th qubit of `machine`.
+    ///
+    pub fn run<M>(&self, machine: &mut M) -> ClassicalBits
+    where
+        M: QuantumMachine
+            + SingleGateApplicator
+            + DoubleGateApplicator
+            + TripleGateApplicator
+            + MultiGateApplicator,
+    {
+        let qubits = machine.get_qubits();
+        self.run_on(machine, &qubits)
+    }
+
+    ///
+    /// Runs the circuit on `machine` and returns the values of the classical bits.
+    ///
+    /// The qubit with index `i` in the circuit is mapped to `qubits[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `qubits` has fewer qubits than the circuit.
+    ///
+    pub fn run_on<M>(&self, machine: &mut M, qubits: &[Qubit]) -> ClassicalBits
+    where
+        M: QuantumMachine
+            + SingleGateApplicator
+            + DoubleGateApplicator
+            + TripleGateApplicator
+            + MultiGateApplicator,
+    {
+        assert!(
+            qubits.len() >= self.qubits_len,
+            "the circuit has {} qubits but only {} qubits are given",
+            self.qubits_len,
+            qubits.len()
+        );
+
+        let mut bits = ClassicalBits::new(self.clbits_len);
+        for instruction in &self.instructions {
+            if let Some(ref condition) = instruction.condition {
+                if !condition.is_satisfied(&bits) {
+                    continue;
+                }
+            }
+
+            match instruction.operation {
+                Operation::Gate(ref gate) => gate.apply(machine, qubits),
+                Operation::Measure(ref qubit, ref clbit) => {
+                    bits.bits[clbit.index] = machine.measure(&qubits[qubit.index]);
+                }
+            }
+        }
+
+        bits
+    }
+
+    ///
+    /// Returns the inverse of the circuit, which applies the inverses of the gates in
+    /// the reverse order.
+    ///
+    /// The inverse shares the qubits with the original circuit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the circuit has measurements or conditioned operations.
+    ///
+    pub fn inverse(&self) -> Circuit {
+        let instructions = self
+            .instructions
+            .iter()
+            .rev()
+            .map(|instruction| match *instruction {
+                Instruction {
+                    operation: Operation::Gate(ref gate),
+                    condition: None,
+                } => Instruction {
+                    operation: Operation::Gate(gate.inverse()),
+                    condition: None,
+                },
+                _ => panic!("a circuit with measurements or conditions cannot be inverted"),
+            })
+            .collect();
+
+        Circuit {
+            id: self.id,
+            qubits_len: self.qubits_len,
+            clbits_len: self.clbits_len,
+            instructions,
+        }
+    }
+
+    fn check_qubit(&self, qubit: &Qubit) {
+        if qubit.machine != self.id {
+            panic!("{}", RusqError::ForeignQubit { index: qubit.index });
+        }
+    }
+
+    fn check_clbit(&self, clbit: &Clbit) {
+        assert!(
+            clbit.circuit == self.id,
+            "classical bit index {} belongs to another circuit",
+            clbit.index
+        );
+    }
+}
+
+impl SingleGateApplicator for Circuit {
+    fn apply_single(&mut self, matrix: &Array2<Complex<f64>>, qubit: &Qubit) {
+        self.push_gate(Gate::Single(matrix.clone(), *qubit));
+    }
+
+    record_gates!(H(q), X(q), Y(q), Z(q), ID(q));
+
+    fn phase(&mut self, phi: f64, qubit: &Qubit) {
+        self.push_gate(Gate::Phase(phi, *qubit));
+    }
+
+    fn rx(&mut self, theta: f64, qubit: &Qubit) {
+        self.push_gate(Gate::RX(theta, *qubit));
+    }
+
+    fn ry(&mut self, theta: f64, qubit: &Qubit) {
+        self.push_gate(Gate::RY(theta, *qubit));
+    }
+
+    fn rz(&mut self, theta: f64, qubit: &Qubit) {
+        self.push_gate(Gate::RZ(theta, *qubit));
+    }
+}
+
+impl DoubleGateApplicator for Circuit {
+    fn apply_double(&mut self, matrix: &Array2<Complex<f64>>, qubit1: &Qubit, qubit2: &Qubit) {
+        self.push_gate(Gate::Double(matrix.clone(), *qubit1, *qubit2));
+    }
+
+    record_gates!(CNOT(q1, q2), SWAP(q1, q2), SQSWAP(q1, q2));
+
+    fn cphase(&mut self, phi: f64, qubit1: &Qubit, qubit2: &Qubit) {
+        self.push_gate(Gate::CPhase(phi, *qubit1, *qubit2));
+    }
+
+    fn rzz(&mut self, theta: f64, qubit1: &Qubit, qubit2: &Qubit) {
+        self.push_gate(Gate::RZZ(theta, *qubit1, *qubit2));
+    }
+}
+
+impl TripleGateApplicator for Circuit {
+    fn apply_triple(
+        &mut self,
+        matrix: &Array2<Complex<f64>>,
+        qubit1: &Qubit,
+        qubit2: &Qubit,
+        qubit3: &Qubit,
+    ) {
+        self.push_gate(Gate::Triple(matrix.clone(), *qubit1, *qubit2, *qubit3));
+    }
+
+    record_gates!(CCNOT(q1, q2, q3), CSWAP(q1, q2, q3));
+}
+
+impl MultiGateApplicator for Circuit {
+    fn apply_multi(&mut self, matrix: &Array2<Complex<f64>>, qubits: &[Qubit]) {
+        // A global phase is not recorded.
+        if qubits.is_empty() {
+            return;
+        }
+        self.push_gate(Gate::Multi(matrix.clone(), qubits.to_vec()));
+    }
+}
@@ -0,0 +1,611 @@
+//!
+//! A module for circuits with free parameters.
+//!
+//! A [ParameterizedCircuit](struct.ParameterizedCircuit.html) records the gates like a
+//! [Circuit](../struct.Circuit.html), while the angles of the phase and rotation gates can be
+//! affine expressions of [Parameter](struct.Parameter.html)s.
+//! The circuit can be bound to the values of the parameters to produce a concrete circuit,
+//! or run directly for the values without building a new circuit.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::circuit::parameterized::{Parameter, ParameterizedCircuit};
+//! use std::collections::HashMap;
+//!
+//! let theta = Parameter::new("θ");
+//! let mut circuit = ParameterizedCircuit::new(2);
+//! let qubits = circuit.get_qubits();
+//! circuit.ry(&theta, &qubits[0]);
+//! circuit.CNOT(&qubits[0], &qubits[1]);
+//! circuit.rz(2. * &theta + 0.5, &qubits[1]);
+//!
+//! let mut values = HashMap::new();
+//! values.insert(theta.clone(), std::f64::consts::PI);
+//! let bound = circuit.bind(&values);
+//! assert_eq!(bound.instructions().len(), 3);
+//!
+//! // The same as running the bound circuit
+//! let mut sim = QuantumSimulator::new(2);
+//! circuit.run(&mut sim, &[std::f64::consts::PI]);
+//! assert_eq!(sim.get_register().measure_integer(&mut sim, Endianness::Little), 3);
+//! ```
+//!
+
+use super::{Circuit, Gate, Instruction, Operation};
+use gates::double::DoubleGateApplicator;
+use gates::multi::MultiGateApplicator;
+use gates::single::SingleGateApplicator;
+use gates::triple::TripleGateApplicator;
+use ndarray::prelude::*;
+use num::complex::Complex;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Mul, Neg, Sub};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use {new_machine_id, QuantumMachine, Qubit, RusqError};
+
+static NEXT_PARAMETER_ID: AtomicUsize = AtomicUsize::new(0);
+
+///
+/// A type for a free parameter.
+///
+/// Each parameter created by [new](#method.new) is distinct from the others,
+/// even if they have the same name.
+///
+#[derive(Debug, Clone)]
+pub struct Parameter {
+    id: usize,
+    name: String,
+}
+
+impl Parameter {
+    /// Creates a new parameter with `name`.
+    pub fn new(name: &str) -> Parameter {
+        Parameter {
+            id: NEXT_PARAMETER_ID.fetch_add(1, Ordering::Relaxed),
+            name: name.to_owned(),
+        }
+    }
+
+    /// Returns the name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl PartialEq for Parameter {
+    fn eq(&self, other: &Parameter) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Parameter {}
+
+impl Hash for Parameter {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl fmt::Display for Parameter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+///
+/// A type for an affine expression of parameters, like `2θ + φ - 0.5`.
+///
+/// The expressions are built by the arithmetic operators from parameters and numbers.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterExpr {
+    terms: Vec<(Parameter, f64)>,
+    constant: f64,
+}
+
+impl ParameterExpr {
+    /// Returns the parameters with their coefficients.
+    pub fn terms(&self) -> &[(Parameter, f64)] {
+        &self.terms
+    }
+
+    /// Returns the constant term.
+    pub fn constant(&self) -> f64 {
+        self.constant
+    }
+
+    ///
+    /// Evaluates the expression for the values of the parameters.
+    ///
+    pub fn evaluate(&self, values: &HashMap<Parameter, f64>) -> Result<f64, RusqError> {
+        self.terms.iter().try_fold(
+            self.constant,
+            |sum, &(ref parameter, coefficient)| match values.get(parameter) {
+                Some(value) => Ok(sum + coefficient * value),
+                None => Err(RusqError::UnboundParameter {
+                    name: parameter.name.clone(),
+                }),
+            },
+        )
+    }
+
+    fn add_term(mut self, parameter: &Parameter, coefficient: f64) -> ParameterExpr {
+        match self.terms.iter().position(|term| term.0 == *parameter) {
+            Some(i) => self.terms[i].1 += coefficient,
+            None => self.terms.push((parameter.clone(), coefficient)),
+        }
+        self
+    }
+}
+
+impl From<f64> for ParameterExpr {
+    fn from(constant: f64) -> ParameterExpr {
+        ParameterExpr {
+            terms: vec![],
+            constant,
+        }
+    }
+}
+
+impl<'a> From<&'a Parameter> for ParameterExpr {
+    fn from(parameter: &'a Parameter) -> ParameterExpr {
+        ParameterExpr::from(0.).add_term(parameter, 1.)
+    }
+}
+
+impl From<Parameter> for ParameterExpr {
+    fn from(parameter: Parameter) -> ParameterExpr {
+        ParameterExpr::from(&parameter)
+    }
+}
+
+impl<T: Into<ParameterExpr>> Add<T> for ParameterExpr {
+    type Output = ParameterExpr;
+
+    fn add(self, other: T) -> ParameterExpr {
+        let other = other.into();
+        let mut sum = other
+            .terms
+            .iter()
+            .fold(self, |sum, &(ref p, c)| sum.add_term(p, c));
+        sum.constant += other.constant;
+        sum
+    }
+}
+
+impl<T: Into<ParameterExpr>> Sub<T> for ParameterExpr {
+    type Output = ParameterExpr;
+
+    fn sub(self, other: T) -> ParameterExpr {
+        self + -other.into()
+    }
+}
+
+impl Neg for ParameterExpr {
+    type Output = ParameterExpr;
+
+    fn neg(self) -> ParameterExpr {
+        self * -1.
+    }
+}
+
+impl Mul<f64> for ParameterExpr {
+    type Output = ParameterExpr;
+
+    fn mul(mut self, factor: f64) -> ParameterExpr {
+        for term in &mut self.terms {
+            term.1 *= factor;
+        }
+        self.constant *= factor;
+        self
+    }
+}
+
+impl Mul<ParameterExpr> for f64 {
+    type Output = ParameterExpr;
+
+    fn mul(self, expr: ParameterExpr) -> ParameterExpr {
+        expr * self
+    }
+}
+
+impl<'a> Mul<&'a Parameter> for f64 {
+    type Output = ParameterExpr;
+
+    fn mul(self, parameter: &'a Parameter) -> ParameterExpr {
+        ParameterExpr::from(parameter) * self
+    }
+}
+
+impl Neg for &Parameter {
+    type Output = ParameterExpr;
+
+    fn neg(self) -> ParameterExpr {
+        -ParameterExpr::from(self)
+    }
+}
+
+///
+/// A type for the gates in a parameterized circuit.
+///
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParameterizedGate {
+    /// A gate without parameters
+    Fixed(Gate),
+    Phase(ParameterExpr, Qubit),
+    RX(ParameterExpr, Qubit),
+    RY(ParameterExpr, Qubit),
+    RZ(ParameterExpr, Qubit),
+    CPhase(ParameterExpr, Qubit, Qubit),
+    RZZ(ParameterExpr, Qubit, Qubit),
+}
+
+impl ParameterizedGate {
+    /// Returns the expression of the angle, or `None` for a gate without parameters.
+    pub fn expr(&self) -> Option<&ParameterExpr> {
+        match *self {
+            ParameterizedGate::Fixed(_) => None,
+            ParameterizedGate::Phase(ref e, _)
+            | ParameterizedGate::RX(ref e, _)
+            | ParameterizedGate::RY(ref e, _)
+            | ParameterizedGate::RZ(ref e, _)
+            | ParameterizedGate::CPhase(ref e, _, _)
+            | ParameterizedGate::RZZ(ref e, _, _) => Some(e),
+        }
+    }
+
+    /// Returns the concrete gate with `angle`, which is ignored for a gate without parameters.
+    fn with_angle(&self, angle: f64) -> Gate {
+        match *self {
+            ParameterizedGate::Fixed(ref gate) => gate.clone(),
+            ParameterizedGate::Phase(_, q) => Gate::Phase(angle, q),
+            ParameterizedGate::RX(_, q) => Gate::RX(angle, q),
+            ParameterizedGate::RY(_, q) => Gate::RY(angle, q),
+            ParameterizedGate::RZ(_, q) => Gate::RZ(angle, q),
+            ParameterizedGate::CPhase(_, q1, q2) => Gate::CPhase(angle, q1, q2),
+            ParameterizedGate::RZZ(_, q1, q2) => Gate::RZZ(angle, q1, q2),
+        }
+    }
+}
+
+///
+/// A type for a circuit whose angles can depend on parameters.
+///
+/// See [the module document](index.html) for an example.
+///
+#[derive(Debug, Clone)]
+pub struct ParameterizedCircuit {
+    id: usize,
+    qubits_len: usize,
+    gates: Vec<ParameterizedGate>,
+    parameters: Vec<Parameter>,
+    indices: HashMap<Parameter, usize>,
+}
+
+impl ParameterizedCircuit {
+    /// Creates a new empty circuit with the given number of qubits.
+    pub fn new(qubits_len: usize) -> ParameterizedCircuit {
+        ParameterizedCircuit {
+            id: new_machine_id(),
+            qubits_len,
+            gates: vec![],
+            parameters: vec![],
+            indices: HashMap::new(),
+        }
+    }
+
+    /// Returns all the qubits in the circuit.
+    pub fn get_qubits(&self) -> Vec<Qubit> {
+        (0..self.qubits_len)
+            .map(|i| Qubit::new(i, self.id))
+            .collect()
+    }
+
+    /// Returns the number of the qubits.
+    pub fn qubits_len(&self) -> usize {
+        self.qubits_len
+    }
+
+    /// Returns the recorded gates.
+    pub fn gates(&self) -> &[ParameterizedGate] {
+        &self.gates
+    }
+
+    ///
+    /// Returns the parameters in the order of their first appearance, which is the order of
+    /// the values for [run](#method.run).
+    ///
+    pub fn parameters(&self) -> &[Parameter] {
+        &self.parameters
+    }
+
+    ///
+    /// Appends a gate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gate refers to a qubit of another circuit.
+    ///
+    pub fn push(&mut self, gate: ParameterizedGate) {
+        for qubit in gate.with_angle(0.).qubits() {
+            if qubit.machine != self.id {
+                panic!("{}", RusqError::ForeignQubit { index: qubit.index });
+            }
+        }
+        for (parameter, _) in gate.expr().iter().flat_map(|e| e.terms()) {
+            if !self.indices.contains_key(parameter) {
+                self.indices
+                    .insert(parameter.clone(), self.parameters.len());
+                self.parameters.push(parameter.clone());
+            }
+        }
+
+        self.gates.push(gate);
+    }
+
+    /// Appends a gate without parameters.
+    pub fn push_gate(&mut self, gate: Gate) {
+        self.push(ParameterizedGate::Fixed(gate));
+    }
+
+    /// Appends the phase gate with the angle `phi`.
+    pub fn phase<E: Into<ParameterExpr>>(&mut self, phi: E, qubit: &Qubit) {
+        self.push(ParameterizedGate::Phase(phi.into(), *qubit));
+    }
+
+    /// Appends the rotation around the X axis with the angle `theta`.
+    pub fn rx<E: Into<ParameterExpr>>(&mut self, theta: E, qubit: &Qubit) {
+        self.push(ParameterizedGate::RX(theta.into(), *qubit));
+    }
+
+    /// Appends the rotation around the Y axis with the angle `theta`.
+    pub fn ry<E: Into<ParameterExpr>>(&mut self, theta: E, qubit: &Qubit) {
+        self.push(ParameterizedGate::RY(theta.into(), *qubit));
+    }
+
+    /// Appends the rotation around the Z axis with the angle `theta`.
+    pub fn rz<E: Into<ParameterExpr>>(&mut self, theta: E, qubit: &Qubit) {
+        self.push(ParameterizedGate::RZ(theta.into(), *qubit));
+    }
+
+    /// Appends the controlled phase gate with the angle `phi`.
+    pub fn cphase<E: Into<ParameterExpr>>(&mut self, phi: E, qubit1: &Qubit, qubit2: &Qubit) {
+        self.push(ParameterizedGate::CPhase(phi.into(), *qubit1, *qubit2));
+    }
+
+    /// Appends the ZZ rotation with the angle `theta`.
+    pub fn rzz<E: Into<ParameterExpr>>(&mut self, theta: E, qubit1: &Qubit, qubit2: &Qubit) {
+        self.push(ParameterizedGate::RZZ(theta.into(), *qubit1, *qubit2));
+    }
+
+    ///
+    /// Produces the concrete circuit for the values of the parameters.
+    ///
+    /// The concrete circuit shares the qubits with this circuit.
+    ///
+    pub fn try_bind(&self, values: &HashMap<Parameter, f64>) -> Result<Circuit, RusqError> {
+        let gates = self
+            .gates
+            .iter()
+            .map(|gate| match gate.expr() {
+                Some(e) => Ok(gate.with_angle(e.evaluate(values)?)),
+                None => Ok(gate.with_angle(0.)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(self.concrete(gates))
+    }
+
+    ///
+    /// Produces the concrete circuit for the values of the parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a parameter in the circuit has no value.
+    ///
+    pub fn bind(&self, values: &HashMap<Parameter, f64>) -> Circuit {
+        match self.try_bind(values) {
+            Ok(circuit) => circuit,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    ///
+    /// Produces the concrete circuit for the values of the parameters in the order of
+    /// [parameters](#method.parameters).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of `values` differs from the one of the parameters.
+    ///
+    pub fn bind_values(&self, values: &[f64]) -> Circuit {
+        self.bind_shifted(values, None)
+    }
+
+    ///
+    /// Produces the concrete circuit with the angle of the `index`-th gate shifted by `delta`
+    /// for `shift = Some((index, delta))`.
+    ///
+    pub(crate) fn bind_shifted(&self, values: &[f64], shift: Option<(usize, f64)>) -> Circuit {
+        self.check_values(values);
+
+        let gates = self
+            .gates
+            .iter()
+            .enumerate()
+            .map(|(i, gate)| {
+                let angle = gate.expr().map_or(0., |e| self.angle(e, values));
+                match shift {
+                    Some((index, delta)) if index == i => gate.with_angle(angle + delta),
+                    _ => gate.with_angle(angle),
+                }
+            })
+            .collect();
+
+        self.concrete(gates)
+    }
+
+    fn concrete(&self, gates: Vec<Gate>) -> Circuit {
+        Circuit {
+            id: self.id,
+            qubits_len: self.qubits_len,
+            clbits_len: 0,
+            instructions: gates
+                .into_iter()
+                .map(|gate| Instruction {
+                    operation: Operation::Gate(gate),
+                    condition: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the position of `parameter` in [parameters](#method.parameters).
+    pub(crate) fn parameter_index(&self, parameter: &Parameter) -> usize {
+        self.indices[parameter]
+    }
+
+    /// Evaluates `expr` for the values in the order of the parameters.
+    fn angle(&self, expr: &ParameterExpr, values: &[f64]) -> f64 {
+        expr.terms.iter().fold(expr.constant, |sum, &(ref p, c)| {
+            sum + c * values[self.indices[p]]
+        })
+    }
+
+    fn check_values(&self, values: &[f64]) {
+        assert_eq!(
+            values.len(),
+            self.parameters.len(),
+            "the circuit has {} parameters but {} values are given",
+            self.parameters.len(),
+            values.len()
+        );
+    }
+
+    ///
+    /// Runs the circuit on `machine` for the values of the parameters in the order of
+    /// [parameters](#method.parameters).
+    ///
+    /// The qubit with index `i` in the circuit is mapped to the `i`-th qubit of `machine`.
+    ///
+    pub fn run<M>(&self, machine: &mut M, values: &[f64])
+    where
+        M: QuantumMachine
+            + SingleGateApplicator
+            + DoubleGateApplicator
+            + TripleGateApplicator
+            + MultiGateApplicator,
+    {
+        let qubits = machine.get_qubits();
+        self.run_on(machine, &qubits, values);
+    }
+
+    ///
+    /// Runs the circuit on `machine` for the values of the parameters in the order of
+    /// [parameters](#method.parameters).
+    ///
+    /// The qubit with index `i` in the circuit is mapped to `qubits[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `qubits` has fewer qubits than the circuit, or the number of `values`
+    /// differs from the one of the parameters.
+    ///
+    pub fn run_on<M>(&self, machine: &mut M, qubits: &[Qubit], values: &[f64])
+    where
+        M: SingleGateApplicator + DoubleGateApplicator + TripleGateApplicator + MultiGateApplicator,
+    {
+        assert!(
+            qubits.len() >= self.qubits_len,
+            "the circuit has {} qubits but only {} qubits are given",
+            self.qubits_len,
+            qubits.len()
+        );
+        self.check_values(values);
+
+        let q = |qubit: &Qubit| &qubits[qubit.index];
+        let angle = |e: &ParameterExpr| self.angle(e, values);
+        for gate in &self.gates {
+            match *gate {
+                ParameterizedGate::Fixed(ref gate) => gate.apply(machine, qubits),
+                ParameterizedGate::Phase(ref e, ref q1) => machine.phase(angle(e), q(q1)),
+                ParameterizedGate::RX(ref e, ref q1) => machine.rx(angle(e), q(q1)),
+                ParameterizedGate::RY(ref e, ref q1) => machine.ry(angle(e), q(q1)),
+                ParameterizedGate::RZ(ref e, ref q1) => machine.rz(angle(e), q(q1)),
+                ParameterizedGate::CPhase(ref e, ref q1, ref q2) => {
+                    machine.cphase(angle(e), q(q1), q(q2))
+                }
+                ParameterizedGate::RZZ(ref e, ref q1, ref q2) => {
+                    machine.rzz(angle(e), q(q1), q(q2))
+                }
+            }
+        }
+    }
+}
+
+impl SingleGateApplicator for ParameterizedCircuit {
+    fn apply_single(&mut self, matrix: &Array2<Complex<f64>>, qubit: &Qubit) {
+        self.push_gate(Gate::Single(matrix.clone(), *qubit));
+    }
+
+    record_gates!(H(q), X(q), Y(q), Z(q), ID(q));
+
+    fn phase(&mut self, phi: f64, qubit: &Qubit) {
+        ParameterizedCircuit::phase(self, phi, qubit);
+    }
+
+    fn rx(&mut self, theta: f64, qubit: &Qubit) {
+        ParameterizedCircuit::rx(self, theta, qubit);
+    }
+
+    fn ry(&mut self, theta: f64, qubit: &Qubit) {
+        ParameterizedCircuit::ry(self, theta, qubit);
+    }
+
+    fn rz(&mut self, theta: f64, qubit: &Qubit) {
+        ParameterizedCircuit::rz(self, theta, qubit);
+    }
+}
+
+impl DoubleGateApplicator for ParameterizedCircuit {
+    fn apply_double(&mut self, matrix: &Array2<Complex<f64>>, qubit1: &Qubit, qubit2: &Qubit) {
+        self.push_gate(Gate::Double(matrix.clone(), *qubit1, *qubit2));
+    }
+
+    record_gates!(CNOT(q1, q2), SWAP(q1, q2), SQSWAP(q1, q2));
+
+    fn cphase(&mut self, phi: f64, qubit1: &Qubit, qubit2: &Qubit) {
+        ParameterizedCircuit::cphase(self, phi, qubit1, qubit2);
+    }
+
+    fn rzz(&mut self, theta: f64, qubit1: &Qubit, qubit2: &Qubit) {
+        ParameterizedCircuit::rzz(self, theta, qubit1, qubit2);
+    }
+}
+
+impl TripleGateApplicator for ParameterizedCircuit {
+    fn apply_triple(
+        &mut self,
+        matrix: &Array2<Complex<f64>>,
+        qubit1: &Qubit,
+        qubit2: &Qubit,
+        qubit3: &Qubit,
+    ) {
+        self.push_gate(Gate::Triple(matrix.clone(), *qubit1, *qubit2, *qubit3));
+    }
+
+    record_gates!(CCNOT(q1, q2, q3), CSWAP(q1, q2, q3));
+}
+
+impl MultiGateApplicator for ParameterizedCircuit {
+    fn apply_multi(&mut self, matrix: &Array2<Complex<f64>>, qubits: &[Qubit]) {
+        if !qubits.is_empty() {
+            self.push_gate(Gate::Multi(matrix.clone(), qubits.to_vec()));
+        }
+    }
+}
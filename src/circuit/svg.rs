@@ -0,0 +1,231 @@
+//!
+//! A module for exporting circuits as SVG images.
+//!
+//! The symbols follow the text diagrams in [text module](../text/index.html):
+//! the classical wires are drawn as double lines, the bits written by measurements are marked
+//! with triangles, and the bits conditioning operations are marked with filled (one) or
+//! open (zero) circles.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::circuit::{svg, Circuit};
+//!
+//! let mut circuit = Circuit::new(2, 0);
+//! let qubits = circuit.get_qubits();
+//! circuit.H(&qubits[0]);
+//! circuit.CNOT(&qubits[0], &qubits[1]);
+//!
+//! let image = svg::svg(&circuit);
+//! assert!(image.starts_with("<svg"));
+//! assert!(image.trim_end().ends_with("</svg>"));
+//! ```
+//!
+
+use super::layout::{self, ClassicalElement, Element};
+use super::{Circuit, Instruction};
+use MeasuredResult;
+
+const ROW_HEIGHT: f64 = 40.;
+const TOP: f64 = 30.;
+const LEFT: f64 = 50.;
+const CHAR_WIDTH: f64 = 9.;
+const MIN_COLUMN_WIDTH: f64 = 40.;
+const BOX_HEIGHT: f64 = 28.;
+
+struct Canvas {
+    elements: Vec<String>,
+}
+
+impl Canvas {
+    fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        self.elements.push(format!(
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="black" stroke-width="1.5"/>"#,
+            x1, y1, x2, y2
+        ));
+    }
+
+    fn double_line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        if y1 == y2 {
+            self.line(x1, y1 - 1.5, x2, y2 - 1.5);
+            self.line(x1, y1 + 1.5, x2, y2 + 1.5);
+        } else {
+            self.line(x1 - 1.5, y1, x2 - 1.5, y2);
+            self.line(x1 + 1.5, y1, x2 + 1.5, y2);
+        }
+    }
+
+    fn circle(&mut self, x: f64, y: f64, r: f64, fill: &str) {
+        self.elements.push(format!(
+            r#"<circle cx="{}" cy="{}" r="{}" fill="{}" stroke="black" stroke-width="1.5"/>"#,
+            x, y, r, fill
+        ));
+    }
+
+    fn rect(&mut self, x: f64, y: f64, width: f64, height: f64) {
+        self.elements.push(format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="white" stroke="black" stroke-width="1.5"/>"#,
+            x, y, width, height
+        ));
+    }
+
+    fn text(&mut self, x: f64, y: f64, anchor: &str, text: &str) {
+        self.elements.push(format!(
+            r#"<text x="{}" y="{}" text-anchor="{}" dominant-baseline="central" font-family="serif" font-size="16">{}</text>"#,
+            x,
+            y,
+            anchor,
+            escape(text)
+        ));
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn row_y(row: usize) -> f64 {
+    TOP + ROW_HEIGHT * row as f64
+}
+
+fn box_width(label: &str) -> f64 {
+    (CHAR_WIDTH * label.chars().count() as f64 + 14.).max(BOX_HEIGHT)
+}
+
+fn column_width(instructions: &[&Instruction]) -> f64 {
+    instructions
+        .iter()
+        .flat_map(|i| layout::elements(&i.operation))
+        .map(|(_, element)| match element {
+            Element::Box(label) => box_width(&label) + 12.,
+            _ => MIN_COLUMN_WIDTH,
+        })
+        .fold(MIN_COLUMN_WIDTH, f64::max)
+}
+
+fn draw_instruction(canvas: &mut Canvas, circuit: &Circuit, instruction: &Instruction, x: f64) {
+    let qubits_len = circuit.qubits_len;
+    let (_, last, last_qubit) = layout::span(circuit, instruction);
+    let elements = layout::elements(&instruction.operation);
+    let first_qubit = elements.iter().map(|&(row, _)| row).min().unwrap();
+
+    // Vertical lines first so that the symbols are drawn over them.
+    if first_qubit < last_qubit {
+        canvas.line(x, row_y(first_qubit), x, row_y(last_qubit));
+    }
+    if last_qubit < last {
+        canvas.double_line(x, row_y(last_qubit), x, row_y(last));
+    }
+
+    for (row, element) in elements {
+        let y = row_y(row);
+        match element {
+            Element::Box(label) => {
+                let width = box_width(&label);
+                canvas.rect(x - width / 2., y - BOX_HEIGHT / 2., width, BOX_HEIGHT);
+                canvas.text(x, y, "middle", &label);
+            }
+            Element::Control => canvas.circle(x, y, 4., "black"),
+            Element::Target => {
+                canvas.circle(x, y, 10., "white");
+                canvas.line(x - 10., y, x + 10., y);
+                canvas.line(x, y - 10., x, y + 10.);
+            }
+            Element::Swap => {
+                canvas.line(x - 6., y - 6., x + 6., y + 6.);
+                canvas.line(x - 6., y + 6., x + 6., y - 6.);
+            }
+            Element::Meter => {
+                let half = BOX_HEIGHT / 2.;
+                canvas.rect(x - half, y - half, BOX_HEIGHT, BOX_HEIGHT);
+                canvas.elements.push(format!(
+                    r#"<path d="M {} {} A 9 9 0 0 1 {} {}" fill="none" stroke="black" stroke-width="1.5"/>"#,
+                    x - 9.,
+                    y + 6.,
+                    x + 9.,
+                    y + 6.
+                ));
+                canvas.line(x, y + 6., x + 7., y - 8.);
+            }
+        }
+    }
+
+    for (clbit, element) in layout::classical_elements(instruction) {
+        let y = row_y(qubits_len + clbit);
+        match element {
+            ClassicalElement::Write => canvas.elements.push(format!(
+                r#"<polygon points="{},{} {},{} {},{}" fill="black"/>"#,
+                x - 6.,
+                y - 8.,
+                x + 6.,
+                y - 8.,
+                x,
+                y
+            )),
+            ClassicalElement::Condition(MeasuredResult::One) => canvas.circle(x, y, 4., "black"),
+            ClassicalElement::Condition(MeasuredResult::Zero) => canvas.circle(x, y, 4., "white"),
+        }
+    }
+}
+
+///
+/// Exports the circuit as a standalone SVG image.
+///
+pub fn svg(circuit: &Circuit) -> String {
+    let qubits_len = circuit.qubits_len;
+    let rows = qubits_len + circuit.clbits_len;
+    let columns = layout::columns(circuit);
+    let widths = columns
+        .iter()
+        .map(|instructions| column_width(instructions))
+        .collect::<Vec<_>>();
+
+    let width = LEFT + widths.iter().sum::<f64>() + 20.;
+    let height = TOP * 2. + ROW_HEIGHT * (rows.max(1) - 1) as f64;
+
+    let mut canvas = Canvas { elements: vec![] };
+    for row in 0..rows {
+        let y = row_y(row);
+        let label = if row < qubits_len {
+            format!("q{}", row)
+        } else {
+            format!("c{}", row - qubits_len)
+        };
+        canvas.text(10., y, "start", &label);
+        if row < qubits_len {
+            canvas.line(LEFT - 10., y, width - 10., y);
+        } else {
+            canvas.double_line(LEFT - 10., y, width - 10., y);
+        }
+    }
+
+    let mut x = LEFT;
+    for (instructions, column_width) in columns.iter().zip(widths) {
+        for instruction in instructions {
+            draw_instruction(&mut canvas, circuit, instruction, x + column_width / 2.);
+        }
+        x += column_width;
+    }
+
+    let mut res = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = width,
+        h = height
+    );
+    res.push('\n');
+    res.push_str(&format!(
+        r#"<rect width="{}" height="{}" fill="white"/>"#,
+        width, height
+    ));
+    res.push('\n');
+    for element in canvas.elements {
+        res.push_str(&element);
+        res.push('\n');
+    }
+    res.push_str("</svg>\n");
+
+    res
+}
@@ -0,0 +1,232 @@
+//!
+//! A module for drawing circuits as text.
+//!
+//! Each qubit and classical bit is drawn as a horizontal wire.
+//! Boxes are drawn for single qubit gates, `●` for controls, `⊕` for the targets of
+//! CNOT and CCNOT, `×` for SWAP and CSWAP and `M` for measurements.
+//! The qubits of a gate given by a matrix are labeled `U[0]`, `U[1]`, ... in the order of
+//! the arguments, `U[0]` being the most significant qubit of the matrix.
+//! On the classical wires, `╩` shows the bit written by a measurement and `■` (`□`) shows
+//! the bit required to be one (zero) for a conditioned operation.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::circuit::Circuit;
+//!
+//! let mut circuit = Circuit::new(2, 1);
+//! let qubits = circuit.get_qubits();
+//! let clbits = circuit.get_clbits();
+//! circuit.H(&qubits[0]);
+//! circuit.CNOT(&qubits[0], &qubits[1]);
+//! circuit.measure(&qubits[1], &clbits[0]);
+//!
+//! assert_eq!(
+//!     circuit.to_string(),
+//!     [
+//!         "q0: ──┤H├──●───────",
+//!         "           │",
+//!         "q1: ───────⊕──┤M├──",
+//!         "               ║",
+//!         "c0: ═══════════╩═══",
+//!     ].join("\n")
+//! );
+//! ```
+//!
+
+use super::layout::{self, ClassicalElement, Element};
+use super::Circuit;
+use std::fmt;
+use MeasuredResult;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Link {
+    None,
+    Quantum,
+    Classical,
+}
+
+struct Column {
+    cells: Vec<Option<String>>,
+    links: Vec<Link>,
+    width: usize,
+}
+
+impl Column {
+    fn new(circuit: &Circuit, instructions: &[&super::Instruction]) -> Column {
+        let qubits_len = circuit.qubits_len;
+        let rows = qubits_len + circuit.clbits_len;
+        let mut cells = vec![None; rows];
+        let mut links = vec![Link::None; rows.saturating_sub(1)];
+
+        for instruction in instructions {
+            let (first, last, last_qubit) = layout::span(circuit, instruction);
+
+            for (g, link) in links.iter_mut().enumerate().take(last).skip(first) {
+                *link = if g < last_qubit {
+                    Link::Quantum
+                } else {
+                    Link::Classical
+                };
+            }
+
+            for (row, cell) in cells.iter_mut().enumerate().take(last + 1).skip(first) {
+                *cell = Some(
+                    if row < qubits_len {
+                        if row < last_qubit {
+                            "┼"
+                        } else {
+                            "╫"
+                        }
+                    } else {
+                        "╬"
+                    }
+                    .to_owned(),
+                );
+            }
+
+            for (row, element) in layout::elements(&instruction.operation) {
+                cells[row] = Some(match element {
+                    Element::Box(label) => format!("┤{}├", label),
+                    Element::Control => "●".to_owned(),
+                    Element::Target => "⊕".to_owned(),
+                    Element::Swap => "×".to_owned(),
+                    Element::Meter => "┤M├".to_owned(),
+                });
+            }
+
+            for (clbit, element) in layout::classical_elements(instruction) {
+                cells[qubits_len + clbit] = Some(
+                    match element {
+                        ClassicalElement::Write => "╩",
+                        ClassicalElement::Condition(MeasuredResult::One) => "■",
+                        ClassicalElement::Condition(MeasuredResult::Zero) => "□",
+                    }
+                    .to_owned(),
+                );
+            }
+        }
+
+        let half = cells
+            .iter()
+            .filter_map(|c| c.as_ref())
+            .map(|c| c.chars().count() / 2)
+            .max()
+            .unwrap_or(0);
+
+        Column {
+            cells,
+            links,
+            width: 2 * half + 3,
+        }
+    }
+
+    fn wire(&self, row: usize, fill: char) -> String {
+        let center = self.width / 2;
+        match self.cells[row] {
+            Some(ref symbol) => {
+                let left = center - symbol.chars().count() / 2;
+                let right = self.width - left - symbol.chars().count();
+                let mut res = fill_str(fill, left);
+                res.push_str(symbol);
+                res.push_str(&fill_str(fill, right));
+                res
+            }
+            None => fill_str(fill, self.width),
+        }
+    }
+
+    fn gap(&self, row: usize) -> String {
+        let center = self.width / 2;
+        let mut res = fill_str(' ', center);
+        res.push(match self.links[row] {
+            Link::None => ' ',
+            Link::Quantum => '│',
+            Link::Classical => '║',
+        });
+        res.push_str(&fill_str(' ', self.width - center - 1));
+        res
+    }
+}
+
+fn fill_str(c: char, len: usize) -> String {
+    (0..len).map(|_| c).collect()
+}
+
+///
+/// Draws the circuit as text.
+///
+/// If `max_width` is given, the diagram is wrapped so that each line has at most `max_width`
+/// characters as long as possible. The wrapped parts are marked with `»` and `«`.
+///
+pub fn draw(circuit: &Circuit, max_width: Option<usize>) -> String {
+    let qubits_len = circuit.qubits_len;
+    let rows = qubits_len + circuit.clbits_len;
+    let columns = layout::columns(circuit)
+        .iter()
+        .map(|instructions| Column::new(circuit, instructions))
+        .collect::<Vec<_>>();
+
+    let labels = (0..rows)
+        .map(|row| {
+            if row < qubits_len {
+                format!("q{}: ", row)
+            } else {
+                format!("c{}: ", row - qubits_len)
+            }
+        })
+        .collect::<Vec<_>>();
+    let label_width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+    // Splits the columns into the chunks fitting in `max_width`.
+    let mut chunks: Vec<&[Column]> = vec![];
+    let mut start = 0;
+    while start < columns.len() || chunks.is_empty() {
+        let mut end = start;
+        let mut width = label_width + 2;
+        while end < columns.len() {
+            width += columns[end].width;
+            if end > start && max_width.is_some_and(|w| width > w) {
+                break;
+            }
+            end += 1;
+        }
+        chunks.push(&columns[start..end]);
+        start = end;
+    }
+
+    let mut lines = vec![];
+    for (i, chunk) in chunks.iter().enumerate() {
+        if i > 0 {
+            lines.push(String::new());
+        }
+
+        for (row, label) in labels.iter().enumerate() {
+            let fill = if row < qubits_len { '─' } else { '═' };
+            let mut line = format!("{:width$}", label, width = label_width);
+            line.push(if i > 0 { '«' } else { fill });
+            for column in chunk.iter() {
+                line.push_str(&column.wire(row, fill));
+            }
+            line.push(if i + 1 < chunks.len() { '»' } else { fill });
+            lines.push(line);
+
+            if row + 1 < rows {
+                let mut line = fill_str(' ', label_width + 1);
+                for column in chunk.iter() {
+                    line.push_str(&column.gap(row));
+                }
+                lines.push(line.trim_end().to_owned());
+            }
+        }
+    }
+
+    lines.join("\n")
+}
+
+impl fmt::Display for Circuit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", draw(self, None))
+    }
+}
@@ -0,0 +1,275 @@
+//!
+//! A module for reduced density matrices and entanglement measures.
+//!
+//! This module is enabled by the `entanglement` feature. Unlike the other operations, it
+//! reads the amplitudes of a [QuantumSimulator](../simulator/struct.QuantumSimulator.html)
+//! directly, which is not possible on a physical machine.
+//!
+//! In a reduced density matrix on `qubits`, the state of `qubits[k]` is bit `k` of the
+//! indices. The entropies are in bits.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::entanglement;
+//! use rusq::prelude::*;
+//!
+//! let mut sim = QuantumSimulator::new(2);
+//! let qubits = sim.get_qubits();
+//! sim.H(&qubits[0]);
+//! sim.CNOT(&qubits[0], &qubits[1]);
+//!
+//! // A Bell pair has one bit of entanglement.
+//! let rho = entanglement::reduced_density_matrix(&sim, &qubits[..1]);
+//! assert!((entanglement::von_neumann_entropy(&rho) - 1.).abs() < 1e-10);
+//! assert!((entanglement::purity(&rho) - 0.5).abs() < 1e-10);
+//!
+//! let rho = entanglement::reduced_density_matrix(&sim, &qubits);
+//! assert!((entanglement::concurrence(&rho) - 1.).abs() < 1e-10);
+//! ```
+//!
+
+use linalg::{adjoint, hermitian_eigen, psd_sqrt};
+use ndarray::prelude::*;
+use num::complex::Complex;
+use simulator::QuantumSimulator;
+use {QuantumMachine, Qubit};
+
+const EPS: f64 = 1e-12;
+
+///
+/// Returns the reduced density matrix of the state of `sim` on `qubits`, tracing out the
+/// other qubits.
+///
+/// # Panics
+///
+/// Panics if a qubit is invalid for `sim` or given more than once.
+///
+pub fn reduced_density_matrix(sim: &QuantumSimulator, qubits: &[Qubit]) -> Array2<Complex<f64>> {
+    let rest = complement(sim, qubits);
+    let matrix = coefficients(sim, qubits, &rest);
+    matrix.dot(&adjoint(&matrix))
+}
+
+///
+/// Returns the purity $\mathrm{tr} \rho^2$ of the density matrix `rho`.
+///
+pub fn purity(rho: &Array2<Complex<f64>>) -> f64 {
+    rho.dot(rho).diag().iter().map(|x| x.re).sum()
+}
+
+///
+/// Returns the von Neumann entropy $-\mathrm{tr} \rho \log_2 \rho$ of the density matrix
+/// `rho`.
+///
+pub fn von_neumann_entropy(rho: &Array2<Complex<f64>>) -> f64 {
+    -eigenvalues(rho)
+        .into_iter()
+        .filter(|&p| p > EPS)
+        .map(|p| p * p.log2())
+        .sum::<f64>()
+}
+
+///
+/// Returns the Rényi entropy $\log_2 (\mathrm{tr} \rho^\alpha) / (1 - \alpha)$ of order
+/// `alpha` of the density matrix `rho`.
+///
+/// The order 1 gives the von Neumann entropy, and the infinite order gives the
+/// min-entropy $-\log_2 \lambda_{\max}$.
+///
+/// # Panics
+///
+/// Panics if `alpha` is negative.
+///
+pub fn renyi_entropy(rho: &Array2<Complex<f64>>, alpha: f64) -> f64 {
+    assert!(alpha >= 0., "the order must not be negative");
+
+    if (alpha - 1.).abs() < EPS {
+        return von_neumann_entropy(rho);
+    }
+    let eigenvalues = eigenvalues(rho).into_iter().filter(|&p| p > EPS);
+    if alpha.is_infinite() {
+        -eigenvalues.fold(0., f64::max).log2()
+    } else {
+        eigenvalues.map(|p| p.powf(alpha)).sum::<f64>().log2() / (1. - alpha)
+    }
+}
+
+///
+/// Returns the entanglement entropy of `qubits` with the other qubits of `sim`, which is
+/// the von Neumann entropy of their reduced density matrix.
+///
+/// # Panics
+///
+/// Panics if a qubit is invalid for `sim` or given more than once.
+///
+pub fn entanglement_entropy(sim: &QuantumSimulator, qubits: &[Qubit]) -> f64 {
+    von_neumann_entropy(&reduced_density_matrix(sim, qubits))
+}
+
+///
+/// Returns the mutual information $S(A) + S(B) - S(AB)$ between the qubits `a` and `b`.
+///
+/// # Panics
+///
+/// Panics if a qubit is invalid for `sim`, or if `a` and `b` overlap.
+///
+pub fn mutual_information(sim: &QuantumSimulator, a: &[Qubit], b: &[Qubit]) -> f64 {
+    let ab: Vec<_> = a.iter().chain(b).cloned().collect();
+    entanglement_entropy(sim, a) + entanglement_entropy(sim, b) - entanglement_entropy(sim, &ab)
+}
+
+///
+/// Returns Wootters' concurrence of the two-qubit density matrix `rho`.
+///
+/// # Panics
+///
+/// Panics if `rho` is not a 4x4 matrix.
+///
+pub fn concurrence(rho: &Array2<Complex<f64>>) -> f64 {
+    assert_eq!(rho.dim(), (4, 4), "the concurrence needs a two-qubit state");
+
+    // ρ̃ = (Y ⊗ Y) ρ* (Y ⊗ Y), where Y ⊗ Y maps |i> to -(-1)^(popcount i)|3 - i>.
+    let sign = |i: usize| {
+        if i.count_ones().is_multiple_of(2) {
+            1.
+        } else {
+            -1.
+        }
+    };
+    let flipped = Array2::from_shape_fn((4, 4), |(i, j)| {
+        rho[[3 - i, 3 - j]].conj() * sign(i) * sign(j)
+    });
+
+    // The square roots of the eigenvalues of ρρ̃ are the ones of √ρ ρ̃ √ρ.
+    let sqrt = psd_sqrt(rho);
+    let lambdas: Vec<_> = eigenvalues(&sqrt.dot(&flipped).dot(&sqrt))
+        .into_iter()
+        .map(|p| p.max(0.).sqrt())
+        .collect();
+
+    (lambdas[0] - lambdas[1] - lambdas[2] - lambdas[3]).max(0.)
+}
+
+///
+/// A type for the Schmidt decomposition $\sum_k s_k |a_k\rangle |b_k\rangle$ of a pure state.
+///
+#[derive(Debug, Clone)]
+pub struct SchmidtDecomposition {
+    /// The Schmidt coefficients $s_k$ in the descending order
+    pub coefficients: Vec<f64>,
+    /// The states $|a_k\rangle$ of the first part as the columns
+    pub left: Array2<Complex<f64>>,
+    /// The states $|b_k\rangle$ of the second part as the columns
+    pub right: Array2<Complex<f64>>,
+}
+
+impl SchmidtDecomposition {
+    /// Returns the number of the coefficients larger than `tolerance`.
+    pub fn rank(&self, tolerance: f64) -> usize {
+        self.coefficients.iter().filter(|&&s| s > tolerance).count()
+    }
+}
+
+///
+/// Returns the Schmidt decomposition of the state of `sim` across the bipartition into the
+/// qubits `a` and `b`.
+///
+/// There are as many terms as the dimension of the smaller part, and the states of each part
+/// are indexed as the reduced density matrices.
+///
+/// # Panics
+///
+/// Panics if a qubit is invalid for `sim`, or if `a` and `b` do not partition the qubits.
+///
+pub fn schmidt_decomposition(
+    sim: &QuantumSimulator,
+    a: &[Qubit],
+    b: &[Qubit],
+) -> SchmidtDecomposition {
+    complement(sim, b);
+    let mut sorted = b.to_vec();
+    sorted.sort_by_key(|q| q.index);
+    assert!(
+        complement(sim, a) == sorted,
+        "the qubits are not a bipartition"
+    );
+
+    let matrix = coefficients(sim, a, b);
+    let (values, vectors) = hermitian_eigen(&matrix.dot(&adjoint(&matrix)));
+    let terms = values.len().min(matrix.cols());
+
+    let coefficients: Vec<_> = values[..terms].iter().map(|&p| p.max(0.).sqrt()).collect();
+    let left = vectors.slice(s![.., ..terms]).to_owned();
+    // |b_k> = M^T conj(a_k) / s_k, or any orthogonal state for s_k = 0
+    let mut right = Array2::zeros((matrix.cols(), terms));
+    for (k, &coefficient) in coefficients.iter().enumerate() {
+        let column = if coefficient > EPS {
+            left.column(k)
+                .mapv(|x| x.conj())
+                .dot(&matrix)
+                .mapv(|x| x / coefficient)
+        } else {
+            orthogonal_state(&right, k)
+        };
+        right.column_mut(k).assign(&column);
+    }
+
+    SchmidtDecomposition {
+        coefficients,
+        left,
+        right,
+    }
+}
+
+/// Returns a state orthogonal to the first `k` columns of `states` by the Gram–Schmidt process.
+fn orthogonal_state(states: &Array2<Complex<f64>>, k: usize) -> Array1<Complex<f64>> {
+    for e in 0..states.rows() {
+        let mut state = Array1::zeros(states.rows());
+        state[e] = Complex::new(1., 0.);
+        for j in 0..k {
+            let column = states.column(j);
+            let overlap = column.mapv(|x| x.conj()).dot(&state);
+            state -= &column.mapv(|x| x * overlap);
+        }
+        let norm = state.iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt();
+        if norm > 1e-6 {
+            return state.mapv(|x| x / norm);
+        }
+    }
+    unreachable!("no orthogonal state is left")
+}
+
+/// Returns the qubits of `sim` not in `qubits` in the ascending order, checking `qubits`.
+fn complement(sim: &QuantumSimulator, qubits: &[Qubit]) -> Vec<Qubit> {
+    let refs: Vec<_> = qubits.iter().collect();
+    if let Err(e) = sim.check_qubits(&refs) {
+        panic!("{}", e);
+    }
+    sim.get_qubits()
+        .into_iter()
+        .filter(|q| !qubits.contains(q))
+        .collect()
+}
+
+///
+/// Returns the amplitudes as the matrix whose rows are indexed by `rows` and columns by
+/// `columns`, which together must be all the qubits.
+///
+fn coefficients(sim: &QuantumSimulator, rows: &[Qubit], columns: &[Qubit]) -> Array2<Complex<f64>> {
+    let gather = |i: usize, qubits: &[Qubit]| {
+        qubits
+            .iter()
+            .enumerate()
+            .fold(0, |index, (k, q)| index | (i >> q.index & 1) << k)
+    };
+    let mut matrix = Array2::zeros((1 << rows.len(), 1 << columns.len()));
+    for (i, amplitude) in sim.states().iter().enumerate() {
+        matrix[[gather(i, rows), gather(i, columns)]] = *amplitude;
+    }
+    matrix
+}
+
+fn eigenvalues(matrix: &Array2<Complex<f64>>) -> Vec<f64> {
+    hermitian_eigen(matrix).0
+}
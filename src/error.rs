@@ -0,0 +1,57 @@
+//!
+//! A module for the errors in Rusq.
+//!
+
+use std::error::Error;
+use std::fmt;
+
+///
+/// A type for the errors on an invalid operation to a quantum machine.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum RusqError {
+    /// The qubit with `index` does not exist in the machine with `qubits_len` qubits.
+    QubitOutOfRange { index: usize, qubits_len: usize },
+    /// The qubit with `index` belongs to another machine.
+    ForeignQubit { index: usize },
+    /// The qubit with `index` is given more than once to a single operation.
+    DuplicateQubit { index: usize },
+    /// The shape of the given matrix is not the `expected` one.
+    InvalidMatrixShape {
+        expected: (usize, usize),
+        actual: (usize, usize),
+    },
+    /// The given matrix is not unitary within the tolerance.
+    NonUnitaryMatrix,
+    /// No value is given to the parameter with `name`.
+    UnboundParameter { name: String },
+}
+
+impl fmt::Display for RusqError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RusqError::QubitOutOfRange { index, qubits_len } => write!(
+                f,
+                "qubit index {} is out of range for a machine with {} qubits",
+                index, qubits_len
+            ),
+            RusqError::ForeignQubit { index } => {
+                write!(f, "qubit index {} belongs to another machine", index)
+            }
+            RusqError::DuplicateQubit { index } => {
+                write!(f, "qubit index {} is given more than once", index)
+            }
+            RusqError::InvalidMatrixShape { expected, actual } => write!(
+                f,
+                "expected a {}x{} matrix but got a {}x{} matrix",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            RusqError::NonUnitaryMatrix => write!(f, "the matrix is not unitary"),
+            RusqError::UnboundParameter { ref name } => {
+                write!(f, "no value is given to parameter {}", name)
+            }
+        }
+    }
+}
+
+impl Error for RusqError {}
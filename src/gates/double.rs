@@ -66,6 +66,23 @@ pub trait DoubleGateApplicator {
         matrix[[3, 3]] = Complex::new(phi.cos(), phi.sin());
         self.apply_double(&matrix, qubit1, qubit2);
     }
+
+    /// The rotation $e^{-i \theta Z \otimes Z / 2}$
+    fn rzz(&mut self, theta: f64, qubit1: &Qubit, qubit2: &Qubit) {
+        let same = Complex::new((theta / 2.).cos(), -(theta / 2.).sin());
+        let different = same.conj();
+        let mut matrix = carray![
+            [0., 0., 0., 0.],
+            [0., 0., 0., 0.],
+            [0., 0., 0., 0.],
+            [0., 0., 0., 0.]
+        ];
+        matrix[[0, 0]] = same;
+        matrix[[1, 1]] = different;
+        matrix[[2, 2]] = different;
+        matrix[[3, 3]] = same;
+        self.apply_double(&matrix, qubit1, qubit2);
+    }
 }
 
 lazy_static! {
@@ -5,3 +5,4 @@
 pub mod single;
 pub mod double;
 pub mod triple;
+pub mod multi;
@@ -0,0 +1,130 @@
+//!
+//! A module for quantum gates on an arbitrary number of qubits.
+//!
+//! An arbitrary unitary matrix is applied by
+//! [MultiGateApplicator](trait.MultiGateApplicator.html).
+//!
+//! The multi-controlled gates in this module are decomposed into single qubit gates, CNOT and
+//! controlled phases, so that they can be applied to any type implementing
+//! [SingleGateApplicator](../single/trait.SingleGateApplicator.html) and
+//! [DoubleGateApplicator](../double/trait.DoubleGateApplicator.html).
+//! The decomposition uses no ancilla qubits, but the number of the gates grows exponentially
+//! with the number of the qubits.
+//!
+
+use gates::double::DoubleGateApplicator;
+use gates::single::SingleGateApplicator;
+use ndarray::prelude::*;
+use num::complex::Complex;
+use std::f64::consts::PI;
+use Qubit;
+
+///
+/// An trait for the types which accept operations for an arbitrary number of qubits.
+///
+pub trait MultiGateApplicator {
+    ///
+    /// An operation for the given unitary matrix `matrix` to `qubits`.
+    ///
+    /// As in the other gate applicators, `qubits[0]` corresponds to the most significant bit of
+    /// the row and column indices of `matrix`.
+    ///
+    fn apply_multi(&mut self, matrix: &Array2<Complex<f64>>, qubits: &[Qubit]);
+}
+
+///
+/// Multiplies the phase $e^{i\phi}$ to the states where all `qubits` are $|1\rangle$.
+///
+/// The operation is symmetric in the qubits: any of them can be regarded as the target.
+///
+/// # Examples
+///
+/// ```
+/// use rusq::prelude::*;
+/// use rusq::gates::multi;
+/// use std::f64::consts::PI;
+///
+/// let mut sim = QuantumSimulator::new(3);
+/// let qubits = sim.get_qubits();
+/// sim.X(&qubits[0]);
+/// sim.X(&qubits[1]);
+/// sim.H(&qubits[2]);
+///
+/// // Acts as Z on `qubits[2]` since the others are |1>.
+/// multi::mcphase(&mut sim, PI, &qubits);
+///
+/// sim.H(&qubits[2]);
+/// assert_eq!(sim.measure(&qubits[2]), MeasuredResult::One);
+/// ```
+///
+pub fn mcphase<M>(machine: &mut M, phi: f64, qubits: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    match qubits.len() {
+        0 => {}
+        1 => machine.phase(phi, &qubits[0]),
+        2 => machine.cphase(phi, &qubits[0], &qubits[1]),
+        3 => {
+            // x_1 x_2 = (x_1 + x_2 - (x_1 xor x_2)) / 2
+            machine.cphase(phi / 2., &qubits[1], &qubits[2]);
+            machine.CNOT(&qubits[0], &qubits[1]);
+            machine.cphase(-phi / 2., &qubits[1], &qubits[2]);
+            machine.CNOT(&qubits[0], &qubits[1]);
+            machine.cphase(phi / 2., &qubits[0], &qubits[2]);
+        }
+        n => {
+            // x_1 x_2 ... x_n = 2^{1-n} \sum_S (-1)^{|S|+1} (the parity of x_k for k in S),
+            // where S runs over the non-empty subsets of the qubits.
+            let unit = phi / (1u64 << (n - 1)) as f64;
+            for subset in 1..1usize << n {
+                let members = (0..n)
+                    .filter(|k| (subset >> k) & 1 == 1)
+                    .collect::<Vec<_>>();
+                let (&target, controls) = members.split_last().unwrap();
+                let sign = if members.len() % 2 == 1 { 1. } else { -1. };
+
+                for &k in controls {
+                    machine.CNOT(&qubits[k], &qubits[target]);
+                }
+                machine.phase(sign * unit, &qubits[target]);
+                for &k in controls.iter().rev() {
+                    machine.CNOT(&qubits[k], &qubits[target]);
+                }
+            }
+        }
+    }
+}
+
+///
+/// Flips the phase of the state where all `qubits` are $|1\rangle$.
+///
+pub fn mcz<M>(machine: &mut M, qubits: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    match qubits.len() {
+        1 => machine.Z(&qubits[0]),
+        _ => mcphase(machine, PI, qubits),
+    }
+}
+
+///
+/// Flips `target` if all `controls` are $|1\rangle$.
+///
+pub fn mcx<M>(machine: &mut M, controls: &[Qubit], target: &Qubit)
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    match controls.len() {
+        0 => machine.X(target),
+        1 => machine.CNOT(&controls[0], target),
+        _ => {
+            let mut qubits = controls.to_vec();
+            qubits.push(*target);
+            machine.H(target);
+            mcz(machine, &qubits);
+            machine.H(target);
+        }
+    }
+}
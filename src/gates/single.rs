@@ -11,7 +11,7 @@ use Qubit;
 
 #[derive(Debug)]
 pub struct SingleGate {
-    matrix: Array2<Complex<f64>>,
+    pub matrix: Array2<Complex<f64>>,
 }
 
 macro_rules! gen_gates {
@@ -61,14 +61,36 @@ pub trait SingleGateApplicator {
         matrix[[1, 1]] = Complex::new(phi.cos(), phi.sin());
         self.apply_single(&matrix, qubit);
     }
+
+    /// The rotation $e^{-i \theta X / 2}$ around the X axis
+    fn rx(&mut self, theta: f64, qubit: &Qubit) {
+        let (c, s) = ((theta / 2.).cos(), (theta / 2.).sin());
+        let matrix = carray![[c, 0.], [0., c]] + carray_i![[0., -s], [-s, 0.]];
+        self.apply_single(&matrix, qubit);
+    }
+
+    /// The rotation $e^{-i \theta Y / 2}$ around the Y axis
+    fn ry(&mut self, theta: f64, qubit: &Qubit) {
+        let (c, s) = ((theta / 2.).cos(), (theta / 2.).sin());
+        let matrix = carray![[c, -s], [s, c]];
+        self.apply_single(&matrix, qubit);
+    }
+
+    /// The rotation $e^{-i \theta Z / 2}$ around the Z axis
+    fn rz(&mut self, theta: f64, qubit: &Qubit) {
+        let mut matrix = carray![[0., 0.], [0., 0.]];
+        matrix[[0, 0]] = Complex::new((theta / 2.).cos(), -(theta / 2.).sin());
+        matrix[[1, 1]] = Complex::new((theta / 2.).cos(), (theta / 2.).sin());
+        self.apply_single(&matrix, qubit);
+    }
 }
 
 lazy_static! {
     pub static ref H: SingleGate = {
         SingleGate {
             matrix: carray![
-                [1., -1.],
-                [1.,  1.]
+                [1.,  1.],
+                [1., -1.]
             ] / (2f64).sqrt(),
         }
     };
@@ -0,0 +1,178 @@
+//!
+//! A module for the gradients of expectation values with respect to the parameters of a
+//! [ParameterizedCircuit](../circuit/parameterized/struct.ParameterizedCircuit.html).
+//!
+//! Two methods are provided:
+//!
+//! * [parameter_shift](fn.parameter_shift.html) runs the circuit with each parameterized gate
+//!   shifted by $\pm\pi/2$. The expectation values are given by a closure running the shifted
+//!   circuits, so that it works on any quantum machine and with the estimates from shots.
+//! * [adjoint](fn.adjoint.html) computes all the derivatives from one forward and one backward
+//!   sweep of the state on a [QuantumSimulator](../simulator/simulator/struct.QuantumSimulator.html).
+//!
+//! All the parameterized gates are $e^{-i\theta G}$ with $G$ whose eigenvalues differ by one,
+//! for which the shift rule
+//! $\partial_\theta \langle H\rangle = (\langle H\rangle_{\theta+\pi/2} - \langle H\rangle_{\theta-\pi/2}) / 2$
+//! is exact.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::circuit::parameterized::{Parameter, ParameterizedCircuit};
+//! use rusq::gradient;
+//! use rusq::observable::Hamiltonian;
+//!
+//! let theta = Parameter::new("θ");
+//! let mut circuit = ParameterizedCircuit::new(1);
+//! let qubits = circuit.get_qubits();
+//! circuit.ry(&theta, &qubits[0]);
+//!
+//! // <Z> = cos θ
+//! let hamiltonian = Hamiltonian::from_terms(&[(1., "Z")]);
+//! let mut sim = QuantumSimulator::new(1);
+//! let shifted = gradient::parameter_shift(&circuit, &[0.5], |shifted| {
+//!     sim.reset();
+//!     shifted.run(&mut sim);
+//!     hamiltonian.expectation(&sim, &sim.get_qubits())
+//! });
+//! assert!((shifted[0] + 0.5f64.sin()).abs() < 1e-10);
+//!
+//! sim.reset();
+//! let adjoint = gradient::adjoint(&mut sim, &circuit, &[0.5], &hamiltonian);
+//! assert!((adjoint[0] + 0.5f64.sin()).abs() < 1e-10);
+//! ```
+//!
+
+use circuit::parameterized::ParameterizedCircuit;
+use circuit::{Circuit, Gate, Operation};
+use ndarray::prelude::*;
+use num::complex::Complex;
+use observable::Hamiltonian;
+use simulator::{QuantumSimulator, Snapshot};
+use std::f64::consts::PI;
+use QuantumMachine;
+
+///
+/// Computes the gradient by the parameter-shift rule.
+///
+/// `expectation` returns the expectation value for a concrete circuit, which is `circuit`
+/// with one of the gates shifted. It is called twice for each parameterized gate.
+///
+/// # Panics
+///
+/// Panics if the number of `values` differs from the one of the parameters of `circuit`.
+///
+pub fn parameter_shift<F>(
+    circuit: &ParameterizedCircuit,
+    values: &[f64],
+    mut expectation: F,
+) -> Vec<f64>
+where
+    F: FnMut(&Circuit) -> f64,
+{
+    let mut gradient = vec![0.; values.len()];
+    for (i, gate) in circuit.gates().iter().enumerate() {
+        let expr = match gate.expr() {
+            Some(expr) if !expr.terms().is_empty() => expr,
+            _ => continue,
+        };
+
+        let plus = expectation(&circuit.bind_shifted(values, Some((i, PI / 2.))));
+        let minus = expectation(&circuit.bind_shifted(values, Some((i, -PI / 2.))));
+        let derivative = (plus - minus) / 2.;
+        for &(ref parameter, coefficient) in expr.terms() {
+            gradient[circuit.parameter_index(parameter)] += coefficient * derivative;
+        }
+    }
+
+    gradient
+}
+
+///
+/// Computes the gradient of the expectation value of `hamiltonian` by the adjoint method.
+///
+/// The circuit is run on the current state of `sim` as
+/// [Circuit::run](../circuit/struct.Circuit.html#method.run) does, and `sim` is left in
+/// the final state. The positions in `hamiltonian` refer to the qubits of `sim`.
+///
+/// # Panics
+///
+/// Panics if the number of `values` differs from the one of the parameters of `circuit`.
+///
+pub fn adjoint(
+    sim: &mut QuantumSimulator,
+    circuit: &ParameterizedCircuit,
+    values: &[f64],
+    hamiltonian: &Hamiltonian,
+) -> Vec<f64> {
+    let bound = circuit.bind_values(values);
+    bound.run(sim);
+
+    // |ψ> is swept back gate by gate together with <λ| = <ψ|H.
+    let qubits = sim.get_qubits();
+    let mut psi = QuantumSimulator::from_snapshot(sim.snapshot());
+    let mut lambda = QuantumSimulator::from_snapshot(Snapshot {
+        dimension: qubits.len(),
+        states: hamiltonian.apply(sim, &qubits),
+    });
+    let psi_qubits = psi.get_qubits();
+    let lambda_qubits = lambda.get_qubits();
+
+    let mut gradient = vec![0.; values.len()];
+    for (gate, instruction) in circuit.gates().iter().zip(bound.instructions()).rev() {
+        let concrete = match instruction.operation {
+            Operation::Gate(ref gate) => gate,
+            Operation::Measure(..) => unreachable!(),
+        };
+        concrete.inverse().apply(&mut psi, &psi_qubits);
+
+        if let Some(expr) = gate.expr().filter(|expr| !expr.terms().is_empty()) {
+            // d<ψ|H|ψ>/dθ = 2 Re <λ|dU/dθ|ψ> with |ψ> before the gate
+            let mut mu = QuantumSimulator::from_snapshot(psi.snapshot());
+            let mu_qubits = mu.get_qubits();
+            let (derivative, factor) = derivative(concrete);
+            derivative.apply(&mut mu, &mu_qubits);
+            let overlap = lambda
+                .states()
+                .iter()
+                .zip(mu.states())
+                .fold(Complex::new(0., 0.), |sum, (l, m)| sum + l.conj() * m);
+            let derivative = 2. * factor * overlap.re;
+
+            for &(ref parameter, coefficient) in expr.terms() {
+                gradient[circuit.parameter_index(parameter)] += coefficient * derivative;
+            }
+        }
+
+        concrete.inverse().apply(&mut lambda, &lambda_qubits);
+    }
+
+    gradient
+}
+
+///
+/// Returns the gate and the factor whose product is the derivative of the gate with respect to
+/// its angle.
+///
+fn derivative(gate: &Gate) -> (Gate, f64) {
+    // dR(θ)/dθ = R(θ + π) / 2 for R(θ) = cos(θ/2) - i sin(θ/2) P
+    let phase = |phi: f64| Complex::new(-phi.sin(), phi.cos());
+    let zero = Complex::new(0., 0.);
+    match *gate {
+        Gate::RX(theta, q) => (Gate::RX(theta + PI, q), 0.5),
+        Gate::RY(theta, q) => (Gate::RY(theta + PI, q), 0.5),
+        Gate::RZ(theta, q) => (Gate::RZ(theta + PI, q), 0.5),
+        Gate::RZZ(theta, q1, q2) => (Gate::RZZ(theta + PI, q1, q2), 0.5),
+        Gate::Phase(phi, q) => (
+            Gate::Single(array![[zero, zero], [zero, phase(phi)]], q),
+            1.,
+        ),
+        Gate::CPhase(phi, q1, q2) => {
+            let mut matrix = Array2::from_elem((4, 4), zero);
+            matrix[[3, 3]] = phase(phi);
+            (Gate::Double(matrix, q1, q2), 1.)
+        }
+        ref gate => panic!("{:?} has no parameter", gate),
+    }
+}
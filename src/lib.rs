@@ -18,6 +18,25 @@ extern crate rand;
 pub mod simulator;
 pub mod gates;
 pub mod prelude;
+pub mod circuit;
+#[cfg(feature = "entanglement")]
+pub mod entanglement;
+pub mod error;
+pub mod register;
+pub mod algorithms;
+pub mod arithmetic;
+pub mod gradient;
+mod linalg;
+pub mod observable;
+pub mod optimize;
+pub mod qec;
+pub mod state;
+pub mod synthesis;
+
+pub use error::RusqError;
+pub use register::{Endianness, QubitRegister};
+
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 ///
 /// A type for the result of the measurement of a qubit.
@@ -36,10 +55,31 @@ pub enum MeasuredResult {
 /// This qubit type just represents the index in a given quantum machine.
 /// All "states" are carried by a type implementing [QuantumMachine](trait.QuantumMachine.html) trait.
 ///
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+/// A qubit can be obtained only from a quantum machine and remembers the machine it belongs to,
+/// so that the machine can reject qubits of another machine.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct Qubit {
-    /// The index in a given quantum machine.
-    pub index: usize,
+    pub(crate) index: usize,
+    pub(crate) machine: usize,
+}
+
+impl Qubit {
+    pub(crate) fn new(index: usize, machine: usize) -> Qubit {
+        Qubit { index, machine }
+    }
+
+    /// Returns the index in the quantum machine.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+static NEXT_MACHINE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns an identifier unique to each quantum machine in the process.
+pub(crate) fn new_machine_id() -> usize {
+    NEXT_MACHINE_ID.fetch_add(1, Ordering::Relaxed)
 }
 
 ///
@@ -47,12 +87,18 @@ pub struct Qubit {
 ///
 /// The type implementing this must be responsible for the states - namely, the wavefunction.
 ///
-/// Currently, only the supporting type is [QuantumSimulator](simulator/simulator/struct.QuantumSimulator.html).
+/// The supporting types are [QuantumSimulator](simulator/simulator/struct.QuantumSimulator.html) and
+/// [UnitarySimulator](simulator/unitary/struct.UnitarySimulator.html), although the latter cannot measure.
 ///
 pub trait QuantumMachine {
     /// Returns all the qubits in the machine.
     fn get_qubits(&self) -> Vec<Qubit>;
 
+    /// Returns all the qubits in the machine as a register.
+    fn get_register(&self) -> QubitRegister {
+        QubitRegister::new(self.get_qubits())
+    }
+
     /// Measures the given qubit.
     /// Note that the qubit is expected to be projected to the corresponding state.
     fn measure(&mut self, qubit: &Qubit) -> MeasuredResult;
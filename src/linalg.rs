@@ -0,0 +1,161 @@
+//!
+//! A module for the linear algebra on complex matrices.
+//!
+
+use ndarray::prelude::*;
+use num::complex::Complex;
+
+/// Returns the conjugate transpose of `matrix`.
+pub(crate) fn adjoint(matrix: &Array2<Complex<f64>>) -> Array2<Complex<f64>> {
+    matrix.t().mapv(|x| x.conj())
+}
+
+/// Returns the Kronecker product of `a` and `b`, where `a` acts on the higher bits.
+pub(crate) fn kron(a: &Array2<Complex<f64>>, b: &Array2<Complex<f64>>) -> Array2<Complex<f64>> {
+    let (n, m) = (a.rows(), b.rows());
+    Array2::from_shape_fn((n * m, n * m), |(i, j)| {
+        a[[i / m, j / m]] * b[[i % m, j % m]]
+    })
+}
+
+/// Returns the determinant of the square `matrix` by the Gaussian elimination.
+pub(crate) fn determinant(matrix: &Array2<Complex<f64>>) -> Complex<f64> {
+    let n = matrix.rows();
+    let mut a = matrix.to_owned();
+    let mut det = Complex::new(1., 0.);
+    for k in 0..n {
+        let pivot = (k..n)
+            .max_by(|&i, &j| a[[i, k]].norm().partial_cmp(&a[[j, k]].norm()).unwrap())
+            .unwrap();
+        if a[[pivot, k]].norm() == 0. {
+            return Complex::new(0., 0.);
+        }
+        if pivot != k {
+            for j in 0..n {
+                a.swap([k, j], [pivot, j]);
+            }
+            det = -det;
+        }
+        det *= a[[k, k]];
+        for i in k + 1..n {
+            let factor = a[[i, k]] / a[[k, k]];
+            for j in k..n {
+                let x = a[[k, j]];
+                a[[i, j]] -= factor * x;
+            }
+        }
+    }
+    det
+}
+
+/// Returns the square root of the positive semidefinite `matrix`.
+pub(crate) fn psd_sqrt(matrix: &Array2<Complex<f64>>) -> Array2<Complex<f64>> {
+    let (values, vectors) = hermitian_eigen(matrix);
+    let n = values.len();
+    let roots = Array2::from_shape_fn((n, n), |(i, j)| {
+        let root = if i == j { values[i].max(0.).sqrt() } else { 0. };
+        Complex::new(root, 0.)
+    });
+    vectors.dot(&roots).dot(&adjoint(&vectors))
+}
+
+///
+/// Returns the eigenvalues in the descending order and the eigenvectors as the columns of
+/// the Hermitian `matrix`, by the cyclic Jacobi method.
+///
+pub(crate) fn hermitian_eigen(matrix: &Array2<Complex<f64>>) -> (Vec<f64>, Array2<Complex<f64>>) {
+    let n = matrix.rows();
+    let mut a = matrix.to_owned();
+    let mut v = Array2::eye(n);
+    let scale = a.iter().map(|x| x.norm_sqr()).sum::<f64>().max(1e-300);
+
+    for _ in 0..100 {
+        let off: f64 = a
+            .indexed_iter()
+            .filter(|&((i, j), _)| i != j)
+            .map(|(_, x)| x.norm_sqr())
+            .sum();
+        if off <= 1e-30 * scale {
+            break;
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a[[p, q]];
+                if apq.norm() == 0. {
+                    continue;
+                }
+
+                // Makes a[p][q] real by the phase on q.
+                let phase = Complex::from_polar(&1., &-apq.arg());
+                a.column_mut(q).mapv_inplace(|x| x * phase);
+                a.row_mut(q).mapv_inplace(|x| x * phase.conj());
+                v.column_mut(q).mapv_inplace(|x| x * phase);
+
+                // The real Jacobi rotation zeroing a[p][q]
+                let theta = (a[[q, q]].re - a[[p, p]].re) / (2. * apq.norm());
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.).sqrt());
+                let c = 1. / (t * t + 1.).sqrt();
+                let s = t * c;
+                rotate(&mut a, p, q, c, s, true);
+                rotate(&mut a, p, q, c, s, false);
+                rotate(&mut v, p, q, c, s, true);
+            }
+        }
+    }
+
+    let mut order: Vec<_> = (0..n).collect();
+    order.sort_by(|&i, &j| a[[j, j]].re.partial_cmp(&a[[i, i]].re).unwrap());
+    let values = order.iter().map(|&i| a[[i, i]].re).collect();
+    let vectors = Array2::from_shape_fn((n, n), |(i, j)| v[[i, order[j]]]);
+    (values, vectors)
+}
+
+///
+/// Returns the eigenvalues and the eigenvectors as the columns of the unitary `matrix`.
+///
+/// The Hermitian and anti-Hermitian parts commute and share the eigenvectors, which are
+/// obtained from a generic combination of them.
+///
+pub(crate) fn unitary_eigen(
+    matrix: &Array2<Complex<f64>>,
+) -> (Vec<Complex<f64>>, Array2<Complex<f64>>) {
+    let n = matrix.rows();
+    let dagger = adjoint(matrix);
+    let mut best = (f64::INFINITY, None);
+    for &weight in &[0.5772156649, -1.3247179572, 2.2360679775, -0.4142135624] {
+        let combination = Array2::from_shape_fn((n, n), |(i, j)| {
+            let (x, y) = (matrix[[i, j]], dagger[[i, j]]);
+            (x + y) / 2. + (x - y) / Complex::new(0., 2.) * weight
+        });
+        let vectors = hermitian_eigen(&combination).1;
+        let diagonal = adjoint(&vectors).dot(matrix).dot(&vectors);
+        let off = diagonal
+            .indexed_iter()
+            .filter(|&((i, j), _)| i != j)
+            .map(|(_, x)| x.norm_sqr())
+            .sum::<f64>();
+        if off < best.0 {
+            let values = (0..n).map(|k| diagonal[[k, k]]).collect();
+            best = (off, Some((values, vectors)));
+        }
+        if off <= 1e-24 {
+            break;
+        }
+    }
+    best.1.unwrap()
+}
+
+/// Multiplies the rotation on the columns, or the transposed one on the rows.
+fn rotate(a: &mut Array2<Complex<f64>>, p: usize, q: usize, c: f64, s: f64, columns: bool) {
+    for k in 0..a.rows() {
+        let (kp, kq) = if columns {
+            ([k, p], [k, q])
+        } else {
+            ([p, k], [q, k])
+        };
+        let (x, y) = (a[kp], a[kq]);
+        a[kp] = x * c - y * s;
+        a[kq] = x * s + y * c;
+    }
+}
@@ -0,0 +1,266 @@
+//!
+//! A module for observables given as weighted sums of Pauli strings.
+//!
+//! A Pauli string is written as a string of `I`, `X`, `Y` and `Z`, where the `k`-th
+//! character acts on the `k`-th qubit of the given qubits.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::observable::Hamiltonian;
+//!
+//! let hamiltonian = Hamiltonian::from_terms(&[(1., "ZZ"), (0.5, "XI")]);
+//!
+//! let mut sim = QuantumSimulator::new(2);
+//! let qubits = sim.get_qubits();
+//! sim.X(&qubits[0]);
+//!
+//! assert!((hamiltonian.expectation(&sim, &qubits) - (-1.)).abs() < 1e-10);
+//! ```
+//!
+
+use num::complex::Complex;
+use rand::Rng;
+use simulator::QuantumSimulator;
+use Qubit;
+
+///
+/// A type for the Pauli operators.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Pauli {
+    I,
+    X,
+    Y,
+    Z,
+}
+
+///
+/// A type for a Pauli string with a real coefficient.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct PauliString {
+    /// The coefficient of the string
+    pub coefficient: f64,
+    /// The operators with the positions of the qubits they act on
+    pub paulis: Vec<(usize, Pauli)>,
+}
+
+impl PauliString {
+    ///
+    /// Creates a Pauli string from the string of `I`, `X`, `Y` and `Z`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `paulis` contains another character.
+    ///
+    pub fn new(coefficient: f64, paulis: &str) -> PauliString {
+        let paulis = paulis
+            .chars()
+            .enumerate()
+            .filter_map(|(k, c)| match c {
+                'I' => None,
+                'X' => Some((k, Pauli::X)),
+                'Y' => Some((k, Pauli::Y)),
+                'Z' => Some((k, Pauli::Z)),
+                _ => panic!("{:?} is not a Pauli operator", c),
+            })
+            .collect();
+
+        PauliString {
+            coefficient,
+            paulis,
+        }
+    }
+
+    ///
+    /// Returns the expectation value of the string without the coefficient
+    /// for the current state of `sim`.
+    ///
+    pub fn expectation(&self, sim: &QuantumSimulator, qubits: &[Qubit]) -> f64 {
+        let (flip, sign, phase) = self.masks(sim, qubits);
+
+        let states = sim.states();
+        let sum = states
+            .iter()
+            .enumerate()
+            .fold(Complex::new(0., 0.), |sum, (i, amplitude)| {
+                let product = states[i ^ flip].conj() * amplitude;
+                if (i & sign).count_ones() % 2 == 0 {
+                    sum + product
+                } else {
+                    sum - product
+                }
+            });
+        (sum * phase).re
+    }
+
+    /// Returns `(flip, sign, phase)` such that P|i> = phase (-1)^(i & sign) |i ^ flip>.
+    fn masks(&self, sim: &QuantumSimulator, qubits: &[Qubit]) -> (usize, usize, Complex<f64>) {
+        let (mut flip, mut sign, mut ys) = (0usize, 0usize, 0usize);
+        for &(k, pauli) in &self.paulis {
+            let bit = 1 << qubit_at(sim, qubits, k).index;
+            match pauli {
+                Pauli::I => {}
+                Pauli::X => flip |= bit,
+                Pauli::Y => {
+                    flip |= bit;
+                    sign |= bit;
+                    ys += 1;
+                }
+                Pauli::Z => sign |= bit,
+            }
+        }
+        let phase = [
+            Complex::new(1., 0.),
+            Complex::new(0., 1.),
+            Complex::new(-1., 0.),
+            Complex::new(0., -1.),
+        ][ys % 4];
+
+        (flip, sign, phase)
+    }
+
+    /// Adds `coefficient * P|ψ>` for the amplitudes of the state of `sim` to `result`.
+    fn apply_to(&self, sim: &QuantumSimulator, qubits: &[Qubit], result: &mut [Complex<f64>]) {
+        let (flip, sign, phase) = self.masks(sim, qubits);
+        let factor = phase * self.coefficient;
+        for (i, amplitude) in sim.states().iter().enumerate() {
+            if (i & sign).count_ones() % 2 == 0 {
+                result[i ^ flip] += factor * amplitude;
+            } else {
+                result[i ^ flip] -= factor * amplitude;
+            }
+        }
+    }
+
+    ///
+    /// Estimates the expectation value of the string without the coefficient with the shot
+    /// noise of `shots` measurements.
+    ///
+    /// Nothing is measured: the exact expectation value is computed from the amplitudes of
+    /// `sim`, and the outcomes $\pm 1$ of `shots` measurements in the eigenbasis are drawn
+    /// from it by the random number generator of `sim`. The estimate thus has the same
+    /// distribution as the measurements, while the state is not collapsed.
+    ///
+    pub fn sample_expectation(
+        &self,
+        sim: &mut QuantumSimulator,
+        qubits: &[Qubit],
+        shots: usize,
+    ) -> f64 {
+        assert!(shots > 0, "at least one shot is needed");
+
+        if self.paulis.iter().all(|&(_, pauli)| pauli == Pauli::I) {
+            return 1.;
+        }
+
+        let probability = (1. + self.expectation(sim, qubits)) / 2.;
+        let rng = sim.rng();
+        let positives = (0..shots)
+            .filter(|_| rng.gen::<f64>() < probability)
+            .count();
+
+        2. * positives as f64 / shots as f64 - 1.
+    }
+}
+
+fn qubit_at<'a>(sim: &QuantumSimulator, qubits: &'a [Qubit], k: usize) -> &'a Qubit {
+    assert!(
+        k < qubits.len(),
+        "the Pauli string acts on the qubit {} but only {} qubits are given",
+        k,
+        qubits.len()
+    );
+    if let Err(e) = sim.check_qubits(&[&qubits[k]]) {
+        panic!("{}", e);
+    }
+    &qubits[k]
+}
+
+///
+/// A type for a Hamiltonian as a weighted sum of Pauli strings.
+///
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Hamiltonian {
+    terms: Vec<PauliString>,
+}
+
+impl Hamiltonian {
+    /// Creates a Hamiltonian without terms.
+    pub fn new() -> Hamiltonian {
+        Hamiltonian { terms: vec![] }
+    }
+
+    ///
+    /// Creates a Hamiltonian from the pairs of the coefficients and the Pauli strings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a string contains a character other than `I`, `X`, `Y` and `Z`.
+    ///
+    pub fn from_terms(terms: &[(f64, &str)]) -> Hamiltonian {
+        let mut hamiltonian = Hamiltonian::new();
+        for &(coefficient, paulis) in terms {
+            hamiltonian.add_term(coefficient, paulis);
+        }
+        hamiltonian
+    }
+
+    /// Adds the term `coefficient * paulis`.
+    pub fn add_term(&mut self, coefficient: f64, paulis: &str) {
+        self.push(PauliString::new(coefficient, paulis));
+    }
+
+    /// Adds the Pauli string as a term.
+    pub fn push(&mut self, term: PauliString) {
+        self.terms.push(term);
+    }
+
+    /// Returns the terms.
+    pub fn terms(&self) -> &[PauliString] {
+        &self.terms
+    }
+
+    ///
+    /// Returns the expectation value for the current state of `sim`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a term acts on a qubit not in `qubits`, or `qubits` do not belong to `sim`.
+    ///
+    pub fn expectation(&self, sim: &QuantumSimulator, qubits: &[Qubit]) -> f64 {
+        self.terms
+            .iter()
+            .map(|term| term.coefficient * term.expectation(sim, qubits))
+            .sum()
+    }
+
+    /// Returns the amplitudes of $H|\psi\rangle$ for the state $|\psi\rangle$ of `sim`.
+    pub(crate) fn apply(&self, sim: &QuantumSimulator, qubits: &[Qubit]) -> Vec<Complex<f64>> {
+        let mut result = vec![Complex::new(0., 0.); sim.states().len()];
+        for term in &self.terms {
+            term.apply_to(sim, qubits, &mut result);
+        }
+        result
+    }
+
+    ///
+    /// Estimates the expectation value with the shot noise of `shots` measurements for each
+    /// term.
+    ///
+    /// See [PauliString::sample_expectation](struct.PauliString.html#method.sample_expectation).
+    ///
+    pub fn sample_expectation(
+        &self,
+        sim: &mut QuantumSimulator,
+        qubits: &[Qubit],
+        shots: usize,
+    ) -> f64 {
+        self.terms
+            .iter()
+            .map(|term| term.coefficient * term.sample_expectation(sim, qubits, shots))
+            .sum()
+    }
+}
@@ -0,0 +1,98 @@
+//!
+//! A module for the Adam optimizer.
+//!
+
+use super::{Counted, Differentiation, Iteration, Minimum, Optimizer};
+
+///
+/// The Adam optimizer by Kingma and Ba, the gradient descent with the step scaled by the
+/// moving averages of the gradient and its square.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Adam {
+    /// The size of the steps
+    pub learning_rate: f64,
+    /// The decay rate of the average of the gradient
+    pub beta1: f64,
+    /// The decay rate of the average of the square of the gradient
+    pub beta2: f64,
+    /// The constant added to the denominator for the numerical stability
+    pub epsilon: f64,
+    /// The maximum number of the iterations
+    pub max_iterations: usize,
+    /// The iteration stops when the norm of the gradient is less than this.
+    pub tolerance: f64,
+    /// The estimation of the gradient, see [Differentiation](../enum.Differentiation.html)
+    pub differentiation: Differentiation,
+}
+
+impl Default for Adam {
+    fn default() -> Adam {
+        Adam {
+            learning_rate: 0.01,
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+            max_iterations: 1000,
+            tolerance: 1e-8,
+            differentiation: Differentiation::default(),
+        }
+    }
+}
+
+impl Optimizer for Adam {
+    fn minimize_with_callback<F, C>(
+        &mut self,
+        objective: F,
+        initial: &[f64],
+        mut callback: C,
+    ) -> Minimum
+    where
+        F: FnMut(&[f64]) -> f64,
+        C: FnMut(&Iteration),
+    {
+        let mut objective = Counted::new(objective);
+        let mut x = initial.to_vec();
+        let mut value = objective.evaluate(&x);
+        callback(&Iteration {
+            iteration: 0,
+            parameters: &x,
+            value,
+            evaluations: objective.evaluations,
+        });
+
+        let mut first = vec![0.; x.len()];
+        let mut second = vec![0.; x.len()];
+        for iteration in 1..=self.max_iterations {
+            let gradient = self
+                .differentiation
+                .gradient(&mut |x: &[f64]| objective.evaluate(x), &x);
+            if gradient.iter().map(|g| g * g).sum::<f64>().sqrt() < self.tolerance {
+                break;
+            }
+
+            let correction1 = 1. - self.beta1.powi(iteration as i32);
+            let correction2 = 1. - self.beta2.powi(iteration as i32);
+            for (i, g) in gradient.iter().enumerate() {
+                first[i] = self.beta1 * first[i] + (1. - self.beta1) * g;
+                second[i] = self.beta2 * second[i] + (1. - self.beta2) * g * g;
+                let m = first[i] / correction1;
+                let v = second[i] / correction2;
+                x[i] -= self.learning_rate * m / (v.sqrt() + self.epsilon);
+            }
+            value = objective.evaluate(&x);
+            callback(&Iteration {
+                iteration,
+                parameters: &x,
+                value,
+                evaluations: objective.evaluations,
+            });
+        }
+
+        Minimum {
+            parameters: x,
+            value,
+            evaluations: objective.evaluations,
+        }
+    }
+}
@@ -0,0 +1,470 @@
+//!
+//! A module for a constrained optimization by linear approximations in the spirit of
+//! Powell's COBYLA.
+//!
+
+use super::{Counted, Iteration, Minimum, Optimizer};
+
+///
+/// A derivative-free optimizer for the objective functions with inequality constraints,
+/// which follows Powell's COBYLA in a simplified form.
+///
+/// The objective function and the constraints are approximated linearly by their values on a
+/// simplex, and the step minimizing the merit function
+/// $f(x) + \mu \sum_i \max(0, -c_i(x))$ of the approximations within the trust region is
+/// taken. The radius of the trust region is halved when the step does not decrease the merit
+/// function and doubled when the decrease is as predicted, and the search ends when the
+/// radius reaches `final_radius`.
+///
+/// The weight $\mu$ is increased to twice the estimates of the Lagrange multipliers from the
+/// approximations whenever the step violates their constraints.
+///
+/// The constraints $c_i(x) \ge 0$ may be violated by the result if no feasible point is found.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Cobyla {
+    /// The initial radius of the trust region
+    pub initial_radius: f64,
+    /// The final radius of the trust region
+    pub final_radius: f64,
+    /// The initial weight $\mu$ of the violation of the constraints
+    pub penalty: f64,
+    /// The maximum number of the iterations
+    pub max_iterations: usize,
+}
+
+impl Default for Cobyla {
+    fn default() -> Cobyla {
+        Cobyla {
+            initial_radius: 0.5,
+            final_radius: 1e-6,
+            penalty: 0.,
+            max_iterations: 1000,
+        }
+    }
+}
+
+/// A point on the simplex with the values of the objective function and the constraints.
+struct Vertex {
+    x: Vec<f64>,
+    value: f64,
+    constraints: Vec<f64>,
+    violation: f64,
+}
+
+impl Vertex {
+    fn merit(&self, penalty: f64) -> f64 {
+        self.value + penalty * self.violation
+    }
+}
+
+/// The objective function with the constraints.
+struct Problem<F, G> {
+    objective: Counted<F>,
+    constraints: G,
+}
+
+impl<F, G> Problem<F, G>
+where
+    F: FnMut(&[f64]) -> f64,
+    G: FnMut(&[f64]) -> Vec<f64>,
+{
+    fn vertex(&mut self, x: Vec<f64>) -> Vertex {
+        let value = self.objective.evaluate(&x);
+        let constraints = (self.constraints)(&x);
+        let violation = constraints.iter().map(|c| (-c).max(0.)).sum();
+        Vertex {
+            x,
+            value,
+            constraints,
+            violation,
+        }
+    }
+
+    /// Returns the simplex of the size `radius` around `best`, which is placed first.
+    fn simplex(&mut self, best: Vertex, radius: f64) -> Vec<Vertex> {
+        let mut simplex = Vec::with_capacity(best.x.len() + 1);
+        for i in 0..best.x.len() {
+            let mut x = best.x.clone();
+            x[i] += radius;
+            simplex.push(self.vertex(x));
+        }
+        simplex.insert(0, best);
+        simplex
+    }
+}
+
+impl Cobyla {
+    ///
+    /// Minimizes `objective` subject to `constraints(x)[i] >= 0` for all `i` starting from
+    /// `initial`, calling `callback` after each iteration.
+    ///
+    /// The constraints are evaluated at the same points as the objective function, and the
+    /// evaluations in the result are the ones of the objective function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusq::optimize::{Cobyla, Iteration};
+    ///
+    /// // The nearest point to (2, 1) in the half plane x + y <= 1
+    /// let mut optimizer = Cobyla::default();
+    /// let minimum = optimizer.minimize_constrained(
+    ///     |x: &[f64]| (x[0] - 2.).powi(2) + (x[1] - 1.).powi(2),
+    ///     |x: &[f64]| vec![1. - x[0] - x[1]],
+    ///     &[0., 0.],
+    ///     |_: &Iteration| {},
+    /// );
+    ///
+    /// assert!((minimum.parameters[0] - 1.).abs() < 1e-4);
+    /// assert!(minimum.parameters[1].abs() < 1e-4);
+    /// ```
+    ///
+    pub fn minimize_constrained<F, G, C>(
+        &mut self,
+        objective: F,
+        constraints: G,
+        initial: &[f64],
+        mut callback: C,
+    ) -> Minimum
+    where
+        F: FnMut(&[f64]) -> f64,
+        G: FnMut(&[f64]) -> Vec<f64>,
+        C: FnMut(&Iteration),
+    {
+        let n = initial.len();
+        let mut penalty = self.penalty;
+        let mut problem = Problem {
+            objective: Counted::new(objective),
+            constraints,
+        };
+
+        let mut radius = self.initial_radius;
+        let first = problem.vertex(initial.to_vec());
+        let mut simplex = problem.simplex(first, radius);
+        for iteration in 0.. {
+            let best = (0..=n)
+                .min_by(|&i, &j| {
+                    let (a, b) = (simplex[i].merit(penalty), simplex[j].merit(penalty));
+                    a.partial_cmp(&b).unwrap()
+                })
+                .unwrap();
+            simplex.swap(0, best);
+            callback(&Iteration {
+                iteration,
+                parameters: &simplex[0].x,
+                value: simplex[0].value,
+                evaluations: problem.objective.evaluations,
+            });
+            if iteration == self.max_iterations || n == 0 {
+                break;
+            }
+
+            let trial = linear_models(&simplex, radius).and_then(|(gradient, constraints)| {
+                let mut step = trust_step(&gradient, &constraints, penalty, radius);
+                let multiplier = multiplier(&gradient, &constraints, &step);
+                if 2. * multiplier > penalty {
+                    penalty = 2. * multiplier;
+                    step = trust_step(&gradient, &constraints, penalty, radius);
+                }
+
+                let model = |d: &[f64]| merit_model(&gradient, &constraints, penalty, d);
+                let predicted = model(&vec![0.; n]) - model(&step);
+                if predicted > 0. {
+                    let x = simplex[0].x.iter().zip(&step).map(|(x, d)| x + d);
+                    Some((x.collect::<Vec<_>>(), predicted))
+                } else {
+                    None
+                }
+            });
+
+            if let Some((x, predicted)) = trial {
+                let trial = problem.vertex(x);
+                let reduction = simplex[0].merit(penalty) - trial.merit(penalty);
+                if reduction > 0. {
+                    // The radius grows back if the models predict the reduction well
+                    // for a step to the boundary of the trust region.
+                    let length = distance(&simplex[0].x, &trial.x);
+                    if reduction > 0.75 * predicted && length > 0.9 * radius {
+                        radius = (2. * radius).min(self.initial_radius);
+                    }
+                    // The vertex farthest from the new best is replaced to keep the simplex
+                    // in the trust region.
+                    let farthest = (0..=n)
+                        .max_by(|&i, &j| {
+                            distance(&simplex[i].x, &trial.x)
+                                .partial_cmp(&distance(&simplex[j].x, &trial.x))
+                                .unwrap()
+                        })
+                        .unwrap();
+                    simplex[farthest] = trial;
+                    continue;
+                }
+            }
+
+            if radius <= self.final_radius {
+                break;
+            }
+            radius = (radius / 2.).max(self.final_radius);
+            let best = simplex.swap_remove(0);
+            simplex = problem.simplex(best, radius);
+        }
+
+        let best = simplex.swap_remove(0);
+        Minimum {
+            parameters: best.x,
+            value: best.value,
+            evaluations: problem.objective.evaluations,
+        }
+    }
+}
+
+impl Optimizer for Cobyla {
+    fn minimize_with_callback<F, C>(
+        &mut self,
+        objective: F,
+        initial: &[f64],
+        callback: C,
+    ) -> Minimum
+    where
+        F: FnMut(&[f64]) -> f64,
+        C: FnMut(&Iteration),
+    {
+        self.minimize_constrained(objective, |_: &[f64]| vec![], initial, callback)
+    }
+}
+
+fn distance(x: &[f64], y: &[f64]) -> f64 {
+    x.iter()
+        .zip(y)
+        .map(|(x, y)| (x - y).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+fn dot(x: &[f64], y: &[f64]) -> f64 {
+    x.iter().zip(y).map(|(x, y)| x * y).sum()
+}
+
+///
+/// Returns the gradients of the linear interpolations of the objective function and the
+/// constraints with their values at the first vertex, or `None` if the simplex is degenerate.
+///
+#[allow(clippy::type_complexity)]
+fn linear_models(simplex: &[Vertex], radius: f64) -> Option<(Vec<f64>, Vec<(f64, Vec<f64>)>)> {
+    let base = &simplex[0];
+
+    // (x_j - x_0) . g = f_j - f_0 for all the right-hand sides
+    let rows = simplex[1..]
+        .iter()
+        .map(|vertex| {
+            let mut row = vertex
+                .x
+                .iter()
+                .zip(&base.x)
+                .map(|(x, x0)| x - x0)
+                .collect::<Vec<_>>();
+            row.push(vertex.value - base.value);
+            row.extend(
+                vertex
+                    .constraints
+                    .iter()
+                    .zip(&base.constraints)
+                    .map(|(c, c0)| c - c0),
+            );
+            row
+        })
+        .collect();
+
+    let mut solutions = solve(rows, 1e-10 * radius)?.into_iter();
+    let gradient = solutions.next().unwrap();
+    let constraints = base.constraints.iter().cloned().zip(solutions).collect();
+    Some((gradient, constraints))
+}
+
+///
+/// Solves the linear equations given by the rows of the coefficients followed by the
+/// right-hand sides, by the Gaussian elimination with the partial pivoting.
+///
+/// Returns the solution for each right-hand side, or `None` if a pivot is less than
+/// `threshold`.
+///
+fn solve(mut rows: Vec<Vec<f64>>, threshold: f64) -> Option<Vec<Vec<f64>>> {
+    let n = rows.len();
+    for k in 0..n {
+        let pivot = (k..n)
+            .max_by(|&i, &j| rows[i][k].abs().partial_cmp(&rows[j][k].abs()).unwrap())
+            .unwrap();
+        if rows[pivot][k].abs() < threshold {
+            return None;
+        }
+        rows.swap(k, pivot);
+        let pivot = rows[k].clone();
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i != k {
+                let factor = row[k] / pivot[k];
+                for (r, p) in row.iter_mut().zip(&pivot).skip(k) {
+                    *r -= factor * p;
+                }
+            }
+        }
+    }
+
+    let columns = rows.first().map_or(0, |row| row.len() - n);
+    Some(
+        (n..n + columns)
+            .map(|l| {
+                rows.iter()
+                    .enumerate()
+                    .map(|(i, row)| row[l] / row[i])
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+///
+/// Returns the largest estimate $|\nabla f| / |\nabla c_i|$ of the Lagrange multipliers of
+/// the linearized constraints violated by `step`.
+///
+fn multiplier(gradient: &[f64], constraints: &[(f64, Vec<f64>)], step: &[f64]) -> f64 {
+    let norm = dot(gradient, gradient).sqrt();
+    constraints
+        .iter()
+        .filter(|&(c, a)| c + dot(a, step) < 0. && dot(a, a) > 0.)
+        .map(|(_, a)| norm / dot(a, a).sqrt())
+        .fold(0., f64::max)
+}
+
+fn merit_model(gradient: &[f64], constraints: &[(f64, Vec<f64>)], penalty: f64, d: &[f64]) -> f64 {
+    dot(gradient, d)
+        + penalty
+            * constraints
+                .iter()
+                .map(|(c, a)| (-(c + dot(a, d))).max(0.))
+                .sum::<f64>()
+}
+
+///
+/// Minimizes the linear model of the merit function within the ball of `radius`.
+///
+/// The minimum is found approximately by the projected subgradient method, and then exactly
+/// with the linearized constraints near or beyond their boundaries there taken as equalities.
+///
+fn trust_step(
+    gradient: &[f64],
+    constraints: &[(f64, Vec<f64>)],
+    penalty: f64,
+    radius: f64,
+) -> Vec<f64> {
+    const STEPS: usize = 200;
+
+    let model = |d: &[f64]| merit_model(gradient, constraints, penalty, d);
+    let mut d = vec![0.; gradient.len()];
+    let mut best = (d.clone(), model(&d));
+    for k in 0..STEPS {
+        let mut subgradient = gradient.to_vec();
+        for (c, a) in constraints {
+            if c + dot(a, &d) < 0. {
+                for (s, a) in subgradient.iter_mut().zip(a) {
+                    *s -= penalty * a;
+                }
+            }
+        }
+        let norm = dot(&subgradient, &subgradient).sqrt();
+        if norm == 0. {
+            break;
+        }
+
+        let length = radius / (k as f64 + 1.).sqrt();
+        for (d, s) in d.iter_mut().zip(&subgradient) {
+            *d -= length * s / norm;
+        }
+        let norm = dot(&d, &d).sqrt();
+        if norm > radius {
+            for d in &mut d {
+                *d *= radius / norm;
+            }
+        }
+
+        let value = model(&d);
+        if value < best.1 {
+            best = (d.clone(), value);
+        }
+    }
+
+    let active = constraints
+        .iter()
+        .filter(|&(c, a)| c + dot(a, &best.0) < 0.2 * radius * dot(a, a).sqrt())
+        .collect::<Vec<_>>();
+    match boundary_step(gradient, &active, radius) {
+        Some(ref d) if model(d) <= best.1 => d.clone(),
+        _ => best.0,
+    }
+}
+
+///
+/// Minimizes $g \cdot d$ within the ball of `radius` subject to $c_i + a_i \cdot d = 0$ for the
+/// constraints, or returns `None` if there is no such $d$.
+///
+fn boundary_step(
+    gradient: &[f64],
+    constraints: &[&(f64, Vec<f64>)],
+    radius: f64,
+) -> Option<Vec<f64>> {
+    // d = d_0 - t P g with the point d_0 of the minimum norm and the projection P onto the
+    // null space, where d_0 = A^T u and P g = g - A^T v for (A A^T) (u, v) = (-c, A g).
+    let rows = constraints
+        .iter()
+        .map(|&(c, a)| {
+            let mut row = constraints
+                .iter()
+                .map(|&(_, b)| dot(a, b))
+                .collect::<Vec<_>>();
+            row.push(-c);
+            row.push(dot(a, gradient));
+            row
+        })
+        .collect::<Vec<_>>();
+    let scale = constraints
+        .iter()
+        .map(|&(_, a)| dot(a, a))
+        .fold(0., f64::max);
+    let (u, v) = if constraints.is_empty() {
+        (vec![], vec![])
+    } else {
+        let mut solutions = solve(rows, 1e-12 * scale)?;
+        let v = solutions.pop().unwrap();
+        (solutions.pop().unwrap(), v)
+    };
+
+    let combine = |weights: &[f64]| {
+        let mut sum = vec![0.; gradient.len()];
+        for (&(_, a), w) in constraints.iter().zip(weights) {
+            for (s, a) in sum.iter_mut().zip(a) {
+                *s += w * a;
+            }
+        }
+        sum
+    };
+    let origin = combine(&u);
+    let projected = gradient
+        .iter()
+        .zip(combine(&v))
+        .map(|(g, a)| g - a)
+        .collect::<Vec<_>>();
+
+    let room = radius * radius - dot(&origin, &origin);
+    if room < 0. {
+        return None;
+    }
+    let norm = dot(&projected, &projected).sqrt();
+    let t = if norm > 0. { room.sqrt() / norm } else { 0. };
+    Some(
+        origin
+            .iter()
+            .zip(&projected)
+            .map(|(d, p)| d - t * p)
+            .collect(),
+    )
+}
@@ -0,0 +1,80 @@
+//!
+//! A module for the gradient descent.
+//!
+
+use super::{Counted, Differentiation, Iteration, Minimum, Optimizer};
+
+///
+/// The gradient descent with a fixed learning rate.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct GradientDescent {
+    /// The factor of the gradient in each step
+    pub learning_rate: f64,
+    /// The maximum number of the iterations
+    pub max_iterations: usize,
+    /// The iteration stops when the norm of the gradient is less than this.
+    pub tolerance: f64,
+    /// The estimation of the gradient, see [Differentiation](../enum.Differentiation.html)
+    pub differentiation: Differentiation,
+}
+
+impl Default for GradientDescent {
+    fn default() -> GradientDescent {
+        GradientDescent {
+            learning_rate: 0.1,
+            max_iterations: 1000,
+            tolerance: 1e-8,
+            differentiation: Differentiation::default(),
+        }
+    }
+}
+
+impl Optimizer for GradientDescent {
+    fn minimize_with_callback<F, C>(
+        &mut self,
+        objective: F,
+        initial: &[f64],
+        mut callback: C,
+    ) -> Minimum
+    where
+        F: FnMut(&[f64]) -> f64,
+        C: FnMut(&Iteration),
+    {
+        let mut objective = Counted::new(objective);
+        let mut x = initial.to_vec();
+        let mut value = objective.evaluate(&x);
+        callback(&Iteration {
+            iteration: 0,
+            parameters: &x,
+            value,
+            evaluations: objective.evaluations,
+        });
+
+        for iteration in 1..=self.max_iterations {
+            let gradient = self
+                .differentiation
+                .gradient(&mut |x: &[f64]| objective.evaluate(x), &x);
+            if gradient.iter().map(|g| g * g).sum::<f64>().sqrt() < self.tolerance {
+                break;
+            }
+
+            for (x, g) in x.iter_mut().zip(&gradient) {
+                *x -= self.learning_rate * g;
+            }
+            value = objective.evaluate(&x);
+            callback(&Iteration {
+                iteration,
+                parameters: &x,
+                value,
+                evaluations: objective.evaluations,
+            });
+        }
+
+        Minimum {
+            parameters: x,
+            value,
+            evaluations: objective.evaluations,
+        }
+    }
+}
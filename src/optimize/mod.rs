@@ -0,0 +1,183 @@
+//!
+//! A module for classical optimizers for the variational algorithms.
+//!
+//! The optimizers minimize an objective function of real parameters, which usually runs a
+//! circuit on a simulator and returns an expectation value.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::optimize::{NelderMead, Optimizer};
+//!
+//! let mut optimizer = NelderMead::default();
+//! let minimum = optimizer.minimize(|x: &[f64]| (x[0] - 1.).powi(2) + (x[1] + 2.).powi(2), &[0., 0.]);
+//!
+//! assert!((minimum.parameters[0] - 1.).abs() < 1e-3);
+//! assert!((minimum.parameters[1] + 2.).abs() < 1e-3);
+//! ```
+//!
+//! The progress can be logged by a callback called for each iteration:
+//!
+//! ```
+//! use rusq::optimize::{Adam, Iteration, Optimizer};
+//!
+//! let mut optimizer = Adam {
+//!     learning_rate: 0.1,
+//!     ..Adam::default()
+//! };
+//! let mut values = vec![];
+//! let minimum = optimizer.minimize_with_callback(
+//!     |x: &[f64]| x[0].cos(),
+//!     &[0.5],
+//!     |iteration: &Iteration| values.push(iteration.value),
+//! );
+//!
+//! assert!((minimum.value + 1.).abs() < 1e-6);
+//! assert!(values[0] > values[values.len() - 1]);
+//! ```
+//!
+
+pub mod adam;
+pub mod cobyla;
+pub mod gradient_descent;
+pub mod nelder_mead;
+pub mod spsa;
+
+pub use self::adam::Adam;
+pub use self::cobyla::Cobyla;
+pub use self::gradient_descent::GradientDescent;
+pub use self::nelder_mead::NelderMead;
+pub use self::spsa::Spsa;
+
+use std::f64::consts::PI;
+
+///
+/// A type for the result of a minimization.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Minimum {
+    /// The parameters at the minimum found
+    pub parameters: Vec<f64>,
+    /// The value of the objective function at the minimum found
+    pub value: f64,
+    /// The number of the evaluations of the objective function
+    pub evaluations: usize,
+}
+
+///
+/// A type for the state of an optimizer passed to the callback after each iteration.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Iteration<'a> {
+    /// The number of the iterations done so far
+    pub iteration: usize,
+    /// The current parameters
+    pub parameters: &'a [f64],
+    /// The value of the objective function at the current parameters
+    pub value: f64,
+    /// The number of the evaluations of the objective function so far
+    pub evaluations: usize,
+}
+
+///
+/// A trait for the optimizers which minimize an objective function.
+///
+pub trait Optimizer {
+    ///
+    /// Minimizes `objective` starting from `initial`, calling `callback` after each iteration.
+    ///
+    fn minimize_with_callback<F, C>(
+        &mut self,
+        objective: F,
+        initial: &[f64],
+        callback: C,
+    ) -> Minimum
+    where
+        F: FnMut(&[f64]) -> f64,
+        C: FnMut(&Iteration);
+
+    ///
+    /// Minimizes `objective` starting from `initial`.
+    ///
+    fn minimize<F>(&mut self, objective: F, initial: &[f64]) -> Minimum
+    where
+        F: FnMut(&[f64]) -> f64,
+    {
+        self.minimize_with_callback(objective, initial, |_: &Iteration| {})
+    }
+}
+
+///
+/// A type for the ways to estimate the gradient from the values of the objective function.
+///
+/// [GradientDescent](gradient_descent/struct.GradientDescent.html) and
+/// [Adam](adam/struct.Adam.html) estimate the gradient by this at each step, and evaluate
+/// the objective function once more for the callback.
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Differentiation {
+    /// The central finite difference with the given step
+    FiniteDifference(f64),
+    ///
+    /// The parameter-shift rule $(f(\theta + \pi/2) - f(\theta - \pi/2)) / 2$, which is exact
+    /// if each parameter is the angle of a single rotation gate such as
+    /// [rx](../gates/single/trait.SingleGateApplicator.html#method.rx).
+    ///
+    ParameterShift,
+}
+
+impl Differentiation {
+    ///
+    /// Estimates the gradient of `objective` at `x`, evaluating it twice for each parameter.
+    ///
+    pub fn gradient<F>(self, objective: &mut F, x: &[f64]) -> Vec<f64>
+    where
+        F: FnMut(&[f64]) -> f64,
+    {
+        let (shift, factor) = match self {
+            Differentiation::FiniteDifference(h) => (h, 1. / (2. * h)),
+            Differentiation::ParameterShift => (PI / 2., 0.5),
+        };
+
+        let mut shifted = x.to_vec();
+        (0..x.len())
+            .map(|i| {
+                shifted[i] = x[i] + shift;
+                let plus = objective(&shifted);
+                shifted[i] = x[i] - shift;
+                let minus = objective(&shifted);
+                shifted[i] = x[i];
+                factor * (plus - minus)
+            })
+            .collect()
+    }
+}
+
+impl Default for Differentiation {
+    fn default() -> Differentiation {
+        Differentiation::FiniteDifference(1e-6)
+    }
+}
+
+/// An objective function counting its evaluations.
+pub(crate) struct Counted<F> {
+    objective: F,
+    pub(crate) evaluations: usize,
+}
+
+impl<F> Counted<F>
+where
+    F: FnMut(&[f64]) -> f64,
+{
+    pub(crate) fn new(objective: F) -> Counted<F> {
+        Counted {
+            objective,
+            evaluations: 0,
+        }
+    }
+
+    pub(crate) fn evaluate(&mut self, x: &[f64]) -> f64 {
+        self.evaluations += 1;
+        (self.objective)(x)
+    }
+}
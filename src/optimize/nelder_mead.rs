@@ -0,0 +1,149 @@
+//!
+//! A module for the Nelder–Mead simplex method.
+//!
+
+use super::{Counted, Iteration, Minimum, Optimizer};
+
+///
+/// The Nelder–Mead simplex method, which needs no gradients.
+///
+/// The standard coefficients are used: 1 for the reflection, 2 for the expansion and
+/// 1/2 for the contraction and the shrink.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct NelderMead {
+    /// The maximum number of the iterations
+    pub max_iterations: usize,
+    /// The iteration stops when the values on the simplex differ less than this.
+    pub tolerance: f64,
+    /// The size of the initial simplex along each parameter
+    pub initial_step: f64,
+}
+
+impl Default for NelderMead {
+    fn default() -> NelderMead {
+        NelderMead {
+            max_iterations: 1000,
+            tolerance: 1e-10,
+            initial_step: 0.5,
+        }
+    }
+}
+
+impl Optimizer for NelderMead {
+    fn minimize_with_callback<F, C>(
+        &mut self,
+        objective: F,
+        initial: &[f64],
+        mut callback: C,
+    ) -> Minimum
+    where
+        F: FnMut(&[f64]) -> f64,
+        C: FnMut(&Iteration),
+    {
+        let n = initial.len();
+        let mut objective = Counted::new(objective);
+        if n == 0 {
+            let value = objective.evaluate(initial);
+            callback(&Iteration {
+                iteration: 0,
+                parameters: initial,
+                value,
+                evaluations: 1,
+            });
+            return Minimum {
+                parameters: vec![],
+                value,
+                evaluations: 1,
+            };
+        }
+
+        let mut simplex = vec![initial.to_vec()];
+        for i in 0..n {
+            let mut vertex = initial.to_vec();
+            vertex[i] += self.initial_step;
+            simplex.push(vertex);
+        }
+        let mut values = simplex
+            .iter()
+            .map(|x| objective.evaluate(x))
+            .collect::<Vec<_>>();
+
+        for iteration in 0.. {
+            let mut order = (0..=n).collect::<Vec<_>>();
+            order.sort_by(|&i, &j| values[i].partial_cmp(&values[j]).unwrap());
+            simplex = order.iter().map(|&i| simplex[i].clone()).collect();
+            values = order.iter().map(|&i| values[i]).collect();
+
+            callback(&Iteration {
+                iteration,
+                parameters: &simplex[0],
+                value: values[0],
+                evaluations: objective.evaluations,
+            });
+            if iteration == self.max_iterations || values[n] - values[0] <= self.tolerance {
+                break;
+            }
+
+            let centroid = (0..n)
+                .map(|k| simplex[..n].iter().map(|x| x[k]).sum::<f64>() / n as f64)
+                .collect::<Vec<_>>();
+            // The point on the line from the worst vertex through the centroid
+            let towards = |t: f64, worst: &[f64]| {
+                centroid
+                    .iter()
+                    .zip(worst)
+                    .map(|(c, w)| c + t * (c - w))
+                    .collect::<Vec<_>>()
+            };
+
+            let reflected = towards(1., &simplex[n]);
+            let reflected_value = objective.evaluate(&reflected);
+            if reflected_value < values[0] {
+                let expanded = towards(2., &simplex[n]);
+                let expanded_value = objective.evaluate(&expanded);
+                if expanded_value < reflected_value {
+                    simplex[n] = expanded;
+                    values[n] = expanded_value;
+                } else {
+                    simplex[n] = reflected;
+                    values[n] = reflected_value;
+                }
+                continue;
+            }
+            if reflected_value < values[n - 1] {
+                simplex[n] = reflected;
+                values[n] = reflected_value;
+                continue;
+            }
+
+            let (contracted, threshold) = if reflected_value < values[n] {
+                (towards(0.5, &simplex[n]), reflected_value)
+            } else {
+                (towards(-0.5, &simplex[n]), values[n])
+            };
+            let contracted_value = objective.evaluate(&contracted);
+            if contracted_value < threshold {
+                simplex[n] = contracted;
+                values[n] = contracted_value;
+                continue;
+            }
+
+            for i in 1..=n {
+                simplex[i] = simplex[0]
+                    .iter()
+                    .zip(&simplex[i])
+                    .map(|(best, x)| best + 0.5 * (x - best))
+                    .collect();
+                values[i] = objective.evaluate(&simplex[i]);
+            }
+        }
+
+        // The simplex is sorted at the end of the last iteration.
+        Minimum {
+            parameters: simplex[0].clone(),
+            value: values[0],
+            evaluations: objective.evaluations,
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//!
+//! A module for the simultaneous perturbation stochastic approximation (SPSA).
+//!
+
+use super::{Counted, Iteration, Minimum, Optimizer};
+use rand::Rng;
+use simulator::simulator::seeded_rng;
+
+///
+/// The SPSA by Spall, which estimates the gradient from two evaluations along a random
+/// direction for each iteration, however many parameters there are.
+///
+/// It tolerates the noise of the objective function, e.g. the expectation values from shots.
+/// The step at the `k`-th iteration is
+/// $a / (k + 1 + A)^\alpha$ times the gradient estimated with the perturbation of the size
+/// $c / (k + 1)^\gamma$ along each parameter.
+///
+/// The value passed to the callback is the mean of the two evaluations, and the objective
+/// function is evaluated once more at the end for the value of the result.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Spsa {
+    /// The number of the iterations
+    pub max_iterations: usize,
+    /// The scale $a$ of the steps
+    pub a: f64,
+    /// The scale $c$ of the perturbations
+    pub c: f64,
+    /// The stability constant $A$ delaying the decay of the steps
+    pub stability: f64,
+    /// The exponent $\alpha$ of the decay of the steps
+    pub alpha: f64,
+    /// The exponent $\gamma$ of the decay of the perturbations
+    pub gamma: f64,
+    /// The seed of the random perturbations
+    pub seed: u64,
+}
+
+impl Default for Spsa {
+    fn default() -> Spsa {
+        Spsa {
+            max_iterations: 500,
+            a: 0.5,
+            c: 0.1,
+            stability: 10.,
+            alpha: 0.602,
+            gamma: 0.101,
+            seed: 0,
+        }
+    }
+}
+
+impl Optimizer for Spsa {
+    fn minimize_with_callback<F, C>(
+        &mut self,
+        objective: F,
+        initial: &[f64],
+        mut callback: C,
+    ) -> Minimum
+    where
+        F: FnMut(&[f64]) -> f64,
+        C: FnMut(&Iteration),
+    {
+        let mut objective = Counted::new(objective);
+        let mut rng = seeded_rng(self.seed);
+        let mut x = initial.to_vec();
+
+        for k in 0..self.max_iterations {
+            let step = self.a / (k as f64 + 1. + self.stability).powf(self.alpha);
+            let perturbation = self.c / (k as f64 + 1.).powf(self.gamma);
+            let direction = (0..x.len())
+                .map(|_| if rng.gen() { 1. } else { -1. })
+                .collect::<Vec<f64>>();
+
+            let shifted = |sign: f64| {
+                x.iter()
+                    .zip(&direction)
+                    .map(|(x, d)| x + sign * perturbation * d)
+                    .collect::<Vec<_>>()
+            };
+            let plus = objective.evaluate(&shifted(1.));
+            let minus = objective.evaluate(&shifted(-1.));
+
+            // The components of the estimated gradient are (plus - minus) / (2 c_k d_i),
+            // where 1 / d_i = d_i for d_i = ±1.
+            let slope = (plus - minus) / (2. * perturbation);
+            for (x, d) in x.iter_mut().zip(&direction) {
+                *x -= step * slope * d;
+            }
+            callback(&Iteration {
+                iteration: k + 1,
+                parameters: &x,
+                value: (plus + minus) / 2.,
+                evaluations: objective.evaluations,
+            });
+        }
+
+        let value = objective.evaluate(&x);
+        Minimum {
+            parameters: x,
+            value,
+            evaluations: objective.evaluations,
+        }
+    }
+}
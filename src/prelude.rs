@@ -3,10 +3,14 @@
 //!
 
 pub use simulator::QuantumSimulator;
+pub use simulator::UnitarySimulator;
 pub use QuantumMachine;
 pub use MeasuredResult;
 pub use Qubit;
+pub use QubitRegister;
+pub use Endianness;
 
 pub use gates::single::SingleGateApplicator;
 pub use gates::double::DoubleGateApplicator;
 pub use gates::triple::TripleGateApplicator;
+pub use gates::multi::MultiGateApplicator;
@@ -0,0 +1,224 @@
+//!
+//! A module for the detector error models of noisy circuits.
+//!
+//! A detector error model lists the independent error mechanisms of a
+//! [NoisyCircuit](../frame/struct.NoisyCircuit.html) with their probabilities and the
+//! detectors and the observable they flip, which are found by propagating each Pauli error
+//! to the end of the circuit.
+//!
+
+use qec::frame::{Frame, Instruction, NoisyCircuit};
+use std::collections::HashMap;
+
+///
+/// A type for the flips of the detectors and the observable by an error.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+pub struct Symptom {
+    /// The sorted indices of the flipped detectors
+    pub detectors: Vec<usize>,
+    /// Whether the observable is flipped
+    pub observable: bool,
+}
+
+impl Symptom {
+    /// Returns the symptom of the two errors occurring together.
+    pub fn combine(&self, other: &Symptom) -> Symptom {
+        let mut detectors = self.detectors.clone();
+        for d in &other.detectors {
+            match detectors.binary_search(d) {
+                Ok(i) => {
+                    detectors.remove(i);
+                }
+                Err(i) => detectors.insert(i, *d),
+            }
+        }
+        Symptom {
+            detectors,
+            observable: self.observable ^ other.observable,
+        }
+    }
+
+    /// Returns `true` if nothing is flipped.
+    pub fn is_empty(&self) -> bool {
+        self.detectors.is_empty() && !self.observable
+    }
+}
+
+///
+/// A type for an error mechanism in a detector error model.
+///
+/// A mechanism flipping more than two detectors is decomposed into the symptoms of its
+/// single-qubit $X$ and $Z$ parts, so that each of them is an edge for the matching.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct ErrorMechanism {
+    /// The probability of the error
+    pub probability: f64,
+    /// The symptoms whose combination is the one of the error
+    pub symptoms: Vec<Symptom>,
+}
+
+impl ErrorMechanism {
+    /// Returns the combined symptom of the error.
+    pub fn symptom(&self) -> Symptom {
+        self.symptoms
+            .iter()
+            .fold(Symptom::default(), |symptom, s| symptom.combine(s))
+    }
+}
+
+///
+/// A type for the detector error model of a noisy circuit.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct DetectorErrorModel {
+    detectors_len: usize,
+    errors: Vec<ErrorMechanism>,
+}
+
+impl DetectorErrorModel {
+    ///
+    /// Extracts the model of `circuit`.
+    ///
+    /// The mechanisms with the same symptoms are merged into one, and the ones flipping
+    /// nothing are dropped.
+    ///
+    pub fn from_circuit(circuit: &NoisyCircuit) -> DetectorErrorModel {
+        let mut detectors_of = vec![vec![]; circuit.measurements_len()];
+        for (d, measurements) in circuit.detectors().iter().enumerate() {
+            for &m in measurements {
+                detectors_of[m].push(d);
+            }
+        }
+        let flip = |m: usize| {
+            Symptom {
+                detectors: detectors_of[m].clone(),
+                observable: false,
+            }
+            .combine(&Symptom {
+                detectors: vec![],
+                observable: circuit.observable().contains(&m),
+            })
+        };
+
+        let mut model = DetectorErrorModel {
+            detectors_len: circuit.detectors().len(),
+            errors: vec![],
+        };
+        let mut indices = HashMap::new();
+        let mut measurement = 0;
+        for (i, &instruction) in circuit.instructions().iter().enumerate() {
+            // The symptoms of X and Z on the qubit after the i-th instruction
+            let pauli = |q: usize, x: bool| {
+                let mut frame = Frame::new(circuit.qubits_len());
+                if x {
+                    frame.x[q] = true;
+                } else {
+                    frame.z[q] = true;
+                }
+                propagate(circuit, i + 1, measurement, frame)
+                    .into_iter()
+                    .fold(Symptom::default(), |symptom, m| symptom.combine(&flip(m)))
+            };
+
+            match instruction {
+                Instruction::Measure(_, p) => {
+                    model.add(&mut indices, p, vec![flip(measurement)]);
+                    measurement += 1;
+                }
+                Instruction::Pauli(q, channel) => {
+                    let (x, z) = (pauli(q, true), pauli(q, false));
+                    model.add(&mut indices, channel.x, vec![x.clone()]);
+                    model.add(&mut indices, channel.y, vec![x, z.clone()]);
+                    model.add(&mut indices, channel.z, vec![z]);
+                }
+                Instruction::Depolarize2(q1, q2, p) => {
+                    let parts = [
+                        pauli(q1, true),
+                        pauli(q1, false),
+                        pauli(q2, true),
+                        pauli(q2, false),
+                    ];
+                    for error in 1..16 {
+                        let symptoms = (0..4)
+                            .filter(|k| error >> k & 1 == 1)
+                            .map(|k| parts[k].clone())
+                            .collect();
+                        model.add(&mut indices, p / 15., symptoms);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        model
+    }
+
+    fn add(&mut self, indices: &mut HashMap<Vec<Symptom>, usize>, p: f64, parts: Vec<Symptom>) {
+        if p == 0. {
+            return;
+        }
+
+        let combined = parts
+            .iter()
+            .fold(Symptom::default(), |symptom, s| symptom.combine(s));
+        if combined.is_empty() {
+            return;
+        }
+        let mut symptoms = if combined.detectors.len() <= 2 {
+            vec![combined]
+        } else {
+            parts.into_iter().filter(|s| !s.is_empty()).collect()
+        };
+        symptoms.sort_by(|a, b| a.detectors.cmp(&b.detectors));
+
+        match indices.get(&symptoms) {
+            Some(&i) => {
+                let q = self.errors[i].probability;
+                self.errors[i].probability = p * (1. - q) + q * (1. - p);
+            }
+            None => {
+                indices.insert(symptoms.clone(), self.errors.len());
+                self.errors.push(ErrorMechanism {
+                    probability: p,
+                    symptoms,
+                });
+            }
+        }
+    }
+
+    /// Returns the number of the detectors.
+    pub fn detectors_len(&self) -> usize {
+        self.detectors_len
+    }
+
+    /// Returns the error mechanisms.
+    pub fn errors(&self) -> &[ErrorMechanism] {
+        &self.errors
+    }
+}
+
+///
+/// Propagates `frame` from the `start`-th instruction, where the index of the next
+/// measurement is `measurement`, and returns the indices of the flipped measurements.
+///
+fn propagate(
+    circuit: &NoisyCircuit,
+    start: usize,
+    mut measurement: usize,
+    mut frame: Frame,
+) -> Vec<usize> {
+    let mut flips = vec![];
+    for &instruction in &circuit.instructions()[start..] {
+        if let Instruction::Measure(q, _) = instruction {
+            if frame.x[q] {
+                flips.push(measurement);
+            }
+            measurement += 1;
+        } else {
+            frame.apply(instruction);
+        }
+    }
+    flips
+}
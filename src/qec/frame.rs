@@ -0,0 +1,331 @@
+//!
+//! A module for noisy Clifford circuits sampled by Pauli frames.
+//!
+//! A [NoisyCircuit](struct.NoisyCircuit.html) consists of resets, H, CNOT and measurements
+//! in the $Z$ basis together with Pauli noise. Its detectors are the parities of measurement
+//! results which are deterministic without the noise, and its observable is the parity of
+//! measurement results giving the logical state.
+//!
+//! Since the Pauli errors are only conjugated by the Clifford gates, the flips of the
+//! detectors and the observable are sampled by tracking the Pauli error, the Pauli frame,
+//! without simulating the state. This allows circuits on many qubits, such as the surface
+//! code, which are beyond the state vector simulator.
+//!
+
+use rand::Rng;
+use simulator::simulator::seeded_rng;
+
+///
+/// A type for a Pauli channel on a qubit, which applies $X$, $Y$ and $Z$ with the given
+/// probabilities.
+///
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct PauliChannel {
+    /// The probability of $X$
+    pub x: f64,
+    /// The probability of $Y$
+    pub y: f64,
+    /// The probability of $Z$
+    pub z: f64,
+}
+
+impl PauliChannel {
+    /// The depolarizing channel applying each of $X$, $Y$ and $Z$ with the probability $p/3$.
+    pub fn depolarizing(p: f64) -> PauliChannel {
+        PauliChannel {
+            x: p / 3.,
+            y: p / 3.,
+            z: p / 3.,
+        }
+    }
+
+    /// The channel applying $X$ with the probability $p$.
+    pub fn bit_flip(p: f64) -> PauliChannel {
+        PauliChannel {
+            x: p,
+            ..PauliChannel::default()
+        }
+    }
+
+    /// The channel applying $Z$ with the probability $p$.
+    pub fn phase_flip(p: f64) -> PauliChannel {
+        PauliChannel {
+            z: p,
+            ..PauliChannel::default()
+        }
+    }
+
+    /// Returns `true` if the channel does nothing.
+    pub fn is_noiseless(&self) -> bool {
+        self.x == 0. && self.y == 0. && self.z == 0.
+    }
+}
+
+///
+/// A type for the instructions of a noisy circuit, where the qubits are given by the indices.
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Instruction {
+    /// Resets the qubit to $|0\rangle$.
+    Reset(usize),
+    /// The Hadamard gate
+    H(usize),
+    /// The CNOT gate with the control and the target
+    CNOT(usize, usize),
+    /// Measures the qubit in the $Z$ basis, whose result is flipped with the probability.
+    Measure(usize, f64),
+    /// Applies the Pauli channel to the qubit.
+    Pauli(usize, PauliChannel),
+    ///
+    /// The two-qubit depolarizing channel, which applies each of the 15 non-identity Pauli
+    /// operators with the probability $p/15$.
+    ///
+    Depolarize2(usize, usize, f64),
+}
+
+///
+/// A type for the flips of the detectors and the observable in a shot.
+///
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Sample {
+    /// Whether each detector is flipped
+    pub detectors: Vec<bool>,
+    /// Whether the observable is flipped
+    pub observable: bool,
+}
+
+///
+/// A type for noisy Clifford circuits with detectors and an observable.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct NoisyCircuit {
+    qubits_len: usize,
+    instructions: Vec<Instruction>,
+    measurements_len: usize,
+    detectors: Vec<Vec<usize>>,
+    observable: Vec<usize>,
+}
+
+impl NoisyCircuit {
+    /// Creates an empty circuit on `qubits_len` qubits, which are initially $|0\rangle$.
+    pub fn new(qubits_len: usize) -> NoisyCircuit {
+        NoisyCircuit {
+            qubits_len,
+            instructions: vec![],
+            measurements_len: 0,
+            detectors: vec![],
+            observable: vec![],
+        }
+    }
+
+    /// Returns the number of the qubits.
+    pub fn qubits_len(&self) -> usize {
+        self.qubits_len
+    }
+
+    /// Returns the instructions.
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Returns the number of the measurements.
+    pub fn measurements_len(&self) -> usize {
+        self.measurements_len
+    }
+
+    /// Returns the detectors as the indices of the measurements in the order of the circuit.
+    pub fn detectors(&self) -> &[Vec<usize>] {
+        &self.detectors
+    }
+
+    /// Returns the observable as the indices of the measurements.
+    pub fn observable(&self) -> &[usize] {
+        &self.observable
+    }
+
+    ///
+    /// Appends `instruction`, and returns the index of the measurement if it is a measurement.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `instruction` acts on a qubit out of range, or on the same qubit twice.
+    ///
+    pub fn push(&mut self, instruction: Instruction) -> Option<usize> {
+        let qubits = match instruction {
+            Instruction::Reset(q)
+            | Instruction::H(q)
+            | Instruction::Measure(q, _)
+            | Instruction::Pauli(q, _) => vec![q],
+            Instruction::CNOT(q1, q2) | Instruction::Depolarize2(q1, q2, _) => vec![q1, q2],
+        };
+        for &q in &qubits {
+            assert!(
+                q < self.qubits_len,
+                "qubit {} is out of range for {} qubits",
+                q,
+                self.qubits_len
+            );
+        }
+        assert!(
+            qubits.len() < 2 || qubits[0] != qubits[1],
+            "a two-qubit instruction needs two different qubits"
+        );
+
+        self.instructions.push(instruction);
+        if let Instruction::Measure(..) = instruction {
+            self.measurements_len += 1;
+            Some(self.measurements_len - 1)
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Adds a detector as the parity of the measurements, and returns its index.
+    ///
+    /// The parity must be deterministic in the circuit without the noise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a measurement is not yet in the circuit.
+    ///
+    pub fn add_detector(&mut self, measurements: Vec<usize>) -> usize {
+        self.check_measurements(&measurements);
+        self.detectors.push(measurements);
+        self.detectors.len() - 1
+    }
+
+    ///
+    /// Sets the observable to the parity of the measurements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a measurement is not yet in the circuit.
+    ///
+    pub fn set_observable(&mut self, measurements: Vec<usize>) {
+        self.check_measurements(&measurements);
+        self.observable = measurements;
+    }
+
+    fn check_measurements(&self, measurements: &[usize]) {
+        for &m in measurements {
+            assert!(
+                m < self.measurements_len,
+                "measurement {} is not in the circuit with {} measurements",
+                m,
+                self.measurements_len
+            );
+        }
+    }
+
+    /// Returns the circuit without the noise.
+    pub fn without_noise(&self) -> NoisyCircuit {
+        let instructions = self
+            .instructions
+            .iter()
+            .filter_map(|&instruction| match instruction {
+                Instruction::Pauli(..) | Instruction::Depolarize2(..) => None,
+                Instruction::Measure(q, _) => Some(Instruction::Measure(q, 0.)),
+                instruction => Some(instruction),
+            })
+            .collect();
+
+        NoisyCircuit {
+            instructions,
+            ..self.clone()
+        }
+    }
+
+    ///
+    /// Samples the flips of the detectors and the observable in `shots` shots, which are
+    /// reproducible by `seed`.
+    ///
+    pub fn sample(&self, shots: usize, seed: u64) -> Vec<Sample> {
+        let mut rng = seeded_rng(seed);
+        (0..shots)
+            .map(|_| {
+                let mut frame = Frame::new(self.qubits_len);
+                let mut flips = Vec::with_capacity(self.measurements_len);
+                for &instruction in &self.instructions {
+                    match instruction {
+                        Instruction::Measure(q, p) => {
+                            flips.push(frame.x[q] ^ (rng.gen::<f64>() < p));
+                        }
+                        Instruction::Pauli(q, channel) => {
+                            let r = rng.gen::<f64>();
+                            if r < channel.x {
+                                frame.x[q] ^= true;
+                            } else if r < channel.x + channel.y {
+                                frame.x[q] ^= true;
+                                frame.z[q] ^= true;
+                            } else if r < channel.x + channel.y + channel.z {
+                                frame.z[q] ^= true;
+                            }
+                        }
+                        Instruction::Depolarize2(q1, q2, p) => {
+                            if rng.gen::<f64>() < p {
+                                // The two bits of the index are (x, z) of each qubit.
+                                let pauli = rng.gen_range(1, 16);
+                                frame.x[q1] ^= pauli & 1 == 1;
+                                frame.z[q1] ^= pauli & 2 == 2;
+                                frame.x[q2] ^= pauli & 4 == 4;
+                                frame.z[q2] ^= pauli & 8 == 8;
+                            }
+                        }
+                        instruction => frame.apply(instruction),
+                    }
+                }
+                self.sample_from(&flips)
+            })
+            .collect()
+    }
+
+    /// Returns the sample for the flips of the measurements.
+    pub(crate) fn sample_from(&self, flips: &[bool]) -> Sample {
+        let parity = |measurements: &[usize]| {
+            measurements
+                .iter()
+                .fold(false, |parity, &m| parity ^ flips[m])
+        };
+        Sample {
+            detectors: self.detectors.iter().map(|d| parity(d)).collect(),
+            observable: parity(&self.observable),
+        }
+    }
+}
+
+///
+/// A Pauli operator on all the qubits, which is propagated through the gates.
+///
+pub(crate) struct Frame {
+    pub(crate) x: Vec<bool>,
+    pub(crate) z: Vec<bool>,
+}
+
+impl Frame {
+    pub(crate) fn new(qubits_len: usize) -> Frame {
+        Frame {
+            x: vec![false; qubits_len],
+            z: vec![false; qubits_len],
+        }
+    }
+
+    /// Conjugates the frame by a gate or a reset, ignoring the noise and the measurements.
+    pub(crate) fn apply(&mut self, instruction: Instruction) {
+        match instruction {
+            Instruction::Reset(q) => {
+                self.x[q] = false;
+                self.z[q] = false;
+            }
+            Instruction::H(q) => {
+                std::mem::swap(&mut self.x[q], &mut self.z[q]);
+            }
+            Instruction::CNOT(c, t) => {
+                let (xc, zt) = (self.x[c], self.z[t]);
+                self.x[t] ^= xc;
+                self.z[c] ^= zt;
+            }
+            Instruction::Measure(..) | Instruction::Pauli(..) | Instruction::Depolarize2(..) => {}
+        }
+    }
+}
@@ -0,0 +1,755 @@
+//!
+//! A module for decoding by minimum-weight perfect matching.
+//!
+//! The [MatchingDecoder](struct.MatchingDecoder.html) builds a graph from a
+//! [DetectorErrorModel](../dem/struct.DetectorErrorModel.html), whose nodes are the detectors
+//! and a boundary, and whose edges are the error mechanisms flipping one or two detectors
+//! weighted by $\ln((1-p)/p)$. The flipped detectors are paired up along the shortest paths,
+//! or paired with the boundary, by the minimum-weight perfect matching, and the parity of
+//! the observable flips along the paths is the prediction.
+//!
+//! The matching is found by Edmonds' blossom algorithm, after the implementation by
+//! Joris van Rantwijk.
+//!
+
+use qec::dem::DetectorErrorModel;
+use qec::frame::NoisyCircuit;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+///
+/// Returns the minimum-weight perfect matching of the graph on `nodes` nodes with the
+/// weighted edges `(i, j, w)`, as the node matched to each node, or `None` if no perfect
+/// matching exists.
+///
+/// # Examples
+///
+/// ```
+/// use rusq::qec::matching::minimum_weight_perfect_matching;
+///
+/// let edges = [(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 5)];
+/// assert_eq!(
+///     minimum_weight_perfect_matching(4, &edges),
+///     Some(vec![1, 0, 3, 2])
+/// );
+/// assert_eq!(minimum_weight_perfect_matching(3, &edges[..2]), None);
+/// ```
+///
+pub fn minimum_weight_perfect_matching(
+    nodes: usize,
+    edges: &[(usize, usize, i64)],
+) -> Option<Vec<usize>> {
+    for &(i, j, _) in edges {
+        assert!(
+            i < nodes && j < nodes,
+            "edge ({}, {}) is out of range for {} nodes",
+            i,
+            j,
+            nodes
+        );
+        assert_ne!(i, j, "a loop is not allowed");
+    }
+
+    // The perfect matchings are the maximum-cardinality ones of the maximum weight for the
+    // weights subtracted from a larger one.
+    let max = edges.iter().map(|e| e.2).max().unwrap_or(0);
+    let edges: Vec<_> = edges.iter().map(|&(i, j, w)| (i, j, max + 1 - w)).collect();
+
+    let mate = Blossom::new(nodes, edges).solve();
+    if mate.iter().all(|&m| m != NONE) {
+        Some(mate)
+    } else {
+        None
+    }
+}
+
+const NONE: usize = !0;
+
+///
+/// The state of the maximum-weight maximum-cardinality matching by the blossom algorithm.
+///
+/// The nodes are `0..n`, and the non-trivial blossoms are `n..2n`. The endpoint `p` of the
+/// edge `p / 2` is `edges[p / 2].0` if `p` is even, and `edges[p / 2].1` otherwise.
+///
+struct Blossom {
+    n: usize,
+    edges: Vec<(usize, usize, i64)>,
+    endpoint: Vec<usize>,
+    neighbors: Vec<Vec<usize>>,
+    mate: Vec<usize>,
+    label: Vec<u8>,
+    label_end: Vec<usize>,
+    in_blossom: Vec<usize>,
+    parent: Vec<usize>,
+    children: Vec<Vec<usize>>,
+    base: Vec<usize>,
+    endpoints: Vec<Vec<usize>>,
+    best_edge: Vec<usize>,
+    best_edges: Vec<Option<Vec<usize>>>,
+    unused: Vec<usize>,
+    dual: Vec<i64>,
+    allowed: Vec<bool>,
+    queue: Vec<usize>,
+}
+
+// The labels of the blossoms; a blossom is temporarily `BREADCRUMB` in `scan`.
+const FREE: u8 = 0;
+const S: u8 = 1;
+const T: u8 = 2;
+const BREADCRUMB: u8 = 5;
+
+impl Blossom {
+    fn new(n: usize, edges: Vec<(usize, usize, i64)>) -> Blossom {
+        let max = edges.iter().map(|e| e.2).max().unwrap_or(0).max(0);
+        let endpoint = (0..2 * edges.len())
+            .map(|p| {
+                let (i, j, _) = edges[p / 2];
+                if p % 2 == 0 {
+                    i
+                } else {
+                    j
+                }
+            })
+            .collect();
+        let mut neighbors = vec![vec![]; n];
+        for (k, &(i, j, _)) in edges.iter().enumerate() {
+            neighbors[i].push(2 * k + 1);
+            neighbors[j].push(2 * k);
+        }
+
+        Blossom {
+            n,
+            endpoint,
+            neighbors,
+            mate: vec![NONE; n],
+            label: vec![FREE; 2 * n],
+            label_end: vec![NONE; 2 * n],
+            in_blossom: (0..n).collect(),
+            parent: vec![NONE; 2 * n],
+            children: vec![vec![]; 2 * n],
+            base: (0..n).chain((0..n).map(|_| NONE)).collect(),
+            endpoints: vec![vec![]; 2 * n],
+            best_edge: vec![NONE; 2 * n],
+            best_edges: vec![None; 2 * n],
+            unused: (n..2 * n).collect(),
+            dual: (0..2 * n).map(|b| if b < n { max } else { 0 }).collect(),
+            allowed: vec![false; edges.len()],
+            queue: vec![],
+            edges,
+        }
+    }
+
+    fn slack(&self, k: usize) -> i64 {
+        let (i, j, w) = self.edges[k];
+        self.dual[i] + self.dual[j] - 2 * w
+    }
+
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        if b < self.n {
+            vec![b]
+        } else {
+            self.children[b]
+                .iter()
+                .flat_map(|&c| self.leaves(c))
+                .collect()
+        }
+    }
+
+    /// Returns the child of the blossom `b` at the cyclic index `j`.
+    fn child(&self, b: usize, j: isize) -> usize {
+        let len = self.children[b].len() as isize;
+        self.children[b][((j % len + len) % len) as usize]
+    }
+
+    /// Returns the endpoint between the children of `b` at the cyclic index `j`.
+    fn endpoint_of(&self, b: usize, j: isize) -> usize {
+        let len = self.endpoints[b].len() as isize;
+        self.endpoints[b][((j % len + len) % len) as usize]
+    }
+
+    fn assign_label(&mut self, w: usize, t: u8, p: usize) {
+        let b = self.in_blossom[w];
+        self.label[w] = t;
+        self.label[b] = t;
+        self.label_end[w] = p;
+        self.label_end[b] = p;
+        self.best_edge[w] = NONE;
+        self.best_edge[b] = NONE;
+        if t == S {
+            let leaves = self.leaves(b);
+            self.queue.extend(leaves);
+        } else {
+            let m = self.mate[self.base[b]];
+            let v = self.endpoint[m];
+            self.assign_label(v, S, m ^ 1);
+        }
+    }
+
+    /// Returns the base of the new blossom with the edge between `v` and `w`, or `NONE` if
+    /// they are connected by an augmenting path.
+    fn scan(&mut self, mut v: usize, mut w: usize) -> usize {
+        let mut path = vec![];
+        let mut base = NONE;
+        while v != NONE || w != NONE {
+            let mut b = self.in_blossom[v];
+            if self.label[b] & 4 != 0 {
+                base = self.base[b];
+                break;
+            }
+            path.push(b);
+            self.label[b] = BREADCRUMB;
+            if self.label_end[b] == NONE {
+                v = NONE;
+            } else {
+                v = self.endpoint[self.label_end[b]];
+                b = self.in_blossom[v];
+                v = self.endpoint[self.label_end[b]];
+            }
+            if w != NONE {
+                std::mem::swap(&mut v, &mut w);
+            }
+        }
+        for b in path {
+            self.label[b] = S;
+        }
+        base
+    }
+
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (mut v, mut w, _) = self.edges[k];
+        let bb = self.in_blossom[base];
+        let mut bv = self.in_blossom[v];
+        let mut bw = self.in_blossom[w];
+        let b = self.unused.pop().expect("no unused blossom");
+        self.base[b] = base;
+        self.parent[b] = NONE;
+        self.parent[bb] = b;
+
+        let mut path = vec![];
+        let mut endps = vec![];
+        while bv != bb {
+            self.parent[bv] = b;
+            path.push(bv);
+            endps.push(self.label_end[bv]);
+            v = self.endpoint[self.label_end[bv]];
+            bv = self.in_blossom[v];
+        }
+        path.push(bb);
+        path.reverse();
+        endps.reverse();
+        endps.push(2 * k);
+        while bw != bb {
+            self.parent[bw] = b;
+            path.push(bw);
+            endps.push(self.label_end[bw] ^ 1);
+            w = self.endpoint[self.label_end[bw]];
+            bw = self.in_blossom[w];
+        }
+        self.children[b] = path.clone();
+        self.endpoints[b] = endps;
+
+        self.label[b] = S;
+        self.label_end[b] = self.label_end[bb];
+        self.dual[b] = 0;
+        for v in self.leaves(b) {
+            if self.label[self.in_blossom[v]] == T {
+                self.queue.push(v);
+            }
+            self.in_blossom[v] = b;
+        }
+
+        // The least-slack edges to the other S-blossoms
+        let mut best_to = vec![NONE; 2 * self.n];
+        for bv in path {
+            let lists = match self.best_edges[bv].take() {
+                Some(list) => vec![list],
+                None => self
+                    .leaves(bv)
+                    .into_iter()
+                    .map(|v| self.neighbors[v].iter().map(|p| p / 2).collect())
+                    .collect(),
+            };
+            for list in lists {
+                for k in list {
+                    let (mut i, mut j, _) = self.edges[k];
+                    if self.in_blossom[j] == b {
+                        std::mem::swap(&mut i, &mut j);
+                    }
+                    let bj = self.in_blossom[j];
+                    if bj != b
+                        && self.label[bj] == S
+                        && (best_to[bj] == NONE || self.slack(k) < self.slack(best_to[bj]))
+                    {
+                        best_to[bj] = k;
+                    }
+                }
+            }
+            self.best_edge[bv] = NONE;
+        }
+        let best: Vec<_> = best_to.into_iter().filter(|&k| k != NONE).collect();
+        self.best_edge[b] = NONE;
+        for &k in &best {
+            if self.best_edge[b] == NONE || self.slack(k) < self.slack(self.best_edge[b]) {
+                self.best_edge[b] = k;
+            }
+        }
+        self.best_edges[b] = Some(best);
+    }
+
+    fn expand_blossom(&mut self, b: usize, end_stage: bool) {
+        for s in self.children[b].clone() {
+            self.parent[s] = NONE;
+            if s < self.n {
+                self.in_blossom[s] = s;
+            } else if end_stage && self.dual[s] == 0 {
+                self.expand_blossom(s, end_stage);
+            } else {
+                for v in self.leaves(s) {
+                    self.in_blossom[v] = s;
+                }
+            }
+        }
+
+        if !end_stage && self.label[b] == T {
+            // Relabels the children on the even path from the entry to the base.
+            let entry = self.in_blossom[self.endpoint[self.label_end[b] ^ 1]];
+            let mut j = self.children[b].iter().position(|&c| c == entry).unwrap() as isize;
+            let (step, trick) = if j & 1 == 1 {
+                j -= self.children[b].len() as isize;
+                (1, 0)
+            } else {
+                (-1, 1)
+            };
+            let mut p = self.label_end[b];
+            while j != 0 {
+                let q = self.endpoint[p ^ 1];
+                self.label[q] = FREE;
+                let e = self.endpoint_of(b, j - trick as isize) ^ trick ^ 1;
+                let r = self.endpoint[e];
+                self.label[r] = FREE;
+                self.assign_label(q, T, p);
+                let e = self.endpoint_of(b, j - trick as isize);
+                self.allowed[e / 2] = true;
+                j += step;
+                p = self.endpoint_of(b, j - trick as isize) ^ trick;
+                self.allowed[p / 2] = true;
+                j += step;
+            }
+            let bv = self.child(b, j);
+            let q = self.endpoint[p ^ 1];
+            self.label[q] = T;
+            self.label[bv] = T;
+            self.label_end[q] = p;
+            self.label_end[bv] = p;
+            self.best_edge[bv] = NONE;
+            j += step;
+            while self.child(b, j) != entry {
+                let bv = self.child(b, j);
+                if self.label[bv] == S {
+                    j += step;
+                    continue;
+                }
+                if let Some(v) = self.leaves(bv).into_iter().find(|&v| self.label[v] != FREE) {
+                    self.label[v] = FREE;
+                    let m = self.endpoint[self.mate[self.base[bv]]];
+                    self.label[m] = FREE;
+                    let end = self.label_end[v];
+                    self.assign_label(v, T, end);
+                }
+                j += step;
+            }
+        }
+
+        self.label[b] = FREE;
+        self.label_end[b] = NONE;
+        self.children[b] = vec![];
+        self.endpoints[b] = vec![];
+        self.base[b] = NONE;
+        self.best_edges[b] = None;
+        self.best_edge[b] = NONE;
+        self.unused.push(b);
+    }
+
+    /// Swaps the matched and unmatched edges in `b` so that `v` becomes its base.
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t = v;
+        while self.parent[t] != b {
+            t = self.parent[t];
+        }
+        if t >= self.n {
+            self.augment_blossom(t, v);
+        }
+
+        let i = self.children[b].iter().position(|&c| c == t).unwrap();
+        let mut j = i as isize;
+        let (step, trick) = if i & 1 == 1 {
+            j -= self.children[b].len() as isize;
+            (1, 0)
+        } else {
+            (-1, 1)
+        };
+        while j != 0 {
+            j += step;
+            let t = self.child(b, j);
+            let p = self.endpoint_of(b, j - trick as isize) ^ trick;
+            if t >= self.n {
+                let e = self.endpoint[p];
+                self.augment_blossom(t, e);
+            }
+            j += step;
+            let t = self.child(b, j);
+            if t >= self.n {
+                let e = self.endpoint[p ^ 1];
+                self.augment_blossom(t, e);
+            }
+            self.mate[self.endpoint[p]] = p ^ 1;
+            self.mate[self.endpoint[p ^ 1]] = p;
+        }
+        self.children[b].rotate_left(i);
+        self.endpoints[b].rotate_left(i);
+        self.base[b] = self.base[self.children[b][0]];
+    }
+
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k];
+        for &(mut s, mut p) in &[(v, 2 * k + 1), (w, 2 * k)] {
+            loop {
+                let bs = self.in_blossom[s];
+                if bs >= self.n {
+                    self.augment_blossom(bs, s);
+                }
+                self.mate[s] = p;
+                if self.label_end[bs] == NONE {
+                    break;
+                }
+                let t = self.endpoint[self.label_end[bs]];
+                let bt = self.in_blossom[t];
+                s = self.endpoint[self.label_end[bt]];
+                let j = self.endpoint[self.label_end[bt] ^ 1];
+                if bt >= self.n {
+                    self.augment_blossom(bt, j);
+                }
+                self.mate[j] = self.label_end[bt];
+                p = self.label_end[bt] ^ 1;
+            }
+        }
+    }
+
+    /// Returns `true` if the matching is augmented in the stage.
+    fn stage(&mut self) -> bool {
+        let n = self.n;
+        self.label = vec![FREE; 2 * n];
+        self.best_edge = vec![NONE; 2 * n];
+        for b in n..2 * n {
+            self.best_edges[b] = None;
+        }
+        self.allowed = vec![false; self.edges.len()];
+        self.queue.clear();
+        for v in 0..n {
+            if self.mate[v] == NONE && self.label[self.in_blossom[v]] == FREE {
+                self.assign_label(v, S, NONE);
+            }
+        }
+
+        loop {
+            while let Some(v) = self.queue.pop() {
+                for p in self.neighbors[v].clone() {
+                    let k = p / 2;
+                    let w = self.endpoint[p];
+                    if self.in_blossom[v] == self.in_blossom[w] {
+                        continue;
+                    }
+                    let slack = self.slack(k);
+                    if !self.allowed[k] && slack <= 0 {
+                        self.allowed[k] = true;
+                    }
+                    let bw = self.in_blossom[w];
+                    if self.allowed[k] {
+                        if self.label[bw] == FREE {
+                            self.assign_label(w, T, p ^ 1);
+                        } else if self.label[bw] == S {
+                            let base = self.scan(v, w);
+                            if base != NONE {
+                                self.add_blossom(base, k);
+                            } else {
+                                self.augment_matching(k);
+                                return true;
+                            }
+                        } else if self.label[w] == FREE {
+                            self.label[w] = T;
+                            self.label_end[w] = p ^ 1;
+                        }
+                    } else if self.label[bw] == S {
+                        let b = self.in_blossom[v];
+                        if self.best_edge[b] == NONE || slack < self.slack(self.best_edge[b]) {
+                            self.best_edge[b] = k;
+                        }
+                    } else if self.label[w] == FREE
+                        && (self.best_edge[w] == NONE || slack < self.slack(self.best_edge[w]))
+                    {
+                        self.best_edge[w] = k;
+                    }
+                }
+            }
+
+            // Updates the dual variables by the largest step keeping the slacks.
+            let mut delta: Option<(i64, u8, usize)> = None;
+            let mut update = |d: i64, kind: u8, index: usize| {
+                if delta.is_none_or(|(best, _, _)| d < best) {
+                    delta = Some((d, kind, index));
+                }
+            };
+            for v in 0..n {
+                if self.label[self.in_blossom[v]] == FREE && self.best_edge[v] != NONE {
+                    update(self.slack(self.best_edge[v]), 2, self.best_edge[v]);
+                }
+            }
+            for b in 0..2 * n {
+                if self.parent[b] == NONE && self.label[b] == S && self.best_edge[b] != NONE {
+                    update(self.slack(self.best_edge[b]) / 2, 3, self.best_edge[b]);
+                }
+            }
+            for b in n..2 * n {
+                if self.base[b] != NONE && self.parent[b] == NONE && self.label[b] == T {
+                    update(self.dual[b], 4, b);
+                }
+            }
+            let (delta, kind, index) = delta.unwrap_or_else(|| {
+                let min = self.dual[..n].iter().cloned().min().unwrap_or(0);
+                (min.max(0), 1, NONE)
+            });
+
+            for v in 0..n {
+                match self.label[self.in_blossom[v]] {
+                    S => self.dual[v] -= delta,
+                    T => self.dual[v] += delta,
+                    _ => {}
+                }
+            }
+            for b in n..2 * n {
+                if self.base[b] != NONE && self.parent[b] == NONE {
+                    match self.label[b] {
+                        S => self.dual[b] += delta,
+                        T => self.dual[b] -= delta,
+                        _ => {}
+                    }
+                }
+            }
+
+            match kind {
+                1 => return false,
+                2 => {
+                    self.allowed[index] = true;
+                    let (i, j, _) = self.edges[index];
+                    let i = if self.label[self.in_blossom[i]] == FREE {
+                        j
+                    } else {
+                        i
+                    };
+                    self.queue.push(i);
+                }
+                3 => {
+                    self.allowed[index] = true;
+                    let i = self.edges[index].0;
+                    self.queue.push(i);
+                }
+                _ => self.expand_blossom(index, false),
+            }
+        }
+    }
+
+    fn solve(mut self) -> Vec<usize> {
+        for _ in 0..self.n {
+            if !self.stage() {
+                break;
+            }
+            for b in self.n..2 * self.n {
+                if self.parent[b] == NONE
+                    && self.base[b] != NONE
+                    && self.label[b] == S
+                    && self.dual[b] == 0
+                {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+
+        let endpoint = &self.endpoint;
+        self.mate
+            .iter()
+            .map(|&p| if p == NONE { NONE } else { endpoint[p] })
+            .collect()
+    }
+}
+
+///
+/// A decoder predicting the flip of the observable from the flipped detectors by the
+/// minimum-weight perfect matching.
+///
+#[derive(Debug, Clone)]
+pub struct MatchingDecoder {
+    detectors_len: usize,
+    // The neighbors of each node, with the weight and whether the observable is flipped.
+    // The node `detectors_len` is the boundary.
+    neighbors: Vec<Vec<(usize, f64, bool)>>,
+}
+
+// The scale of the weights rounded to the integers for the matching
+const SCALE: f64 = 1e4;
+
+impl MatchingDecoder {
+    ///
+    /// Creates the decoder for `model`.
+    ///
+    /// The symptoms flipping more than two detectors are ignored. The parallel edges with
+    /// the same flip of the observable are merged, and the likelier one is kept otherwise.
+    ///
+    pub fn new(model: &DetectorErrorModel) -> MatchingDecoder {
+        let boundary = model.detectors_len();
+        let mut edges: HashMap<(usize, usize), (f64, bool)> = HashMap::new();
+        for error in model.errors() {
+            for symptom in &error.symptoms {
+                let key = match symptom.detectors[..] {
+                    [d] => (d, boundary),
+                    [d1, d2] => (d1, d2),
+                    _ => continue,
+                };
+                let p = error.probability;
+                let edge = edges.entry(key).or_insert((0., symptom.observable));
+                if edge.1 == symptom.observable {
+                    edge.0 = edge.0 * (1. - p) + p * (1. - edge.0);
+                } else if p > edge.0 {
+                    *edge = (p, symptom.observable);
+                }
+            }
+        }
+
+        let mut neighbors = vec![vec![]; boundary + 1];
+        let mut keys: Vec<_> = edges.keys().cloned().collect();
+        keys.sort();
+        for (i, j) in keys {
+            let (p, observable) = edges[&(i, j)];
+            let weight = ((1. - p) / p).ln().max(0.);
+            neighbors[i].push((j, weight, observable));
+            neighbors[j].push((i, weight, observable));
+        }
+
+        MatchingDecoder {
+            detectors_len: boundary,
+            neighbors,
+        }
+    }
+
+    ///
+    /// Returns the predicted flip of the observable for the flips of the detectors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `detectors` is not the number of the detectors.
+    ///
+    pub fn decode(&self, detectors: &[bool]) -> bool {
+        assert_eq!(
+            detectors.len(),
+            self.detectors_len,
+            "the number of the detectors does not match"
+        );
+
+        let defects: Vec<_> = (0..detectors.len()).filter(|&d| detectors[d]).collect();
+        let k = defects.len();
+        if k == 0 {
+            return false;
+        }
+
+        // The defects are `0..k`, and their copies of the boundary are `k..2k`.
+        let paths: Vec<_> = defects.iter().map(|&d| self.shortest_paths(d)).collect();
+        let mut edges = vec![];
+        for (i, path) in paths.iter().enumerate() {
+            for j in i + 1..k {
+                if let Some((w, _)) = path[defects[j]] {
+                    edges.push((i, j, (w * SCALE).round() as i64));
+                }
+                edges.push((k + i, k + j, 0));
+            }
+            if let Some((w, _)) = path[self.detectors_len] {
+                edges.push((i, k + i, (w * SCALE).round() as i64));
+            }
+        }
+
+        let mate = match minimum_weight_perfect_matching(2 * k, &edges) {
+            Some(mate) => mate,
+            None => return false,
+        };
+        (0..k).fold(false, |observable, i| {
+            let flip = if mate[i] >= k {
+                paths[i][self.detectors_len].unwrap().1
+            } else if mate[i] > i {
+                paths[i][defects[mate[i]]].unwrap().1
+            } else {
+                false
+            };
+            observable ^ flip
+        })
+    }
+
+    ///
+    /// Returns the fraction of the shots where the decoder fails to predict the flip of the
+    /// observable, sampling `circuit` with `seed`.
+    ///
+    pub fn logical_error_rate(&self, circuit: &NoisyCircuit, shots: usize, seed: u64) -> f64 {
+        let failures = circuit
+            .sample(shots, seed)
+            .iter()
+            .filter(|sample| self.decode(&sample.detectors) != sample.observable)
+            .count();
+        failures as f64 / shots as f64
+    }
+
+    /// Dijkstra's algorithm, where the boundary is not passed through.
+    fn shortest_paths(&self, source: usize) -> Vec<Option<(f64, bool)>> {
+        let mut paths = vec![None; self.neighbors.len()];
+        let mut done = vec![false; self.neighbors.len()];
+        let mut heap = BinaryHeap::new();
+        paths[source] = Some((0., false));
+        heap.push(Node(0., source));
+        while let Some(Node(distance, v)) = heap.pop() {
+            if done[v] {
+                continue;
+            }
+            done[v] = true;
+            if v == self.detectors_len {
+                continue;
+            }
+            let observable = paths[v].unwrap().1;
+            for &(w, weight, flip) in &self.neighbors[v] {
+                let d = distance + weight;
+                if !done[w] && paths[w].is_none_or(|(current, _)| d < current) {
+                    paths[w] = Some((d, observable ^ flip));
+                    heap.push(Node(d, w));
+                }
+            }
+        }
+        paths
+    }
+}
+
+/// A node with its distance, ordered for the min-heap.
+#[derive(PartialEq)]
+struct Node(f64, usize);
+
+impl Eq for Node {}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Node) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Node) -> Ordering {
+        other
+            .0
+            .partial_cmp(&self.0)
+            .unwrap_or(Ordering::Equal)
+            .then(other.1.cmp(&self.1))
+    }
+}
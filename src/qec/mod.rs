@@ -0,0 +1,336 @@
+//!
+//! A module for quantum error-correcting codes.
+//!
+//! The codes are [stabilizer codes](trait.StabilizerCode.html) encoding one logical qubit:
+//!
+//! * [BitFlipCode](repetition/struct.BitFlipCode.html) and
+//!   [PhaseFlipCode](repetition/struct.PhaseFlipCode.html) on 3 qubits, which correct an $X$
+//!   and a $Z$ error respectively
+//! * [ShorCode](shor/struct.ShorCode.html) on 9 qubits and
+//!   [SteaneCode](steane/struct.SteaneCode.html) on 7 qubits, which correct any single-qubit
+//!   error
+//!
+//! The syndrome is measured with ancilla qubits, and bit `j` of the syndrome is set if the
+//! `j`-th stabilizer has the eigenvalue $-1$. The errors are corrected by a
+//! [LookupTable](struct.LookupTable.html) from the syndromes to the single-qubit errors.
+//!
+//! For larger codes, the [surface code](surface/index.html) generates memory experiments as
+//! [noisy Clifford circuits](frame/index.html) sampled by Pauli frames, whose
+//! [detector error models](dem/index.html) are decoded by the
+//! [minimum-weight perfect matching](matching/index.html).
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::qec::StabilizerCode;
+//! use rusq::qec::steane::SteaneCode;
+//!
+//! let code = SteaneCode;
+//! let mut sim = QuantumSimulator::new(8);
+//! let qubits = sim.get_qubits();
+//! let (data, ancillas) = qubits.split_at(7);
+//!
+//! // Encodes |1>.
+//! sim.X(&data[0]);
+//! code.encode(&mut sim, data);
+//!
+//! // A bit flip on the fourth qubit is found and corrected.
+//! sim.X(&data[3]);
+//! assert_ne!(code.correct(&mut sim, data, ancillas), 0);
+//!
+//! code.decode(&mut sim, data);
+//! assert_eq!(sim.measure(&data[0]), MeasuredResult::One);
+//! ```
+//!
+
+pub mod dem;
+pub mod frame;
+pub mod matching;
+pub mod repetition;
+pub mod shor;
+pub mod steane;
+pub mod surface;
+
+use circuit::{Circuit, Condition};
+use gates::double::DoubleGateApplicator;
+use gates::multi::MultiGateApplicator;
+use gates::single::SingleGateApplicator;
+use gates::triple::TripleGateApplicator;
+use observable::{Pauli, PauliString};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use {MeasuredResult, QuantumMachine, Qubit};
+
+///
+/// A trait for the stabilizer codes encoding one logical qubit.
+///
+pub trait StabilizerCode {
+    /// Returns the number of the physical qubits.
+    fn qubits_len(&self) -> usize;
+
+    ///
+    /// Returns the generators of the stabilizer group, whose positions are the ones of the
+    /// physical qubits.
+    ///
+    fn stabilizers(&self) -> Vec<PauliString>;
+
+    ///
+    /// Encodes the state of `qubits[0]` into `qubits`, whose others must be $|0\rangle$.
+    ///
+    fn encode<M>(&self, machine: &mut M, qubits: &[Qubit])
+    where
+        M: SingleGateApplicator + DoubleGateApplicator;
+
+    ///
+    /// Decodes the logical state into `qubits[0]` and restores the others to $|0\rangle$,
+    /// by applying the inverse of the encoder.
+    ///
+    fn decode<M>(&self, machine: &mut M, qubits: &[Qubit])
+    where
+        M: QuantumMachine
+            + SingleGateApplicator
+            + DoubleGateApplicator
+            + TripleGateApplicator
+            + MultiGateApplicator,
+    {
+        let mut encoder = Circuit::new(self.qubits_len(), 0);
+        let physical = encoder.get_qubits();
+        self.encode(&mut encoder, &physical);
+        encoder.inverse().run_on(machine, qubits);
+    }
+
+    ///
+    /// Measures the stabilizers with `ancillas` and returns the syndrome.
+    ///
+    /// The `j`-th stabilizer is measured with `ancillas[j % ancillas.len()]`, so that a single
+    /// ancilla suffices. The ancillas must be $|0\rangle$ and are reset after the measurements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of `qubits` differs from the one of the code, or `ancillas` is
+    /// empty.
+    ///
+    fn measure_syndrome<M>(&self, machine: &mut M, qubits: &[Qubit], ancillas: &[Qubit]) -> usize
+    where
+        M: QuantumMachine + SingleGateApplicator + DoubleGateApplicator,
+    {
+        check_qubits(self.qubits_len(), qubits, ancillas);
+
+        let mut syndrome = 0;
+        for (j, stabilizer) in self.stabilizers().iter().enumerate() {
+            let ancilla = &ancillas[j % ancillas.len()];
+            measure_stabilizer(machine, stabilizer, qubits, ancilla);
+            if machine.measure(ancilla) == MeasuredResult::One {
+                syndrome |= 1 << j;
+                machine.X(ancilla);
+            }
+        }
+        syndrome
+    }
+
+    /// Returns the lookup table from the syndromes to the single-qubit errors.
+    fn lookup_table(&self) -> LookupTable {
+        LookupTable::new(&self.stabilizers(), self.qubits_len())
+    }
+
+    ///
+    /// Measures the syndrome with `ancillas`, applies the correction in the lookup table and
+    /// returns the syndrome.
+    ///
+    /// Nothing is applied for a syndrome not in the table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of `qubits` differs from the one of the code, or `ancillas` is
+    /// empty.
+    ///
+    fn correct<M>(&self, machine: &mut M, qubits: &[Qubit], ancillas: &[Qubit]) -> usize
+    where
+        M: QuantumMachine + SingleGateApplicator + DoubleGateApplicator,
+    {
+        let syndrome = self.measure_syndrome(machine, qubits, ancillas);
+        if let Some(correction) = self.lookup_table().correction(syndrome) {
+            for &(k, pauli) in correction {
+                apply_pauli(machine, pauli, &qubits[k]);
+            }
+        }
+        syndrome
+    }
+
+    ///
+    /// Returns the circuit measuring the syndrome and applying the correction conditioned on
+    /// it, which is the recorded version of [correct](#method.correct).
+    ///
+    /// The qubits of the circuit are the physical qubits followed by `ancillas_len` ancillas,
+    /// and the `j`-th classical bit is bit `j` of the syndrome.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ancillas_len` is zero.
+    ///
+    fn correction_circuit(&self, ancillas_len: usize) -> Circuit {
+        let stabilizers = self.stabilizers();
+        let mut circuit = Circuit::new(self.qubits_len() + ancillas_len, stabilizers.len());
+        let qubits = circuit.get_qubits();
+        let clbits = circuit.get_clbits();
+        let (data, ancillas) = qubits.split_at(self.qubits_len());
+        check_qubits(self.qubits_len(), data, ancillas);
+
+        for (j, stabilizer) in stabilizers.iter().enumerate() {
+            let ancilla = &ancillas[j % ancillas.len()];
+            measure_stabilizer(&mut circuit, stabilizer, data, ancilla);
+            circuit.measure(ancilla, &clbits[j]);
+            circuit.c_if(Condition::Bit(clbits[j], MeasuredResult::One), |c| {
+                c.X(ancilla)
+            });
+        }
+
+        let table = self.lookup_table();
+        let mut syndromes = table.corrections.keys().cloned().collect::<Vec<_>>();
+        syndromes.sort();
+        for syndrome in syndromes.into_iter().filter(|&syndrome| syndrome != 0) {
+            for &(k, pauli) in &table.corrections[&syndrome] {
+                circuit.c_if(Condition::Register(clbits.clone(), syndrome), |c| {
+                    apply_pauli(c, pauli, &data[k])
+                });
+            }
+        }
+
+        circuit
+    }
+}
+
+///
+/// A type for the table from the syndromes to the corrections.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct LookupTable {
+    corrections: HashMap<usize, Vec<(usize, Pauli)>>,
+}
+
+impl LookupTable {
+    ///
+    /// Creates the table of the errors on at most one of `qubits_len` qubits.
+    ///
+    /// The errors are tried in the order of $X$, $Z$ and $Y$ on each qubit, and the first one
+    /// is taken if some errors give the same syndrome.
+    ///
+    pub fn new(stabilizers: &[PauliString], qubits_len: usize) -> LookupTable {
+        let mut corrections = HashMap::new();
+        corrections.insert(0, vec![]);
+        for &pauli in &[Pauli::X, Pauli::Z, Pauli::Y] {
+            for k in 0..qubits_len {
+                let error = vec![(k, pauli)];
+                corrections
+                    .entry(syndrome(stabilizers, &error))
+                    .or_insert(error);
+            }
+        }
+        LookupTable { corrections }
+    }
+
+    /// Returns the correction for `syndrome`, or `None` if it is not in the table.
+    pub fn correction(&self, syndrome: usize) -> Option<&[(usize, Pauli)]> {
+        self.corrections
+            .get(&syndrome)
+            .map(|error| error.as_slice())
+    }
+
+    /// Returns the number of the syndromes in the table.
+    pub fn len(&self) -> usize {
+        self.corrections.len()
+    }
+
+    /// Returns `true` if the table has no syndromes, which never happens.
+    pub fn is_empty(&self) -> bool {
+        self.corrections.is_empty()
+    }
+}
+
+///
+/// Returns the syndrome of the Pauli error `error` given as the pairs of the positions and
+/// the operators.
+///
+/// # Examples
+///
+/// ```
+/// use rusq::observable::{Pauli, PauliString};
+/// use rusq::qec;
+///
+/// let stabilizers = [PauliString::new(1., "ZZI"), PauliString::new(1., "IZZ")];
+/// assert_eq!(qec::syndrome(&stabilizers, &[(1, Pauli::X)]), 0b11);
+/// assert_eq!(qec::syndrome(&stabilizers, &[(2, Pauli::X)]), 0b10);
+/// assert_eq!(qec::syndrome(&stabilizers, &[(2, Pauli::Z)]), 0);
+/// ```
+///
+pub fn syndrome(stabilizers: &[PauliString], error: &[(usize, Pauli)]) -> usize {
+    stabilizers
+        .iter()
+        .enumerate()
+        .filter(|&(_, stabilizer)| {
+            // The operators anticommute iff they differ at an odd number of the positions
+            // where both are not the identity.
+            let differences = error
+                .iter()
+                .filter(|&&(k, e)| {
+                    stabilizer
+                        .paulis
+                        .iter()
+                        .any(|&(l, s)| l == k && s != e && s != Pauli::I && e != Pauli::I)
+                })
+                .count();
+            differences % 2 == 1
+        })
+        .fold(0, |syndrome, (j, _)| syndrome | 1 << j)
+}
+
+fn check_qubits(qubits_len: usize, qubits: &[Qubit], ancillas: &[Qubit]) {
+    assert_eq!(
+        qubits.len(),
+        qubits_len,
+        "the code has {} qubits but {} qubits are given",
+        qubits_len,
+        qubits.len()
+    );
+    assert!(!ancillas.is_empty(), "at least one ancilla is needed");
+}
+
+/// Flips `ancilla` from |0> to |1> iff `stabilizer` has the eigenvalue -1.
+fn measure_stabilizer<M>(
+    machine: &mut M,
+    stabilizer: &PauliString,
+    qubits: &[Qubit],
+    ancilla: &Qubit,
+) where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    machine.H(ancilla);
+    for &(k, pauli) in &stabilizer.paulis {
+        let target = &qubits[k];
+        match pauli {
+            Pauli::I => {}
+            Pauli::X => machine.CNOT(ancilla, target),
+            Pauli::Y => {
+                // Y = S X S^†
+                machine.phase(-PI / 2., target);
+                machine.CNOT(ancilla, target);
+                machine.phase(PI / 2., target);
+            }
+            Pauli::Z => machine.cphase(PI, ancilla, target),
+        }
+    }
+    machine.H(ancilla);
+}
+
+fn apply_pauli<M>(machine: &mut M, pauli: Pauli, qubit: &Qubit)
+where
+    M: SingleGateApplicator,
+{
+    match pauli {
+        Pauli::I => {}
+        Pauli::X => machine.X(qubit),
+        Pauli::Y => machine.Y(qubit),
+        Pauli::Z => machine.Z(qubit),
+    }
+}
@@ -0,0 +1,65 @@
+//!
+//! A module for the 3-qubit repetition codes.
+//!
+
+use gates::double::DoubleGateApplicator;
+use gates::single::SingleGateApplicator;
+use observable::PauliString;
+use qec::StabilizerCode;
+use Qubit;
+
+///
+/// The 3-qubit bit-flip code $|0\rangle \mapsto |000\rangle$, $|1\rangle \mapsto |111\rangle$,
+/// which corrects an $X$ error on one of the qubits.
+///
+/// The stabilizers are $ZZI$ and $IZZ$.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BitFlipCode;
+
+impl StabilizerCode for BitFlipCode {
+    fn qubits_len(&self) -> usize {
+        3
+    }
+
+    fn stabilizers(&self) -> Vec<PauliString> {
+        vec![PauliString::new(1., "ZZI"), PauliString::new(1., "IZZ")]
+    }
+
+    fn encode<M>(&self, machine: &mut M, qubits: &[Qubit])
+    where
+        M: SingleGateApplicator + DoubleGateApplicator,
+    {
+        machine.CNOT(&qubits[0], &qubits[1]);
+        machine.CNOT(&qubits[0], &qubits[2]);
+    }
+}
+
+///
+/// The 3-qubit phase-flip code $|0\rangle \mapsto |{+}{+}{+}\rangle$,
+/// $|1\rangle \mapsto |{-}{-}{-}\rangle$, which corrects a $Z$ error on one of the qubits.
+///
+/// The stabilizers are $XXI$ and $IXX$.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct PhaseFlipCode;
+
+impl StabilizerCode for PhaseFlipCode {
+    fn qubits_len(&self) -> usize {
+        3
+    }
+
+    fn stabilizers(&self) -> Vec<PauliString> {
+        vec![PauliString::new(1., "XXI"), PauliString::new(1., "IXX")]
+    }
+
+    fn encode<M>(&self, machine: &mut M, qubits: &[Qubit])
+    where
+        M: SingleGateApplicator + DoubleGateApplicator,
+    {
+        BitFlipCode.encode(machine, qubits);
+        for qubit in &qubits[..3] {
+            machine.H(qubit);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//!
+//! A module for Shor's 9-qubit code.
+//!
+
+use gates::double::DoubleGateApplicator;
+use gates::single::SingleGateApplicator;
+use observable::PauliString;
+use qec::StabilizerCode;
+use Qubit;
+
+///
+/// Shor's 9-qubit code, the phase-flip code whose qubits are encoded by the bit-flip code,
+/// which corrects any error on one of the qubits.
+///
+/// The qubits `3b`, `3b + 1` and `3b + 2` form the `b`-th block. The first six stabilizers
+/// are $Z_i Z_{i+1}$ in the blocks, and the last two are $X^{\otimes 6}$ on the neighboring
+/// blocks.
+///
+/// The code is degenerate: the $Z$ errors in a block have the same syndrome, and are all
+/// corrected by $Z$ on the first qubit of the block.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ShorCode;
+
+impl StabilizerCode for ShorCode {
+    fn qubits_len(&self) -> usize {
+        9
+    }
+
+    fn stabilizers(&self) -> Vec<PauliString> {
+        [
+            "ZZIIIIIII",
+            "IZZIIIIII",
+            "IIIZZIIII",
+            "IIIIZZIII",
+            "IIIIIIZZI",
+            "IIIIIIIZZ",
+            "XXXXXXIII",
+            "IIIXXXXXX",
+        ]
+        .iter()
+        .map(|paulis| PauliString::new(1., paulis))
+        .collect()
+    }
+
+    fn encode<M>(&self, machine: &mut M, qubits: &[Qubit])
+    where
+        M: SingleGateApplicator + DoubleGateApplicator,
+    {
+        machine.CNOT(&qubits[0], &qubits[3]);
+        machine.CNOT(&qubits[0], &qubits[6]);
+        for block in qubits[..9].chunks(3) {
+            machine.H(&block[0]);
+            machine.CNOT(&block[0], &block[1]);
+            machine.CNOT(&block[0], &block[2]);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//!
+//! A module for Steane's 7-qubit code.
+//!
+
+use gates::double::DoubleGateApplicator;
+use gates::single::SingleGateApplicator;
+use observable::PauliString;
+use qec::StabilizerCode;
+use Qubit;
+
+/// The rows of the parity-check matrix of the Hamming code in the systematic form.
+const CHECKS: [&str; 3] = ["1101100", "1011010", "0111001"];
+
+///
+/// Steane's 7-qubit code, the CSS code from the [7, 4] Hamming code, which corrects any error
+/// on one of the qubits.
+///
+/// The parity checks of the Hamming code are the qubits $\{0, 1, 3, 4\}$, $\{0, 2, 3, 5\}$
+/// and $\{1, 2, 3, 6\}$. The first three stabilizers are $Z$ and the last three are $X$ on
+/// the checks, so that the syndrome of an $X$ error is in the lower three bits and the one
+/// of a $Z$ error is in the upper three bits.
+///
+/// The logical operators are $X_0 X_1 X_2$ and $Z_0 Z_1 Z_2$.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SteaneCode;
+
+impl StabilizerCode for SteaneCode {
+    fn qubits_len(&self) -> usize {
+        7
+    }
+
+    fn stabilizers(&self) -> Vec<PauliString> {
+        let checks = |pauli: &'static str| {
+            CHECKS.iter().map(move |check| {
+                PauliString::new(1., &check.replace('1', pauli).replace('0', "I"))
+            })
+        };
+        checks("Z").chain(checks("X")).collect()
+    }
+
+    fn encode<M>(&self, machine: &mut M, qubits: &[Qubit])
+    where
+        M: SingleGateApplicator + DoubleGateApplicator,
+    {
+        // a|0...0> + b X_0 X_1 X_2 |0...0>
+        machine.CNOT(&qubits[0], &qubits[1]);
+        machine.CNOT(&qubits[0], &qubits[2]);
+
+        // The sum over the X stabilizers, where the qubits 4, 5 and 6 appear only in one of
+        // the checks.
+        for (i, check) in CHECKS.iter().enumerate() {
+            let pivot = &qubits[4 + i];
+            machine.H(pivot);
+            for (k, c) in check.chars().enumerate() {
+                if c == '1' && k != 4 + i {
+                    machine.CNOT(pivot, &qubits[k]);
+                }
+            }
+        }
+    }
+}
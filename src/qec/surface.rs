@@ -0,0 +1,358 @@
+//!
+//! A module for memory experiments of the rotated surface code.
+//!
+//! The data qubits of the distance-$d$ code are on the $d \times d$ grid, and the $d^2 - 1$
+//! stabilizers are on the plaquettes between them, each of which is measured with an ancilla
+//! qubit. The $X$ stabilizers of weight 2 are on the top and the bottom boundaries, and the
+//! $Z$ ones on the left and the right boundaries, so that the logical $Z$ is the top row
+//! and the logical $X$ is the left column.
+//!
+//! A [memory experiment](struct.SurfaceCode.html#method.memory_experiment) prepares a
+//! logical eigenstate, measures the stabilizers for some rounds under a
+//! [NoiseModel](struct.NoiseModel.html), and measures the data qubits. The result is a
+//! [NoisyCircuit](../frame/struct.NoisyCircuit.html) with the detectors comparing the
+//! consecutive stabilizer outcomes, which can be decoded by a
+//! [MatchingDecoder](../matching/struct.MatchingDecoder.html).
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::qec::dem::DetectorErrorModel;
+//! use rusq::qec::matching::MatchingDecoder;
+//! use rusq::qec::surface::{Basis, NoiseModel, SurfaceCode};
+//!
+//! let circuit = SurfaceCode::new(3).memory_experiment(3, Basis::Z, &NoiseModel::circuit_level(0.001));
+//! let decoder = MatchingDecoder::new(&DetectorErrorModel::from_circuit(&circuit));
+//! assert!(decoder.logical_error_rate(&circuit, 1000, 0) < 0.01);
+//! ```
+//!
+
+use observable::{Pauli, PauliString};
+use qec::frame::{Instruction, NoisyCircuit, PauliChannel};
+
+///
+/// A type for the basis of the stabilizers and the logical states.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Basis {
+    X,
+    Z,
+}
+
+///
+/// A type for the Pauli noise in a memory experiment.
+///
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct NoiseModel {
+    /// The channel on each data qubit at the start of each round
+    pub data: PauliChannel,
+    /// The probability of the two-qubit depolarizing channel after each CNOT
+    pub cnot: f64,
+    /// The channel after each Hadamard gate
+    pub hadamard: PauliChannel,
+    /// The probability of the bit flip after each reset
+    pub reset: f64,
+    /// The probability of flipping each measurement result
+    pub measurement: f64,
+}
+
+impl NoiseModel {
+    /// The model without noise.
+    pub fn noiseless() -> NoiseModel {
+        NoiseModel::default()
+    }
+
+    /// The depolarizing noise of the probability `p` only on the data qubits.
+    pub fn code_capacity(p: f64) -> NoiseModel {
+        NoiseModel {
+            data: PauliChannel::depolarizing(p),
+            ..NoiseModel::default()
+        }
+    }
+
+    ///
+    /// The depolarizing noise of the probability `p` on the data qubits, and the flips of
+    /// the measurement results of the probability `p`.
+    ///
+    pub fn phenomenological(p: f64) -> NoiseModel {
+        NoiseModel {
+            data: PauliChannel::depolarizing(p),
+            measurement: p,
+            ..NoiseModel::default()
+        }
+    }
+
+    /// The noise of the probability `p` on every location of the circuit.
+    pub fn circuit_level(p: f64) -> NoiseModel {
+        NoiseModel {
+            data: PauliChannel::depolarizing(p),
+            cnot: p,
+            hadamard: PauliChannel::depolarizing(p),
+            reset: p,
+            measurement: p,
+        }
+    }
+}
+
+///
+/// A type for a stabilizer of the surface code.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Stabilizer {
+    /// The basis of the stabilizer
+    pub basis: Basis,
+    /// The index of the ancilla qubit
+    pub ancilla: usize,
+    ///
+    /// The data qubits on the north-west, north-east, south-west and south-east corners,
+    /// which are `None` on the boundaries
+    ///
+    pub data: [Option<usize>; 4],
+    /// The position `(i, j)` of the plaquette, which is between the rows `i - 1` and `i`
+    pub position: (usize, usize),
+}
+
+impl Stabilizer {
+    /// Returns the data qubits in the order of the CNOTs measuring the stabilizer.
+    pub fn schedule(&self) -> [Option<usize>; 4] {
+        let [nw, ne, sw, se] = self.data;
+        match self.basis {
+            // The hook errors are perpendicular to the logical operators.
+            Basis::X => [nw, ne, sw, se],
+            Basis::Z => [nw, sw, ne, se],
+        }
+    }
+}
+
+///
+/// A type for the rotated surface code.
+///
+/// The data qubit in the row `r` and the column `c` is `r * d + c`, and the ancillas follow
+/// them in the order of the stabilizers.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct SurfaceCode {
+    distance: usize,
+    stabilizers: Vec<Stabilizer>,
+}
+
+impl SurfaceCode {
+    ///
+    /// Creates the code of `distance`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `distance` is less than 2.
+    ///
+    pub fn new(distance: usize) -> SurfaceCode {
+        assert!(distance >= 2, "the distance must be at least 2");
+
+        let d = distance;
+        let mut stabilizers = vec![];
+        for i in 0..=d {
+            for j in 0..=d {
+                let basis = if (i + j) % 2 == 0 { Basis::X } else { Basis::Z };
+                let vertical = i == 0 || i == d;
+                let horizontal = j == 0 || j == d;
+                let kept = match (vertical, horizontal) {
+                    (false, false) => true,
+                    (true, false) => basis == Basis::X,
+                    (false, true) => basis == Basis::Z,
+                    (true, true) => false,
+                };
+                if !kept {
+                    continue;
+                }
+
+                let qubit = |r: usize, c: usize| {
+                    if 1 <= r && r <= d && 1 <= c && c <= d {
+                        Some((r - 1) * d + c - 1)
+                    } else {
+                        None
+                    }
+                };
+                stabilizers.push(Stabilizer {
+                    basis,
+                    ancilla: d * d + stabilizers.len(),
+                    data: [
+                        qubit(i, j),
+                        qubit(i, j + 1),
+                        qubit(i + 1, j),
+                        qubit(i + 1, j + 1),
+                    ],
+                    position: (i, j),
+                });
+            }
+        }
+
+        SurfaceCode {
+            distance,
+            stabilizers,
+        }
+    }
+
+    /// Returns the distance.
+    pub fn distance(&self) -> usize {
+        self.distance
+    }
+
+    /// Returns the number of the data qubits.
+    pub fn data_len(&self) -> usize {
+        self.distance * self.distance
+    }
+
+    /// Returns the number of the data and the ancilla qubits.
+    pub fn qubits_len(&self) -> usize {
+        self.data_len() + self.stabilizers.len()
+    }
+
+    /// Returns the stabilizers.
+    pub fn stabilizers(&self) -> &[Stabilizer] {
+        &self.stabilizers
+    }
+
+    /// Returns the data qubits of the logical operator in `basis`.
+    pub fn logical(&self, basis: Basis) -> Vec<usize> {
+        let d = self.distance;
+        match basis {
+            Basis::X => (0..d).map(|r| r * d).collect(),
+            Basis::Z => (0..d).collect(),
+        }
+    }
+
+    /// Returns the stabilizers as Pauli strings on the data qubits.
+    pub fn pauli_strings(&self) -> Vec<PauliString> {
+        self.stabilizers
+            .iter()
+            .map(|s| {
+                let pauli = match s.basis {
+                    Basis::X => Pauli::X,
+                    Basis::Z => Pauli::Z,
+                };
+                let mut qubits: Vec<_> = s.data.iter().filter_map(|&q| q).collect();
+                qubits.sort();
+                PauliString {
+                    coefficient: 1.,
+                    paulis: qubits.into_iter().map(|q| (q, pauli)).collect(),
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// Returns the memory experiment of the logical state $|0\rangle$ or $|+\rangle$ for
+    /// `basis` with `rounds` rounds of the stabilizer measurements under `noise`.
+    ///
+    /// In the first round, the detectors are the stabilizers of `basis`, which are
+    /// deterministic. In the later rounds, the detectors compare the outcomes with the
+    /// previous ones, and the final detectors compare the stabilizers of `basis` recovered
+    /// from the data qubits with the last outcomes. The observable is the logical operator
+    /// of `basis`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rounds` is 0.
+    ///
+    pub fn memory_experiment(
+        &self,
+        rounds: usize,
+        basis: Basis,
+        noise: &NoiseModel,
+    ) -> NoisyCircuit {
+        assert!(rounds > 0, "at least one round is needed");
+
+        let mut circuit = NoisyCircuit::new(self.qubits_len());
+        let reset = |circuit: &mut NoisyCircuit, q: usize| {
+            circuit.push(Instruction::Reset(q));
+            if noise.reset > 0. {
+                circuit.push(Instruction::Pauli(q, PauliChannel::bit_flip(noise.reset)));
+            }
+        };
+        let hadamard = |circuit: &mut NoisyCircuit, q: usize| {
+            circuit.push(Instruction::H(q));
+            if !noise.hadamard.is_noiseless() {
+                circuit.push(Instruction::Pauli(q, noise.hadamard));
+            }
+        };
+
+        for q in 0..self.data_len() {
+            reset(&mut circuit, q);
+            if basis == Basis::X {
+                hadamard(&mut circuit, q);
+            }
+        }
+
+        let mut previous: Vec<Option<usize>> = vec![None; self.stabilizers.len()];
+        for _ in 0..rounds {
+            if !noise.data.is_noiseless() {
+                for q in 0..self.data_len() {
+                    circuit.push(Instruction::Pauli(q, noise.data));
+                }
+            }
+            for s in &self.stabilizers {
+                reset(&mut circuit, s.ancilla);
+                if s.basis == Basis::X {
+                    hadamard(&mut circuit, s.ancilla);
+                }
+            }
+            for step in 0..4 {
+                for s in &self.stabilizers {
+                    if let Some(q) = s.schedule()[step] {
+                        let (control, target) = match s.basis {
+                            Basis::X => (s.ancilla, q),
+                            Basis::Z => (q, s.ancilla),
+                        };
+                        circuit.push(Instruction::CNOT(control, target));
+                        if noise.cnot > 0. {
+                            circuit.push(Instruction::Depolarize2(control, target, noise.cnot));
+                        }
+                    }
+                }
+            }
+            for s in &self.stabilizers {
+                if s.basis == Basis::X {
+                    hadamard(&mut circuit, s.ancilla);
+                }
+            }
+            for (i, s) in self.stabilizers.iter().enumerate() {
+                let m = circuit
+                    .push(Instruction::Measure(s.ancilla, noise.measurement))
+                    .unwrap();
+                match previous[i] {
+                    Some(p) => {
+                        circuit.add_detector(vec![m, p]);
+                    }
+                    None if s.basis == basis => {
+                        circuit.add_detector(vec![m]);
+                    }
+                    None => {}
+                }
+                previous[i] = Some(m);
+            }
+        }
+
+        if basis == Basis::X {
+            for q in 0..self.data_len() {
+                hadamard(&mut circuit, q);
+            }
+        }
+        let data: Vec<_> = (0..self.data_len())
+            .map(|q| {
+                circuit
+                    .push(Instruction::Measure(q, noise.measurement))
+                    .unwrap()
+            })
+            .collect();
+        for (i, s) in self.stabilizers.iter().enumerate() {
+            if s.basis == basis {
+                let mut measurements: Vec<_> =
+                    s.data.iter().filter_map(|&q| q).map(|q| data[q]).collect();
+                measurements.extend(previous[i]);
+                circuit.add_detector(measurements);
+            }
+        }
+        circuit.set_observable(self.logical(basis).into_iter().map(|q| data[q]).collect());
+
+        circuit
+    }
+}
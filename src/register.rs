@@ -0,0 +1,180 @@
+//!
+//! A module for registers of qubits.
+//!
+
+use gates::single::SingleGateApplicator;
+use std::ops::Deref;
+use std::slice::{self, SliceIndex};
+use {MeasuredResult, QuantumMachine, Qubit};
+
+///
+/// A type for the order of bits when a register is interpreted as an integer.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Endianness {
+    /// The first qubit represents the least significant bit.
+    Little,
+    /// The first qubit represents the most significant bit.
+    Big,
+}
+
+///
+/// A type for an ordered set of qubits.
+///
+/// A register dereferences to a slice of qubits, so that it can be passed to
+/// the functions taking `&[Qubit]`.
+///
+/// # Examples
+///
+/// ```
+/// use rusq::prelude::*;
+///
+/// let mut sim = QuantumSimulator::new(4);
+/// let register = sim.get_register();
+/// let upper = register.slice(2..);
+///
+/// upper.set_integer(&mut sim, 0b10, Endianness::Little);
+///
+/// assert_eq!(upper.len(), 2);
+/// assert_eq!(register.measure_integer(&mut sim, Endianness::Little), 0b1000);
+/// assert_eq!(register.measure_integer(&mut sim, Endianness::Big), 0b0001);
+/// ```
+///
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct QubitRegister {
+    qubits: Vec<Qubit>,
+}
+
+impl QubitRegister {
+    /// Creates a new register consisting of the given qubits.
+    pub fn new(qubits: Vec<Qubit>) -> QubitRegister {
+        QubitRegister { qubits }
+    }
+
+    /// Returns the number of the qubits in the register.
+    pub fn len(&self) -> usize {
+        self.qubits.len()
+    }
+
+    /// Returns `true` if the register has no qubit.
+    pub fn is_empty(&self) -> bool {
+        self.qubits.is_empty()
+    }
+
+    /// Returns an iterator over the qubits.
+    pub fn iter(&self) -> slice::Iter<'_, Qubit> {
+        self.qubits.iter()
+    }
+
+    /// Returns the qubits as a slice.
+    pub fn as_slice(&self) -> &[Qubit] {
+        &self.qubits
+    }
+
+    /// Returns a new register consisting of the qubits in `range`.
+    pub fn slice<R>(&self, range: R) -> QubitRegister
+    where
+        R: SliceIndex<[Qubit], Output = [Qubit]>,
+    {
+        QubitRegister::new(self.qubits[range].to_vec())
+    }
+
+    ///
+    /// Returns the qubit representing the `k`-th bit, i.e. $2^k$,
+    /// when the register is interpreted as an integer.
+    ///
+    pub fn bit(&self, k: usize, endianness: Endianness) -> &Qubit {
+        match endianness {
+            Endianness::Little => &self.qubits[k],
+            Endianness::Big => &self.qubits[self.qubits.len() - 1 - k],
+        }
+    }
+
+    ///
+    /// Interprets the measured results of the qubits in the register as an integer.
+    ///
+    /// `results[i]` is expected to be the result of the `i`-th qubit of the register.
+    ///
+    pub fn to_integer(&self, results: &[MeasuredResult], endianness: Endianness) -> usize {
+        assert_eq!(results.len(), self.len());
+        (0..self.len()).fold(0, |acc, k| {
+            let i = match endianness {
+                Endianness::Little => k,
+                Endianness::Big => self.len() - 1 - k,
+            };
+            if results[i] == MeasuredResult::One {
+                acc | (1 << k)
+            } else {
+                acc
+            }
+        })
+    }
+
+    /// Measures all the qubits in the register and interprets the results as an integer.
+    pub fn measure_integer<M: QuantumMachine>(
+        &self,
+        machine: &mut M,
+        endianness: Endianness,
+    ) -> usize {
+        let results = self
+            .qubits
+            .iter()
+            .map(|q| machine.measure(q))
+            .collect::<Vec<_>>();
+        self.to_integer(&results, endianness)
+    }
+
+    ///
+    /// Sets the qubits in the register to the computational basis state representing `value`.
+    ///
+    /// The bits of `value` beyond the length of the register are ignored.
+    ///
+    pub fn set_integer<M>(&self, machine: &mut M, value: usize, endianness: Endianness)
+    where
+        M: QuantumMachine + SingleGateApplicator,
+    {
+        for k in 0..self.len() {
+            let qubit = self.bit(k, endianness);
+            let expected = if (value >> k) & 1 == 1 {
+                MeasuredResult::One
+            } else {
+                MeasuredResult::Zero
+            };
+            if machine.measure(qubit) != expected {
+                machine.X(qubit);
+            }
+        }
+    }
+}
+
+impl From<Vec<Qubit>> for QubitRegister {
+    fn from(qubits: Vec<Qubit>) -> QubitRegister {
+        QubitRegister::new(qubits)
+    }
+}
+
+impl Deref for QubitRegister {
+    type Target = [Qubit];
+
+    fn deref(&self) -> &[Qubit] {
+        &self.qubits
+    }
+}
+
+impl<'a> IntoIterator for &'a QubitRegister {
+    type Item = &'a Qubit;
+    type IntoIter = slice::Iter<'a, Qubit>;
+
+    fn into_iter(self) -> slice::Iter<'a, Qubit> {
+        self.qubits.iter()
+    }
+}
+
+impl IntoIterator for QubitRegister {
+    type Item = Qubit;
+    type IntoIter = ::std::vec::IntoIter<Qubit>;
+
+    fn into_iter(self) -> ::std::vec::IntoIter<Qubit> {
+        self.qubits.into_iter()
+    }
+}
@@ -0,0 +1,185 @@
+//!
+//! A module for checkpointing the state of a simulator.
+//!
+//! A [Snapshot](struct.Snapshot.html) is an in-memory copy of the state of
+//! a [QuantumSimulator](../simulator/struct.QuantumSimulator.html), which can be written to and
+//! read from a file in the following versioned binary format.
+//! All the integers and floating point numbers are little-endian.
+//!
+//! | Offset | Size | Content                                                  |
+//! |--------|------|----------------------------------------------------------|
+//! | 0      | 4    | Magic bytes `RUSQ`                                       |
+//! | 4      | 2    | Format version (`u16`, currently `1`)                    |
+//! | 6      | 1    | Precision, i.e. bytes per real number (`4` or `8`)       |
+//! | 7      | 1    | Reserved (`0`)                                           |
+//! | 8      | 8    | Number of qubits $n$ (`u64`, at most `32`)               |
+//! | 16     | -    | $2^n$ amplitudes, each as a real and an imaginary part   |
+//!
+//! The amplitude of the basis state $|i\rangle$ is stored at the $i$-th position,
+//! where the $k$-th bit of $i$ corresponds to the qubit with index $k$.
+//! Rusq always writes the amplitudes in double precision, but single precision files are also
+//! accepted on reading.
+//!
+//! In addition, a snapshot can be exported to the NumPy `.npy` format
+//! as a one-dimensional array of `complex128`.
+//!
+
+use num::complex::Complex;
+use std::cmp;
+use std::io::{self, Read, Write};
+use std::mem;
+
+const MAGIC: &[u8; 4] = b"RUSQ";
+const VERSION: u16 = 1;
+const NPY_MAGIC: &[u8; 6] = b"\x93NUMPY";
+/// The maximum number of qubits accepted on reading
+const MAX_QUBITS: u64 = 32;
+/// The number of amplitudes allocated before reading, so that a corrupt header cannot
+/// allocate an arbitrary amount of memory
+const INITIAL_CAPACITY: usize = 1 << 16;
+
+///
+/// An in-memory copy of the state of a simulator.
+///
+/// A snapshot is obtained by [`QuantumSimulator::snapshot`](../simulator/struct.QuantumSimulator.html#method.snapshot)
+/// and is given back by [`QuantumSimulator::restore`](../simulator/struct.QuantumSimulator.html#method.restore).
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub(crate) dimension: usize,
+    pub(crate) states: Vec<Complex<f64>>,
+}
+
+impl Snapshot {
+    /// Returns the number of qubits of the snapshot.
+    pub fn qubits_len(&self) -> usize {
+        self.dimension
+    }
+
+    ///
+    /// Returns the amplitudes of the snapshot.
+    ///
+    /// Note that the amplitudes are not physically observable. This is meant for the analysis of
+    /// the state, not for the quantum computation itself.
+    ///
+    pub fn amplitudes(&self) -> &[Complex<f64>] {
+        &self.states
+    }
+
+    /// Writes the snapshot to `writer` in the Rusq binary format.
+    pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()> {
+        write_states(writer, self.dimension, &self.states)
+    }
+
+    /// Reads a snapshot in the Rusq binary format from `reader`.
+    pub fn read_from<R: Read>(mut reader: R) -> io::Result<Snapshot> {
+        let mut header = [0u8; 16];
+        reader.read_exact(&mut header)?;
+
+        if &header[0..4] != MAGIC {
+            return Err(invalid_data("not a Rusq checkpoint"));
+        }
+
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != VERSION {
+            return Err(invalid_data(&format!(
+                "unsupported checkpoint version {}",
+                version
+            )));
+        }
+
+        let precision = header[6];
+        if header[7] != 0 {
+            return Err(invalid_data("non-zero reserved byte"));
+        }
+
+        let mut dimension_bytes = [0u8; 8];
+        dimension_bytes.copy_from_slice(&header[8..16]);
+        let dimension = u64::from_le_bytes(dimension_bytes);
+        if dimension > MAX_QUBITS || dimension >= (mem::size_of::<usize>() * 8) as u64 {
+            return Err(invalid_data(&format!("too many qubits: {}", dimension)));
+        }
+        let dimension = dimension as usize;
+
+        let mut states = Vec::with_capacity(cmp::min(1 << dimension, INITIAL_CAPACITY));
+        match precision {
+            4 => {
+                let mut buf = [0u8; 8];
+                for _ in 0..(1usize << dimension) {
+                    reader.read_exact(&mut buf)?;
+                    let mut re = [0u8; 4];
+                    let mut im = [0u8; 4];
+                    re.copy_from_slice(&buf[0..4]);
+                    im.copy_from_slice(&buf[4..8]);
+                    states.push(Complex::new(
+                        f64::from(f32::from_le_bytes(re)),
+                        f64::from(f32::from_le_bytes(im)),
+                    ));
+                }
+            }
+            8 => {
+                let mut buf = [0u8; 16];
+                for _ in 0..(1usize << dimension) {
+                    reader.read_exact(&mut buf)?;
+                    let mut re = [0u8; 8];
+                    let mut im = [0u8; 8];
+                    re.copy_from_slice(&buf[0..8]);
+                    im.copy_from_slice(&buf[8..16]);
+                    states.push(Complex::new(f64::from_le_bytes(re), f64::from_le_bytes(im)));
+                }
+            }
+            p => {
+                return Err(invalid_data(&format!("unsupported precision {}", p)));
+            }
+        }
+
+        Ok(Snapshot { dimension, states })
+    }
+
+    /// Writes the amplitudes of the snapshot to `writer` in the NumPy `.npy` format.
+    pub fn write_npy<W: Write>(&self, writer: W) -> io::Result<()> {
+        write_npy(writer, &self.states)
+    }
+}
+
+pub(crate) fn write_states<W: Write>(
+    mut writer: W,
+    dimension: usize,
+    states: &[Complex<f64>],
+) -> io::Result<()> {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&[8, 0])?;
+    writer.write_all(&(dimension as u64).to_le_bytes())?;
+    for s in states {
+        writer.write_all(&s.re.to_le_bytes())?;
+        writer.write_all(&s.im.to_le_bytes())?;
+    }
+    writer.flush()
+}
+
+pub(crate) fn write_npy<W: Write>(mut writer: W, states: &[Complex<f64>]) -> io::Result<()> {
+    let mut header = format!(
+        "{{'descr': '<c16', 'fortran_order': False, 'shape': ({},), }}",
+        states.len()
+    );
+    // The total header length must be a multiple of 64 and end with a newline.
+    let unpadded = NPY_MAGIC.len() + 2 + 2 + header.len() + 1;
+    let padding = (64 - unpadded % 64) % 64;
+    header.extend((0..padding).map(|_| ' '));
+    header.push('\n');
+
+    writer.write_all(NPY_MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    for s in states {
+        writer.write_all(&s.re.to_le_bytes())?;
+        writer.write_all(&s.im.to_le_bytes())?;
+    }
+    writer.flush()
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_owned())
+}
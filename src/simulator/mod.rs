@@ -3,5 +3,9 @@
 //!
 
 pub mod simulator;
+pub mod checkpoint;
+pub mod unitary;
 
 pub use self::simulator::QuantumSimulator;
+pub use self::checkpoint::Snapshot;
+pub use self::unitary::UnitarySimulator;
@@ -3,12 +3,18 @@
 ///
 
 use num::complex::Complex;
-use rand;
-use {MeasuredResult, QuantumMachine, Qubit};
+use rand::{self, Rng, SeedableRng, XorShiftRng};
+use {new_machine_id, MeasuredResult, QuantumMachine, Qubit};
+use error::RusqError;
 use gates::single::SingleGateApplicator;
 use gates::double::DoubleGateApplicator;
 use gates::triple::TripleGateApplicator;
+use gates::multi::MultiGateApplicator;
 use ndarray::prelude::*;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+use super::checkpoint::{self, Snapshot};
 
 ///
 /// A simulator for a quantum computer
@@ -45,8 +51,11 @@ use ndarray::prelude::*;
 /// to the physical state.
 ///
 pub struct QuantumSimulator {
+    id: usize,
     dimension: usize,
     states: Vec<Complex<f64>>,
+    unitarity_tolerance: Option<f64>,
+    rng: XorShiftRng,
 }
 
 impl QuantumSimulator {
@@ -69,26 +78,446 @@ impl QuantumSimulator {
         states[0] = Complex::new(1., 0.);
 
         QuantumSimulator {
+            id: new_machine_id(),
             dimension: n,
             states: states,
+            unitarity_tolerance: None,
+            rng: rand::random(),
         }
     }
 
+    ///
+    /// Creates a new instance with a given number of qubits, whose measurements are
+    /// reproducible by the given seed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusq::prelude::*;
+    ///
+    /// let results = (0..2)
+    ///     .map(|_| {
+    ///         let mut sim = QuantumSimulator::with_seed(8, 42);
+    ///         let qubits = sim.get_qubits();
+    ///         qubits
+    ///             .iter()
+    ///             .map(|q| {
+    ///                 sim.H(q);
+    ///                 sim.measure(q)
+    ///             })
+    ///             .collect::<Vec<_>>()
+    ///     })
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(results[0], results[1]);
+    /// ```
+    ///
+    pub fn with_seed(n: usize, seed: u64) -> QuantumSimulator {
+        let mut sim = QuantumSimulator::new(n);
+        sim.set_seed(seed);
+        sim
+    }
+
+    ///
+    /// Resets the random number generator for the measurements with the given seed.
+    ///
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = seeded_rng(seed);
+    }
+
+    ///
+    /// Resets all the qubits to $|0\rangle$.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusq::prelude::*;
+    ///
+    /// let mut sim = QuantumSimulator::new(2);
+    /// let qubits = sim.get_qubits();
+    /// sim.H(&qubits[0]);
+    /// sim.X(&qubits[1]);
+    /// sim.reset();
+    ///
+    /// assert_eq!(sim.get_register().measure_integer(&mut sim, Endianness::Little), 0);
+    /// ```
+    ///
+    pub fn reset(&mut self) {
+        for state in &mut self.states {
+            *state = Complex::new(0., 0.);
+        }
+        self.states[0] = Complex::new(1., 0.);
+    }
+
+    ///
+    /// Takes an in-memory snapshot of the current state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusq::prelude::*;
+    ///
+    /// let mut sim = QuantumSimulator::new(1);
+    /// let qubit = &sim.get_qubits()[0];
+    /// sim.X(qubit);
+    ///
+    /// let snapshot = sim.snapshot();
+    /// sim.X(qubit);
+    /// sim.restore(&snapshot);
+    ///
+    /// assert_eq!(sim.measure(qubit), MeasuredResult::One);
+    /// ```
+    ///
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            dimension: self.dimension,
+            states: self.states.clone(),
+        }
+    }
+
+    ///
+    /// Restores the state from the given snapshot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of qubits of the snapshot differs from the one of the simulator.
+    ///
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        assert_eq!(
+            self.dimension, snapshot.dimension,
+            "the snapshot has {} qubits while the simulator has {}",
+            snapshot.dimension, self.dimension
+        );
+        self.states.copy_from_slice(&snapshot.states);
+    }
+
+    ///
+    /// Creates a new instance from the given snapshot.
+    ///
+    pub fn from_snapshot(snapshot: Snapshot) -> QuantumSimulator {
+        QuantumSimulator {
+            id: new_machine_id(),
+            dimension: snapshot.dimension,
+            states: snapshot.states,
+            unitarity_tolerance: None,
+            rng: rand::random(),
+        }
+    }
+
+    ///
+    /// Saves the current state to the file at `path`.
+    ///
+    /// See [checkpoint module](../checkpoint/index.html) for the file format.
+    ///
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = File::create(path)?;
+        checkpoint::write_states(BufWriter::new(file), self.dimension, &self.states)
+    }
+
+    ///
+    /// Loads a simulator from the file at `path` saved by [`save` method](#method.save).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusq::prelude::*;
+    ///
+    /// let path = std::env::temp_dir().join("rusq_doc_load.rusq");
+    ///
+    /// let mut sim = QuantumSimulator::new(2);
+    /// let qubits = sim.get_qubits();
+    /// sim.X(&qubits[1]);
+    /// sim.save(&path).unwrap();
+    ///
+    /// let mut loaded = QuantumSimulator::load(&path).unwrap();
+    /// let qubits = loaded.get_qubits();
+    /// assert_eq!(loaded.measure(&qubits[0]), MeasuredResult::Zero);
+    /// assert_eq!(loaded.measure(&qubits[1]), MeasuredResult::One);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    ///
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<QuantumSimulator> {
+        let file = File::open(path)?;
+        Snapshot::read_from(BufReader::new(file)).map(QuantumSimulator::from_snapshot)
+    }
+
+    ///
+    /// Exports the amplitudes of the current state to the file at `path`
+    /// in the NumPy `.npy` format.
+    ///
+    /// The `k`-th bit of the index of the array corresponds to the qubit with index `k`.
+    ///
+    pub fn export_npy<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = File::create(path)?;
+        checkpoint::write_npy(BufWriter::new(file), &self.states)
+    }
+
+    ///
+    /// Enables or disables the check of the unitarity of the matrices given to the simulator.
+    ///
+    /// If `tolerance` is `Some`, every matrix applied to the qubits is checked to satisfy
+    /// $U^\dagger U = 1$ elementwise within `tolerance`. The check is disabled by default
+    /// since it costs as much as the matrix multiplication.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate ndarray;
+    /// extern crate num;
+    /// extern crate rusq;
+    ///
+    /// use ndarray::prelude::*;
+    /// use num::complex::Complex;
+    /// use rusq::prelude::*;
+    /// use rusq::RusqError;
+    ///
+    /// # fn main() {
+    /// let mut sim = QuantumSimulator::new(1);
+    /// let qubit = &sim.get_qubits()[0];
+    /// sim.set_unitarity_check(Some(1e-10));
+    ///
+    /// let matrix = Array2::from_elem((2, 2), Complex::new(1., 0.));
+    /// assert_eq!(sim.try_apply_single(&matrix, qubit), Err(RusqError::NonUnitaryMatrix));
+    /// # }
+    /// ```
+    ///
+    pub fn set_unitarity_check(&mut self, tolerance: Option<f64>) {
+        self.unitarity_tolerance = tolerance;
+    }
+
+    ///
+    /// Applies `matrix` to `qubit`, or returns an error if the operation is invalid.
+    ///
+    pub fn try_apply_single(
+        &mut self,
+        matrix: &Array2<Complex<f64>>,
+        qubit: &Qubit,
+    ) -> Result<(), RusqError> {
+        self.try_apply(&[qubit], matrix)
+    }
+
+    ///
+    /// Applies `matrix` to `qubit1` and `qubit2`, or returns an error if the operation is invalid.
+    ///
+    pub fn try_apply_double(
+        &mut self,
+        matrix: &Array2<Complex<f64>>,
+        qubit1: &Qubit,
+        qubit2: &Qubit,
+    ) -> Result<(), RusqError> {
+        self.try_apply(&[qubit1, qubit2], matrix)
+    }
+
+    ///
+    /// Applies `matrix` to `qubit1`, `qubit2` and `qubit3`,
+    /// or returns an error if the operation is invalid.
+    ///
+    pub fn try_apply_triple(
+        &mut self,
+        matrix: &Array2<Complex<f64>>,
+        qubit1: &Qubit,
+        qubit2: &Qubit,
+        qubit3: &Qubit,
+    ) -> Result<(), RusqError> {
+        self.try_apply(&[qubit1, qubit2, qubit3], matrix)
+    }
+
+    ///
+    /// Applies `matrix` to `qubits`, or returns an error if the operation is invalid.
+    ///
+    pub fn try_apply_multi(
+        &mut self,
+        matrix: &Array2<Complex<f64>>,
+        qubits: &[Qubit],
+    ) -> Result<(), RusqError> {
+        self.try_apply(&qubits.iter().collect::<Vec<_>>(), matrix)
+    }
+
+    ///
+    /// Measures the given qubit, or returns an error if the qubit is invalid.
+    ///
+    pub fn try_measure(&mut self, qubit: &Qubit) -> Result<MeasuredResult, RusqError> {
+        self.check_qubits(&[qubit])?;
+        Ok(self.measure_unchecked(qubit))
+    }
+
+    /// Returns the amplitudes, where qubit `k` is bit `k` of the index.
+    pub(crate) fn states(&self) -> &[Complex<f64>] {
+        &self.states
+    }
+
+    /// Returns the random number generator for the measurements.
+    pub(crate) fn rng(&mut self) -> &mut XorShiftRng {
+        &mut self.rng
+    }
+
+    pub(crate) fn check_qubits(&self, qubits: &[&Qubit]) -> Result<(), RusqError> {
+        check_qubits(qubits, self.id, self.dimension)
+    }
+
+    fn check_matrix(&self, dim: usize, matrix: &Array2<Complex<f64>>) -> Result<(), RusqError> {
+        check_matrix(dim, matrix, self.unitarity_tolerance)
+    }
+
+    fn try_apply(
+        &mut self,
+        qubits: &[&Qubit],
+        matrix: &Array2<Complex<f64>>,
+    ) -> Result<(), RusqError> {
+        self.check_qubits(qubits)?;
+        self.check_matrix(qubits.len(), matrix)?;
+        self.apply_unchecked(qubits, matrix);
+        Ok(())
+    }
+
     fn apply(&mut self, qubits: &[&Qubit], matrix: &Array2<Complex<f64>>) {
-        let dim = qubits.len();
-
-        let masks = mask_vec(qubits);
-        for i in 0..(self.states.len() >> dim) {
-            let indices = indices_vec(i, qubits, &masks, dim);
-            let values = indices.iter().map(|&i| self.states[i]).collect::<Vec<_>>();
-            let new_values = matrix.dot(&arr1(&values));
-            for (&i, nv) in indices.iter().zip(new_values.to_vec()) {
-                self.states[i] = nv;
+        if let Err(e) = self.try_apply(qubits, matrix) {
+            panic!("{}", e);
+        }
+    }
+
+    fn apply_unchecked(&mut self, qubits: &[&Qubit], matrix: &Array2<Complex<f64>>) {
+        apply_unchecked(&mut self.states, qubits, matrix);
+    }
+}
+
+///
+/// Checks that `qubits` are distinct qubits of the machine `machine` with `qubits_len` qubits.
+///
+pub(crate) fn check_qubits(
+    qubits: &[&Qubit],
+    machine: usize,
+    qubits_len: usize,
+) -> Result<(), RusqError> {
+    for (i, qubit) in qubits.iter().enumerate() {
+        if qubit.machine != machine {
+            return Err(RusqError::ForeignQubit { index: qubit.index });
+        }
+
+        if qubit.index >= qubits_len {
+            return Err(RusqError::QubitOutOfRange {
+                index: qubit.index,
+                qubits_len,
+            });
+        }
+
+        if qubits[..i].iter().any(|q| q.index == qubit.index) {
+            return Err(RusqError::DuplicateQubit { index: qubit.index });
+        }
+    }
+
+    Ok(())
+}
+
+///
+/// Checks that `matrix` is for `dim` qubits, and that it is unitary within
+/// `unitarity_tolerance` if given.
+///
+pub(crate) fn check_matrix(
+    dim: usize,
+    matrix: &Array2<Complex<f64>>,
+    unitarity_tolerance: Option<f64>,
+) -> Result<(), RusqError> {
+    let size = 1 << dim;
+    if matrix.dim() != (size, size) {
+        return Err(RusqError::InvalidMatrixShape {
+            expected: (size, size),
+            actual: matrix.dim(),
+        });
+    }
+
+    if let Some(tolerance) = unitarity_tolerance {
+        let product = matrix.t().mapv(|x| x.conj()).dot(matrix);
+        let is_unitary = product.indexed_iter().all(|((i, j), x)| {
+            let expected = if i == j { 1. } else { 0. };
+            (x - Complex::new(expected, 0.)).norm() <= tolerance
+        });
+        if !is_unitary {
+            return Err(RusqError::NonUnitaryMatrix);
+        }
+    }
+
+    Ok(())
+}
+
+///
+/// Applies `matrix` to `qubits` of the amplitudes `states`.
+///
+/// The bits of the index other than `qubits` are left as they are, so that `states` may be
+/// several states laid out one after another.
+///
+pub(crate) fn apply_unchecked(
+    states: &mut [Complex<f64>],
+    qubits: &[&Qubit],
+    matrix: &Array2<Complex<f64>>,
+) {
+    let dim = qubits.len();
+    if dim == 0 {
+        // Just a global phase
+        for state in states.iter_mut() {
+            *state *= matrix[[0, 0]];
+        }
+        return;
+    }
+
+    let size = 1 << dim;
+    let masks = mask_vec(qubits);
+    let offsets = offsets_vec(qubits);
+    let groups = states.len() >> dim;
+
+    // A diagonal matrix only scales the amplitudes, and the rows of one can be skipped.
+    // This makes the phase gates much faster.
+    let is_diagonal = matrix
+        .indexed_iter()
+        .all(|((i, j), x)| i == j || *x == Complex::new(0., 0.));
+    if is_diagonal {
+        for (row, &offset) in offsets.iter().enumerate() {
+            let factor = matrix[[row, row]];
+            if factor == Complex::new(1., 0.) {
+                continue;
+            }
+            for i in 0..groups {
+                states[base_index(i, &masks, dim) | offset] *= factor;
             }
         }
+        return;
+    }
+
+    let matrix = matrix.iter().cloned().collect::<Vec<_>>();
+    let mut values = vec![Complex::new(0., 0.); size];
+    for i in 0..groups {
+        let base = base_index(i, &masks, dim);
+        for (value, &offset) in values.iter_mut().zip(&offsets) {
+            *value = states[base | offset];
+        }
+        for (row, &offset) in matrix.chunks(size).zip(&offsets) {
+            states[base | offset] = row
+                .iter()
+                .zip(&values)
+                .fold(Complex::new(0., 0.), |acc, (m, v)| acc + m * v);
+        }
     }
 }
 
+///
+/// Returns a random number generator determined by `seed`.
+///
+/// The seed is expanded by SplitMix64 so that close seeds give unrelated sequences.
+///
+pub(crate) fn seeded_rng(seed: u64) -> XorShiftRng {
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    };
+    let (a, b) = (next(), next());
+    let words = [a as u32, (a >> 32) as u32, b as u32, (b >> 32) as u32 | 1];
+    XorShiftRng::from_seed(words)
+}
+
 fn mask_pair(qubit: &Qubit) -> (usize, usize) {
     let upper_mask = 0xFFFF_FFFF_FFFF_FFFFusize << (qubit.index + 1);
     let lower_mask = !(0xFFFF_FFFF_FFFF_FFFFusize << qubit.index);
@@ -111,7 +540,7 @@ fn mask_vec(qubits: &[&Qubit]) -> Vec<usize> {
 
     for i in 1..qubits.len() {
         res[i] = (0xFFFF_FFFF_FFFF_FFFFusize << (qubits[qubits.len() - i - 1].index + 1))
-            | (!(0xFFFF_FFFF_FFFF_FFFFusize << (qubits[qubits.len() - i].index)));
+            & (!(0xFFFF_FFFF_FFFF_FFFFusize << (qubits[qubits.len() - i].index)));
     }
 
     res[qubits.len()] = !(0xFFFF_FFFF_FFFF_FFFFusize << qubits[0].index);
@@ -119,27 +548,39 @@ fn mask_vec(qubits: &[&Qubit]) -> Vec<usize> {
     res
 }
 
-fn indices_vec(index: usize, qubits: &[&Qubit], mask: &[usize], dim: usize) -> Vec<usize> {
-    let imask = (0..dim + 1)
+///
+/// Returns the index of the state with the bits of the qubits zero,
+/// whose other bits are given by `index`.
+///
+fn base_index(index: usize, mask: &[usize], dim: usize) -> usize {
+    (0..dim + 1)
         .map(|s| (index << (dim - s)) & mask[s])
-        .fold(0, |acc, m| acc | m);
+        .fold(0, |acc, m| acc | m)
+}
+
+///
+/// Returns the bits to be added to the base index for each row of the matrix,
+/// where the first qubit corresponds to the most significant bit of the row.
+///
+fn offsets_vec(qubits: &[&Qubit]) -> Vec<usize> {
+    let dim = qubits.len();
     (0..1 << dim)
         .map(|i| {
-            (0..dim).fold(imask, |acc, j| {
+            (0..dim).fold(0, |acc, j| {
                 acc | ((i >> (dim - 1 - j) & 0b1) << qubits[j].index)
             })
         })
         .collect()
 }
 
-impl QuantumMachine for QuantumSimulator {
-    fn measure(&mut self, qubit: &Qubit) -> MeasuredResult {
+impl QuantumSimulator {
+    fn measure_unchecked(&mut self, qubit: &Qubit) -> MeasuredResult {
         let (upper_mask, lower_mask) = mask_pair(qubit);
         let zero_norm_sqr: f64 = (0..(self.states.len() >> 1))
             .map(|i| self.states[index_pair(i, qubit, upper_mask, lower_mask).0].norm_sqr())
             .sum();
 
-        if zero_norm_sqr > rand::random::<f64>() {
+        if zero_norm_sqr > self.rng.gen::<f64>() {
             let norm = zero_norm_sqr.sqrt();
             for i in 0..(self.states.len() >> 1) {
                 let (iz, io) = index_pair(i, qubit, upper_mask, lower_mask);
@@ -157,9 +598,18 @@ impl QuantumMachine for QuantumSimulator {
             MeasuredResult::One
         }
     }
+}
+
+impl QuantumMachine for QuantumSimulator {
+    fn measure(&mut self, qubit: &Qubit) -> MeasuredResult {
+        match self.try_measure(qubit) {
+            Ok(r) => r,
+            Err(e) => panic!("{}", e),
+        }
+    }
 
     fn get_qubits(&self) -> Vec<Qubit> {
-        (0..self.dimension).map(|x| Qubit { index: x }).collect()
+        (0..self.dimension).map(|x| Qubit::new(x, self.id)).collect()
     }
 }
 
@@ -187,6 +637,12 @@ impl TripleGateApplicator for QuantumSimulator {
     }
 }
 
+impl MultiGateApplicator for QuantumSimulator {
+    fn apply_multi(&mut self, matrix: &Array2<Complex<f64>>, qubits: &[Qubit]) {
+        self.apply(&qubits.iter().collect::<Vec<_>>(), matrix);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -194,7 +650,7 @@ mod tests {
 
     #[test]
     fn test_mask_pair() {
-        let qubit = Qubit { index: 12 };
+        let qubit = Qubit::new(12, 0);
         let (upper_mask, lower_mask) = mask_pair(&qubit);
         assert_eq!(
             upper_mask,
@@ -209,7 +665,7 @@ mod tests {
 
     #[test]
     fn test_index_pair() {
-        let qubit = Qubit { index: 13 };
+        let qubit = Qubit::new(13, 0);
         let (upper_mask, lower_mask) = mask_pair(&qubit);
         let (iz, io) = index_pair(
             0b01011101_11111011_11011111usize,
@@ -221,4 +677,19 @@ mod tests {
 
         assert_eq!(io, 0b10111011_11111011_11011111usize);
     }
+
+    #[test]
+    fn test_indices_vec() {
+        let qubits = [Qubit::new(1, 0), Qubit::new(4, 0)];
+        let qubits = [&qubits[0], &qubits[1]];
+        let masks = mask_vec(&qubits);
+        let base = base_index(0b1011, &masks, 2);
+        assert_eq!(
+            offsets_vec(&qubits)
+                .iter()
+                .map(|&offset| base | offset)
+                .collect::<Vec<_>>(),
+            vec![0b100101, 0b110101, 0b100111, 0b110111]
+        );
+    }
 }
@@ -0,0 +1,227 @@
+//!
+//! A module for the simulator of the unitary matrices of circuits.
+//!
+
+use super::simulator::{apply_unchecked, check_matrix, check_qubits};
+use circuit::{Circuit, Operation};
+use gates::double::DoubleGateApplicator;
+use gates::multi::MultiGateApplicator;
+use gates::single::SingleGateApplicator;
+use gates::triple::TripleGateApplicator;
+use ndarray::prelude::*;
+use num::complex::Complex;
+use state;
+use {new_machine_id, MeasuredResult, QuantumMachine, Qubit};
+
+///
+/// A simulator accumulating the unitary matrix of the applied gates.
+///
+/// The matrix is the one of the gates applied since the creation or the last
+/// [reset](#method.reset), where qubit `k` is bit `k` of the row and column indices.
+/// Since the gates are applied to all the $2^n$ basis states at once, the memory consumption
+/// scales $4^n$ with the number of the qubits $n$.
+///
+/// The measurements are not unitary, and [measure](#method.measure) panics.
+///
+/// # Examples
+///
+/// ```
+/// use rusq::prelude::*;
+///
+/// let mut sim = UnitarySimulator::new(2);
+/// let qubits = sim.get_qubits();
+/// sim.H(&qubits[0]);
+/// sim.CNOT(&qubits[1], &qubits[0]);
+/// sim.H(&qubits[0]);
+///
+/// // CZ is diagonal.
+/// let cz = sim.unitary();
+/// for i in 0..4 {
+///     for j in 0..4 {
+///         let expected = match (i, j) {
+///             (3, 3) => -1.,
+///             (i, j) if i == j => 1.,
+///             _ => 0.,
+///         };
+///         assert!((cz[[i, j]].re - expected).abs() < 1e-10);
+///     }
+/// }
+/// ```
+///
+pub struct UnitarySimulator {
+    id: usize,
+    dimension: usize,
+    // The columns one after another, so that the column is given by the bits above the qubits
+    // and the gates act on each column as on a state.
+    columns: Vec<Complex<f64>>,
+    unitarity_tolerance: Option<f64>,
+}
+
+impl UnitarySimulator {
+    ///
+    /// Creates a new instance with a given number of qubits, whose matrix is the identity.
+    ///
+    pub fn new(n: usize) -> UnitarySimulator {
+        let mut sim = UnitarySimulator {
+            id: new_machine_id(),
+            dimension: n,
+            columns: vec![Complex::new(0., 0.); 1 << (2 * n)],
+            unitarity_tolerance: None,
+        };
+        sim.reset();
+        sim
+    }
+
+    /// Resets the matrix to the identity.
+    pub fn reset(&mut self) {
+        let size = 1 << self.dimension;
+        for (i, x) in self.columns.iter_mut().enumerate() {
+            *x = if i % size == i / size {
+                Complex::new(1., 0.)
+            } else {
+                Complex::new(0., 0.)
+            };
+        }
+    }
+
+    /// Returns the number of the qubits.
+    pub fn qubits_len(&self) -> usize {
+        self.dimension
+    }
+
+    /// Returns the unitary matrix of the applied gates.
+    pub fn unitary(&self) -> Array2<Complex<f64>> {
+        let size = 1 << self.dimension;
+        Array2::from_shape_fn((size, size), |(i, j)| self.columns[j * size + i])
+    }
+
+    ///
+    /// Sets whether the applied matrices are checked to be unitary within `tolerance`, as in
+    /// [QuantumSimulator](../simulator/struct.QuantumSimulator.html#method.set_unitarity_check).
+    ///
+    pub fn set_unitarity_check(&mut self, tolerance: Option<f64>) {
+        self.unitarity_tolerance = tolerance;
+    }
+
+    fn apply(&mut self, qubits: &[&Qubit], matrix: &Array2<Complex<f64>>) {
+        let checked = check_qubits(qubits, self.id, self.dimension)
+            .and_then(|_| check_matrix(qubits.len(), matrix, self.unitarity_tolerance));
+        if let Err(e) = checked {
+            panic!("{}", e);
+        }
+        apply_unchecked(&mut self.columns, qubits, matrix);
+    }
+}
+
+impl QuantumMachine for UnitarySimulator {
+    ///
+    /// # Panics
+    ///
+    /// Always panics since a measurement is not unitary.
+    ///
+    fn measure(&mut self, _qubit: &Qubit) -> MeasuredResult {
+        panic!("a measurement is not unitary");
+    }
+
+    fn get_qubits(&self) -> Vec<Qubit> {
+        (0..self.dimension)
+            .map(|x| Qubit::new(x, self.id))
+            .collect()
+    }
+}
+
+impl SingleGateApplicator for UnitarySimulator {
+    fn apply_single(&mut self, matrix: &Array2<Complex<f64>>, qubit: &Qubit) {
+        self.apply(&[qubit], matrix);
+    }
+}
+
+impl DoubleGateApplicator for UnitarySimulator {
+    fn apply_double(&mut self, matrix: &Array2<Complex<f64>>, qubit1: &Qubit, qubit2: &Qubit) {
+        self.apply(&[qubit1, qubit2], matrix);
+    }
+}
+
+impl TripleGateApplicator for UnitarySimulator {
+    fn apply_triple(
+        &mut self,
+        matrix: &Array2<Complex<f64>>,
+        qubit1: &Qubit,
+        qubit2: &Qubit,
+        qubit3: &Qubit,
+    ) {
+        self.apply(&[qubit1, qubit2, qubit3], matrix);
+    }
+}
+
+impl MultiGateApplicator for UnitarySimulator {
+    fn apply_multi(&mut self, matrix: &Array2<Complex<f64>>, qubits: &[Qubit]) {
+        self.apply(&qubits.iter().collect::<Vec<_>>(), matrix);
+    }
+}
+
+///
+/// Returns the unitary matrix of `circuit` on `qubits_len` qubits.
+///
+/// # Panics
+///
+/// Panics if `circuit` has a measurement or a classical condition, or if `qubits_len` is
+/// fewer than the qubits of `circuit`.
+///
+pub fn circuit_unitary(circuit: &Circuit, qubits_len: usize) -> Array2<Complex<f64>> {
+    for instruction in circuit.instructions() {
+        assert!(
+            instruction.condition.is_none(),
+            "a classically-controlled gate has no unitary matrix"
+        );
+        if let Operation::Measure(..) = instruction.operation {
+            panic!("a measurement is not unitary");
+        }
+    }
+
+    let mut sim = UnitarySimulator::new(qubits_len);
+    circuit.run(&mut sim);
+    sim.unitary()
+}
+
+///
+/// Returns `true` if the circuits `a` and `b` have the same unitary matrix up to a global
+/// phase, where each entry differs by at most `tolerance`.
+///
+/// The circuits are compared on the qubits of the larger one.
+///
+/// # Examples
+///
+/// ```
+/// use rusq::prelude::*;
+/// use rusq::circuit::Circuit;
+/// use rusq::simulator::unitary;
+///
+/// let mut a = Circuit::new(2, 0);
+/// let qubits = a.get_qubits();
+/// a.SWAP(&qubits[0], &qubits[1]);
+///
+/// let mut b = Circuit::new(2, 0);
+/// let qubits = b.get_qubits();
+/// b.CNOT(&qubits[0], &qubits[1]);
+/// b.CNOT(&qubits[1], &qubits[0]);
+/// b.CNOT(&qubits[0], &qubits[1]);
+/// assert!(unitary::circuits_equivalent(&a, &b, 1e-10));
+///
+/// b.Z(&qubits[0]);
+/// assert!(!unitary::circuits_equivalent(&a, &b, 1e-10));
+/// ```
+///
+/// # Panics
+///
+/// Panics if either circuit has a measurement or a classical condition.
+///
+pub fn circuits_equivalent(a: &Circuit, b: &Circuit, tolerance: f64) -> bool {
+    let n = a.qubits_len().max(b.qubits_len());
+    let (a, b) = (circuit_unitary(a, n), circuit_unitary(b, n));
+    state::equal_up_to_global_phase(
+        &a.iter().cloned().collect::<Vec<_>>(),
+        &b.iter().cloned().collect::<Vec<_>>(),
+        tolerance,
+    )
+}
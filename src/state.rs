@@ -0,0 +1,335 @@
+//!
+//! A module for comparing quantum states.
+//!
+//! The states are given by the types implementing [ToState](trait.ToState.html): a
+//! [QuantumSimulator](../simulator/struct.QuantumSimulator.html), its
+//! [Snapshot](../simulator/checkpoint/struct.Snapshot.html), a vector of amplitudes, where
+//! qubit `k` is bit `k` of the index, or a density matrix. The states must be normalized.
+//!
+//! Like the [entanglement](../entanglement/index.html) module, the comparison reads the
+//! amplitudes directly, and is meant for the tests of circuits. The macros
+//! [assert_state_approx_eq!](../macro.assert_state_approx_eq.html) and
+//! [assert_state_approx_ne!](../macro.assert_state_approx_ne.html) compare the states by the
+//! trace distance, which ignores the global phase.
+//!
+//! # Examples
+//!
+//! ```
+//! #[macro_use]
+//! extern crate rusq;
+//! extern crate num;
+//!
+//! use num::complex::Complex;
+//! use rusq::prelude::*;
+//! use rusq::state;
+//!
+//! # fn main() {
+//! let mut sim = QuantumSimulator::new(1);
+//! let qubit = &sim.get_qubits()[0];
+//! sim.H(qubit);
+//!
+//! let plus = vec![Complex::new(0.5f64.sqrt(), 0.); 2];
+//! assert!((state::fidelity(&sim, &plus) - 1.).abs() < 1e-10);
+//! assert_state_approx_eq!(sim, plus);
+//!
+//! // ZXZ = -X differs from X only by the global phase.
+//! sim.X(qubit);
+//! let mut other = QuantumSimulator::new(1);
+//! let qubit = &other.get_qubits()[0];
+//! other.H(qubit);
+//! other.Z(qubit);
+//! other.X(qubit);
+//! other.Z(qubit);
+//! assert!(state::equal_up_to_global_phase(&sim, &other, 1e-10));
+//! assert_state_approx_eq!(sim, other, 1e-12);
+//! # }
+//! ```
+//!
+
+use linalg::{adjoint, hermitian_eigen, psd_sqrt};
+use ndarray::prelude::*;
+use num::complex::Complex;
+use simulator::{QuantumSimulator, Snapshot};
+
+///
+/// A type for a pure or a mixed state.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub enum State {
+    /// A pure state with the amplitudes
+    Pure(Vec<Complex<f64>>),
+    /// A mixed state with the density matrix
+    Mixed(Array2<Complex<f64>>),
+}
+
+impl State {
+    /// Returns the dimension of the Hilbert space.
+    pub fn dimension(&self) -> usize {
+        match *self {
+            State::Pure(ref amplitudes) => amplitudes.len(),
+            State::Mixed(ref rho) => rho.rows(),
+        }
+    }
+
+    /// Returns the density matrix.
+    pub fn density_matrix(&self) -> Array2<Complex<f64>> {
+        match *self {
+            State::Pure(ref amplitudes) => {
+                let n = amplitudes.len();
+                Array2::from_shape_fn((n, n), |(i, j)| amplitudes[i] * amplitudes[j].conj())
+            }
+            State::Mixed(ref rho) => rho.clone(),
+        }
+    }
+}
+
+///
+/// A trait for the types which represent a quantum state.
+///
+pub trait ToState {
+    /// Returns the state.
+    fn to_state(&self) -> State;
+}
+
+impl ToState for State {
+    fn to_state(&self) -> State {
+        self.clone()
+    }
+}
+
+impl ToState for QuantumSimulator {
+    fn to_state(&self) -> State {
+        State::Pure(self.states().to_vec())
+    }
+}
+
+impl ToState for Snapshot {
+    fn to_state(&self) -> State {
+        State::Pure(self.amplitudes().to_vec())
+    }
+}
+
+impl ToState for [Complex<f64>] {
+    fn to_state(&self) -> State {
+        State::Pure(self.to_vec())
+    }
+}
+
+impl ToState for Vec<Complex<f64>> {
+    fn to_state(&self) -> State {
+        State::Pure(self.clone())
+    }
+}
+
+impl ToState for Array1<Complex<f64>> {
+    fn to_state(&self) -> State {
+        State::Pure(self.to_vec())
+    }
+}
+
+/// The density matrix is taken as a mixed state.
+impl ToState for Array2<Complex<f64>> {
+    fn to_state(&self) -> State {
+        State::Mixed(self.clone())
+    }
+}
+
+impl<T: ToState + ?Sized> ToState for &T {
+    fn to_state(&self) -> State {
+        (**self).to_state()
+    }
+}
+
+fn states<A, B>(a: &A, b: &B) -> (State, State)
+where
+    A: ToState + ?Sized,
+    B: ToState + ?Sized,
+{
+    let (a, b) = (a.to_state(), b.to_state());
+    assert_eq!(
+        a.dimension(),
+        b.dimension(),
+        "the states have the different dimensions"
+    );
+    (a, b)
+}
+
+fn inner_product(a: &[Complex<f64>], b: &[Complex<f64>]) -> Complex<f64> {
+    a.iter()
+        .zip(b)
+        .fold(Complex::new(0., 0.), |sum, (x, y)| sum + x.conj() * y)
+}
+
+/// Returns `|<a|b>|` and the phase of `<a|b>`.
+fn aligned(a: &[Complex<f64>], b: &[Complex<f64>]) -> (f64, Complex<f64>) {
+    let overlap = inner_product(a, b);
+    if overlap.norm() > 0. {
+        (overlap.norm(), overlap / overlap.norm())
+    } else {
+        (0., Complex::new(1., 0.))
+    }
+}
+
+///
+/// Returns the fidelity between the states `a` and `b`.
+///
+/// The fidelity is $|\langle a|b \rangle|^2$ for the pure states, and
+/// $(\mathrm{tr} \sqrt{\sqrt{\rho} \sigma \sqrt{\rho}})^2$ in general.
+///
+/// # Panics
+///
+/// Panics if the dimensions of the states are different.
+///
+pub fn fidelity<A, B>(a: &A, b: &B) -> f64
+where
+    A: ToState + ?Sized,
+    B: ToState + ?Sized,
+{
+    match states(a, b) {
+        (State::Pure(a), State::Pure(b)) => inner_product(&a, &b).norm_sqr(),
+        (State::Pure(a), State::Mixed(rho)) | (State::Mixed(rho), State::Pure(a)) => {
+            inner_product(&a, &rho.dot(&Array1::from_vec(a.clone())).to_vec()).re
+        }
+        (State::Mixed(rho), State::Mixed(sigma)) => {
+            let sqrt = psd_sqrt(&rho);
+            let root: f64 = hermitian_eigen(&sqrt.dot(&sigma).dot(&sqrt))
+                .0
+                .into_iter()
+                .map(|p| p.max(0.).sqrt())
+                .sum();
+            root * root
+        }
+    }
+}
+
+///
+/// Returns the trace distance $\frac{1}{2} \mathrm{tr} |\rho - \sigma|$ between the states
+/// `a` and `b`, which is $\sqrt{1 - |\langle a|b \rangle|^2}$ for the pure states.
+///
+/// # Panics
+///
+/// Panics if the dimensions of the states are different.
+///
+pub fn trace_distance<A, B>(a: &A, b: &B) -> f64
+where
+    A: ToState + ?Sized,
+    B: ToState + ?Sized,
+{
+    match states(a, b) {
+        (State::Pure(a), State::Pure(b)) => {
+            // 1 - |<a|b>|^2 = |b - e^{iφ} a|^2 (1 + |<a|b>|) / 2 without the cancellation
+            let (overlap, phase) = aligned(&a, &b);
+            let difference: f64 = a
+                .iter()
+                .zip(&b)
+                .map(|(x, y)| (y - x * phase).norm_sqr())
+                .sum();
+            (difference * (1. + overlap) / 2.).sqrt()
+        }
+        (a, b) => {
+            let difference = a.density_matrix() - b.density_matrix();
+            let difference = (&difference + &adjoint(&difference)).mapv(|x| x / 2.);
+            hermitian_eigen(&difference)
+                .0
+                .into_iter()
+                .map(f64::abs)
+                .sum::<f64>()
+                / 2.
+        }
+    }
+}
+
+///
+/// Returns `true` if the states `a` and `b` are equal up to a global phase, that is, each
+/// amplitude differs by at most `tolerance` after the phase of `a` is aligned to `b`.
+///
+/// The entries of the density matrices are compared if either state is mixed.
+///
+/// # Panics
+///
+/// Panics if the dimensions of the states are different.
+///
+pub fn equal_up_to_global_phase<A, B>(a: &A, b: &B, tolerance: f64) -> bool
+where
+    A: ToState + ?Sized,
+    B: ToState + ?Sized,
+{
+    match states(a, b) {
+        (State::Pure(a), State::Pure(b)) => {
+            let phase = aligned(&a, &b).1;
+            a.iter()
+                .zip(&b)
+                .all(|(x, y)| (y - x * phase).norm() <= tolerance)
+        }
+        (a, b) => (a.density_matrix() - b.density_matrix())
+            .iter()
+            .all(|x| x.norm() <= tolerance),
+    }
+}
+
+///
+/// Asserts that two states are equal up to the global phase, by the
+/// [trace distance](state/fn.trace_distance.html) at most the tolerance, which is `1e-8` by
+/// default.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate rusq;
+///
+/// use rusq::prelude::*;
+///
+/// # fn main() {
+/// let mut sim = QuantumSimulator::new(2);
+/// let qubits = sim.get_qubits();
+/// let initial = sim.snapshot();
+///
+/// sim.H(&qubits[0]);
+/// sim.H(&qubits[0]);
+/// assert_state_approx_eq!(sim, initial);
+///
+/// sim.ry(1e-6, &qubits[1]);
+/// assert_state_approx_eq!(sim, initial, 1e-6);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! assert_state_approx_eq {
+    ($left: expr, $right: expr) => {
+        assert_state_approx_eq!($left, $right, 1e-8)
+    };
+
+    ($left: expr, $right: expr, $tolerance: expr) => {{
+        let distance = $crate::state::trace_distance(&$left, &$right);
+        let tolerance: f64 = $tolerance;
+        if !(distance <= tolerance) {
+            panic!(
+                "assertion failed: the states are not approximately equal\n  trace distance: {}\n  tolerance: {}",
+                distance, tolerance
+            );
+        }
+    }};
+}
+
+///
+/// Asserts that two states differ by the [trace distance](state/fn.trace_distance.html)
+/// larger than the tolerance, which is `1e-8` by default.
+///
+#[macro_export]
+macro_rules! assert_state_approx_ne {
+    ($left: expr, $right: expr) => {
+        assert_state_approx_ne!($left, $right, 1e-8)
+    };
+
+    ($left: expr, $right: expr, $tolerance: expr) => {{
+        let distance = $crate::state::trace_distance(&$left, &$right);
+        let tolerance: f64 = $tolerance;
+        if distance <= tolerance {
+            panic!(
+                "assertion failed: the states are approximately equal\n  trace distance: {}\n  tolerance: {}",
+                distance, tolerance
+            );
+        }
+    }};
+}
@@ -0,0 +1,318 @@
+//!
+//! A module for the KAK (Cartan) decomposition of two-qubit unitaries.
+//!
+//! Any two-qubit unitary is written as
+//! $U = e^{i\alpha} (A_1 \otimes B_1) e^{i(c_1 XX + c_2 YY + c_3 ZZ)} (A_0 \otimes B_0)$
+//! with single-qubit unitaries $A_j, B_j$, and the interaction in the middle is synthesized
+//! by at most three CNOTs, following Vatan and Williams, Phys. Rev. A 69, 032315 (2004).
+//!
+
+use super::zyz::ZyzDecomposition;
+use circuit::Circuit;
+use error::RusqError;
+use gates::double::DoubleGateApplicator;
+use gates::single::{SingleGateApplicator, H, ID, X, Y, Z};
+use linalg::{adjoint, determinant, hermitian_eigen, kron};
+use ndarray::prelude::*;
+use num::complex::Complex;
+use simulator::simulator::check_matrix;
+use std::f64::consts::{E, FRAC_1_PI, FRAC_PI_2, FRAC_PI_4, PI, SQRT_2};
+use Qubit;
+
+///
+/// A type for the KAK decomposition of a two-qubit unitary.
+///
+/// As for [apply_double](../../gates/double/trait.DoubleGateApplicator.html), the first
+/// qubit is the most significant bit of the row and column indices, and $A_j$ act on it.
+///
+/// # Examples
+///
+/// ```
+/// use rusq::prelude::*;
+/// use rusq::circuit::Circuit;
+/// use rusq::gates::double::SWAP;
+/// use rusq::simulator::unitary;
+/// use rusq::synthesis::kak::KakDecomposition;
+///
+/// let kak = KakDecomposition::new(&SWAP.matrix).unwrap();
+/// assert_eq!(kak.cnot_count(), 3);
+///
+/// let mut swap = Circuit::new(2, 0);
+/// let qubits = swap.get_qubits();
+/// swap.SWAP(&qubits[0], &qubits[1]);
+/// assert!(unitary::circuits_equivalent(&kak.to_circuit(), &swap, 1e-8));
+/// ```
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct KakDecomposition {
+    /// The global phase $\alpha$
+    pub phase: f64,
+    /// The unitaries $A_0$ and $B_0$ applied before the interaction
+    pub before: [Array2<Complex<f64>>; 2],
+    /// The coefficients $c_1, c_2, c_3$ of the interaction, each in $(-\pi/4, \pi/4]$
+    pub interaction: [f64; 3],
+    /// The unitaries $A_1$ and $B_1$ applied after the interaction
+    pub after: [Array2<Complex<f64>>; 2],
+}
+
+impl KakDecomposition {
+    ///
+    /// Decomposes the 4x4 unitary `matrix`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `matrix` is not a 4x4 unitary matrix within `1e-8`.
+    ///
+    pub fn new(matrix: &Array2<Complex<f64>>) -> Result<KakDecomposition, RusqError> {
+        check_matrix(2, matrix, Some(1e-8))?;
+
+        let magic = magic_basis();
+        let det_phase = determinant(matrix).arg() / 4.;
+        let special = matrix.mapv(|x| x * Complex::from_polar(&1., &-det_phase));
+        let u = adjoint(&magic).dot(&special).dot(&magic);
+
+        // U = K_1 A P^T in the magic basis, where K_1 and P are real orthogonal and
+        // A^2 = P^T U^T U P is diagonal.
+        let square = u.t().dot(&u);
+        let mut p = diagonalize_symmetric(&square);
+        if determinant(&p).re < 0. {
+            p.column_mut(0).mapv_inplace(|x| -x);
+        }
+        let diagonal = p.t().dot(&square).dot(&p);
+        let mut angles: Vec<f64> = (0..4).map(|k| diagonal[[k, k]].arg() / 2.).collect();
+        let inverse = Array2::from_shape_fn((4, 4), |(i, j)| {
+            if i == j {
+                Complex::from_polar(&1., &-angles[i])
+            } else {
+                Complex::new(0., 0.)
+            }
+        });
+        let mut k1 = u.dot(&p).dot(&inverse);
+        if determinant(&k1).re < 0. {
+            angles[0] += PI;
+            k1.column_mut(0).mapv_inplace(|x| -x);
+        }
+
+        // The diagonal of XX, YY and ZZ in the magic basis are orthogonal sign vectors.
+        let paulis = [&X.matrix, &Y.matrix, &Z.matrix];
+        let mut phase = det_phase + angles.iter().sum::<f64>() / 4.;
+        let mut interaction = [0.; 3];
+        let mut pauli = ID.matrix.clone();
+        for (j, matrix) in paulis.iter().enumerate() {
+            let signs = adjoint(&magic).dot(&kron(matrix, matrix)).dot(&magic);
+            let c = (0..4).map(|k| angles[k] * signs[[k, k]].re).sum::<f64>() / 4.;
+
+            // e^{i(c + n pi/2) PP} = e^{icPP} (iPP)^n
+            let n = (c / FRAC_PI_2 - 0.5).ceil();
+            interaction[j] = c - n * FRAC_PI_2;
+            phase += n * FRAC_PI_2;
+            if n as i64 % 2 != 0 {
+                pauli = pauli.dot(*matrix);
+            }
+        }
+
+        let (a0, b0) = factor(&magic.dot(&p.t()).dot(&adjoint(&magic)));
+        let (a1, b1) = factor(&magic.dot(&k1).dot(&adjoint(&magic)));
+        Ok(KakDecomposition {
+            phase,
+            before: [a0, b0],
+            interaction,
+            after: [a1.dot(&pauli), b1.dot(&pauli)],
+        })
+    }
+
+    ///
+    /// Returns the number of CNOTs in the synthesized gates.
+    ///
+    /// This is 0 if the unitary is a product of single-qubit unitaries, 1 if it is locally
+    /// equivalent to CNOT, i.e. the only non-zero coefficient is $\pm\pi/4$, 2 if any of the
+    /// coefficients is zero and 3 otherwise.
+    ///
+    pub fn cnot_count(&self) -> usize {
+        let zeros = self.interaction.iter().filter(|c| c.abs() <= 1e-10).count();
+        let quarters = self
+            .interaction
+            .iter()
+            .filter(|c| (c.abs() - FRAC_PI_4).abs() <= 1e-10)
+            .count();
+        match (zeros, quarters) {
+            (3, _) => 0,
+            (2, 1) => 1,
+            (0, _) => 3,
+            _ => 2,
+        }
+    }
+
+    ///
+    /// Applies CNOTs and rotations to `qubit1` and `qubit2` of `machine`, which equal the
+    /// unitary up to the global phase.
+    ///
+    pub fn apply<M>(&self, machine: &mut M, qubit1: &Qubit, qubit2: &Qubit)
+    where
+        M: SingleGateApplicator + DoubleGateApplicator,
+    {
+        let single = |matrix: &Array2<Complex<f64>>| ZyzDecomposition::new(matrix).unwrap();
+        let [c1, c2, c3] = self.interaction;
+        match self.cnot_count() {
+            0 => {
+                single(&self.after[0].dot(&self.before[0])).apply(machine, qubit1);
+                single(&self.after[1].dot(&self.before[1])).apply(machine, qubit2);
+            }
+            1 => {
+                // e^{i c PP} = (W^dagger)^{(x)2} e^{i c ZZ} W^{(x)2} with W^dagger Z W = P, and
+                // e^{i pi/4 ZZ} is CZ = (I (x) H) CNOT (I (x) H) up to Rz(-pi/2) on each qubit.
+                // e^{-i pi/4 ZZ} is e^{i pi/4 ZZ} (Z (x) Z) up to the global phase.
+                let (axis, c) = self
+                    .interaction
+                    .iter()
+                    .cloned()
+                    .enumerate()
+                    .max_by(|x, y| x.1.abs().partial_cmp(&y.1.abs()).unwrap())
+                    .unwrap();
+                let w = match axis {
+                    0 => H.matrix.clone(),
+                    1 => rx(FRAC_PI_2),
+                    _ => ID.matrix.clone(),
+                };
+                let w_dagger = adjoint(&w);
+                let w = if c < 0. { Z.matrix.dot(&w) } else { w };
+                single(&w.dot(&self.before[0])).apply(machine, qubit1);
+                single(&H.matrix.dot(&w).dot(&self.before[1])).apply(machine, qubit2);
+                machine.CNOT(qubit1, qubit2);
+                let after = w_dagger.dot(&rz(-FRAC_PI_2));
+                single(&self.after[0].dot(&after)).apply(machine, qubit1);
+                single(&self.after[1].dot(&after).dot(&H.matrix)).apply(machine, qubit2);
+            }
+            2 => {
+                // CNOT e^{i(a XI + b IZ)} CNOT = e^{i(a XX + b ZZ)}, where XX and ZZ are turned
+                // into the two interactions with non-zero coefficients by W (x) W.
+                let (w, a, b) = if c3.abs() <= 1e-10 {
+                    (rx(FRAC_PI_2), c1, c2)
+                } else if c2.abs() <= 1e-10 {
+                    (ID.matrix.clone(), c1, c3)
+                } else {
+                    (rz(FRAC_PI_2), c2, c3)
+                };
+                let w_dagger = adjoint(&w);
+                single(&w.dot(&self.before[0])).apply(machine, qubit1);
+                single(&w.dot(&self.before[1])).apply(machine, qubit2);
+                machine.CNOT(qubit1, qubit2);
+                machine.rx(-2. * a, qubit1);
+                machine.rz(-2. * b, qubit2);
+                machine.CNOT(qubit1, qubit2);
+                single(&self.after[0].dot(&w_dagger)).apply(machine, qubit1);
+                single(&self.after[1].dot(&w_dagger)).apply(machine, qubit2);
+            }
+            _ => {
+                // The interaction is Rz(pi/2) on qubit1 after the CNOTs and Rz(-pi/2) on qubit2
+                // before them, which are merged into the single-qubit unitaries.
+                single(&self.before[0]).apply(machine, qubit1);
+                single(&rz(-FRAC_PI_2).dot(&self.before[1])).apply(machine, qubit2);
+                machine.CNOT(qubit2, qubit1);
+                machine.rz(FRAC_PI_2 - 2. * c3, qubit1);
+                machine.ry(2. * c1 - FRAC_PI_2, qubit2);
+                machine.CNOT(qubit1, qubit2);
+                machine.ry(FRAC_PI_2 - 2. * c2, qubit2);
+                machine.CNOT(qubit2, qubit1);
+                single(&self.after[0].dot(&rz(FRAC_PI_2))).apply(machine, qubit1);
+                single(&self.after[1]).apply(machine, qubit2);
+            }
+        }
+    }
+
+    /// Returns the gates as a circuit on two qubits.
+    pub fn to_circuit(&self) -> Circuit {
+        let mut circuit = Circuit::new(2, 0);
+        let qubits = circuit.get_qubits();
+        self.apply(&mut circuit, &qubits[0], &qubits[1]);
+        circuit
+    }
+}
+
+/// Returns the magic basis as the columns, in which the local unitaries are real orthogonal.
+fn magic_basis() -> Array2<Complex<f64>> {
+    let (o, r, i) = (
+        Complex::new(0., 0.),
+        Complex::new(0.5f64.sqrt(), 0.),
+        Complex::new(0., 0.5f64.sqrt()),
+    );
+    array![[r, o, o, i], [o, i, r, o], [o, i, -r, o], [r, o, o, -i]]
+}
+
+/// Returns the rotation around the X axis.
+fn rx(theta: f64) -> Array2<Complex<f64>> {
+    let (c, s) = ((theta / 2.).cos(), (theta / 2.).sin());
+    array![
+        [Complex::new(c, 0.), Complex::new(0., -s)],
+        [Complex::new(0., -s), Complex::new(c, 0.)]
+    ]
+}
+
+/// Returns the rotation around the Z axis.
+fn rz(theta: f64) -> Array2<Complex<f64>> {
+    let o = Complex::new(0., 0.);
+    array![
+        [Complex::from_polar(&1., &(-theta / 2.)), o],
+        [o, Complex::from_polar(&1., &(theta / 2.))]
+    ]
+}
+
+///
+/// Returns the real orthogonal matrix diagonalizing the symmetric unitary `matrix`.
+///
+/// The real and imaginary parts commute and share the eigenvectors, which are obtained from
+/// a generic combination of them.
+///
+fn diagonalize_symmetric(matrix: &Array2<Complex<f64>>) -> Array2<Complex<f64>> {
+    let mut best = (f64::INFINITY, None);
+    for &weight in &[0.5772156649, SQRT_2, -E, FRAC_1_PI] {
+        let combination = matrix.mapv(|x| Complex::new(x.re + weight * x.im, 0.));
+        let mut vectors = hermitian_eigen(&combination).1;
+        // The eigenvectors are real up to the phases of the columns.
+        for mut column in vectors.gencolumns_mut() {
+            let largest = column
+                .iter()
+                .cloned()
+                .max_by(|x, y| x.norm().partial_cmp(&y.norm()).unwrap())
+                .unwrap();
+            let phase = largest.conj() / largest.norm();
+            column.mapv_inplace(|x| Complex::new((x * phase).re, 0.));
+        }
+        let diagonal = vectors.t().dot(matrix).dot(&vectors);
+        let off = diagonal
+            .indexed_iter()
+            .filter(|&((i, j), _)| i != j)
+            .map(|(_, x)| x.norm_sqr())
+            .sum::<f64>();
+        if off < best.0 {
+            best = (off, Some(vectors));
+        }
+        if off <= 1e-24 {
+            break;
+        }
+    }
+    best.1.unwrap()
+}
+
+/// Factors `matrix` into `a` and `b` such that `matrix` is the Kronecker product of them.
+fn factor(matrix: &Array2<Complex<f64>>) -> (Array2<Complex<f64>>, Array2<Complex<f64>>) {
+    let block =
+        |r: usize, s: usize| Array2::from_shape_fn((2, 2), |(p, q)| matrix[[2 * r + p, 2 * s + q]]);
+    let norm = |r: usize, s: usize| block(r, s).iter().map(|x| x.norm_sqr()).sum::<f64>();
+    let (r, s) = (0..4)
+        .map(|k| (k / 2, k % 2))
+        .max_by(|&(r1, s1), &(r2, s2)| norm(r1, s1).partial_cmp(&norm(r2, s2)).unwrap())
+        .unwrap();
+
+    let b = block(r, s);
+    let b = &b / determinant(&b).sqrt();
+    let a = Array2::from_shape_fn((2, 2), |(r, s)| {
+        adjoint(&b)
+            .dot(&block(r, s))
+            .diag()
+            .iter()
+            .fold(Complex::new(0., 0.), |acc, x| acc + x)
+            / 2.
+    });
+    (a, b)
+}
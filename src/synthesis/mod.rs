@@ -0,0 +1,25 @@
+//!
+//! A module for the synthesis of unitary matrices into gates.
+//!
+//! * [ZyzDecomposition](zyz/struct.ZyzDecomposition.html) writes a single-qubit unitary as
+//!   the rotations $R_z R_y R_z$, or $U_3$, with the global phase
+//! * [KakDecomposition](kak/struct.KakDecomposition.html) writes a two-qubit unitary with
+//!   at most three CNOTs and single-qubit rotations
+//! * The [quantum Shannon decomposition](qsd/index.html) writes a unitary on any number of
+//!   qubits with CNOTs and single-qubit rotations, using the
+//!   [multiplexed rotations](multiplexor/index.html)
+//! * [prepare_state](state_preparation/fn.prepare_state.html) prepares any state from
+//!   $|0 \cdots 0\rangle$ by the multiplexed rotations
+//! * [SolovayKitaev](solovay_kitaev/struct.SolovayKitaev.html) approximates a single-qubit
+//!   unitary by the Clifford+T gates within a given error
+//!
+//! The synthesized gates can be applied to any machine, and are checked against the
+//! original matrix by [circuit_unitary](../simulator/unitary/fn.circuit_unitary.html).
+//!
+
+pub mod kak;
+pub mod multiplexor;
+pub mod qsd;
+pub mod solovay_kitaev;
+pub mod state_preparation;
+pub mod zyz;
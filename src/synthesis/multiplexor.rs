@@ -0,0 +1,101 @@
+//!
+//! A module for the uniformly controlled rotations, or the multiplexed rotations.
+//!
+//! A multiplexed rotation applies the rotation by `angles[x]` to the target when the controls
+//! are in the basis state $|x\rangle$, where `controls[0]` is the most significant bit of `x`.
+//! For $k$ controls, it is decomposed into $2^k$ rotations and $2^k$ CNOTs in the order of the
+//! Gray code, following Möttönen et al., Phys. Rev. Lett. 93, 130502 (2004).
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::synthesis::multiplexor;
+//! use std::f64::consts::PI;
+//!
+//! let mut sim = QuantumSimulator::new(2);
+//! let qubits = sim.get_qubits();
+//! sim.X(&qubits[0]);
+//!
+//! // Flips the target only if the control is |1>.
+//! multiplexor::ry(&mut sim, &[0., PI], &qubits[..1], &qubits[1]);
+//! assert_eq!(sim.measure(&qubits[1]), MeasuredResult::One);
+//! ```
+//!
+
+use gates::double::DoubleGateApplicator;
+use gates::single::SingleGateApplicator;
+use Qubit;
+
+/// Applies the rotations $R_y$ by `angles` multiplexed by `controls` to `target`.
+///
+/// # Panics
+///
+/// Panics if the length of `angles` is not $2^k$ for $k$ controls.
+///
+pub fn ry<M>(machine: &mut M, angles: &[f64], controls: &[Qubit], target: &Qubit)
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    multiplex(machine, angles, controls, target, |machine, angle| {
+        machine.ry(angle, target)
+    });
+}
+
+/// Applies the rotations $R_z$ by `angles` multiplexed by `controls` to `target`.
+///
+/// # Panics
+///
+/// Panics if the length of `angles` is not $2^k$ for $k$ controls.
+///
+pub fn rz<M>(machine: &mut M, angles: &[f64], controls: &[Qubit], target: &Qubit)
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    multiplex(machine, angles, controls, target, |machine, angle| {
+        machine.rz(angle, target)
+    });
+}
+
+fn multiplex<M, F>(machine: &mut M, angles: &[f64], controls: &[Qubit], target: &Qubit, rotate: F)
+where
+    M: DoubleGateApplicator,
+    F: Fn(&mut M, f64),
+{
+    let k = controls.len();
+    assert_eq!(
+        angles.len(),
+        1 << k,
+        "{} angles are needed for {} controls",
+        1 << k,
+        k
+    );
+    if angles.iter().all(|angle| angle.abs() <= 1e-12) {
+        return;
+    }
+    if k == 0 {
+        rotate(machine, angles[0]);
+        return;
+    }
+
+    // The CNOTs negate the angles of the rotations before them, so that the angle for x
+    // is \sum_i theta_i (-1)^{x . g_i} with the Gray code g_i.
+    let gray = |i: usize| i ^ (i >> 1);
+    let parity = |x: usize| (x.count_ones() % 2) as usize;
+    let size = angles.len();
+    for i in 0..size {
+        let theta = angles
+            .iter()
+            .enumerate()
+            .map(|(x, angle)| [1., -1.][parity(x & gray(i))] * angle)
+            .sum::<f64>()
+            / size as f64;
+        if theta.abs() > 1e-12 {
+            rotate(machine, theta);
+        }
+
+        // The bit flipped to the next Gray code, which is the highest one at the end
+        let bit = (gray(i) ^ gray((i + 1) % size)).trailing_zeros() as usize;
+        machine.CNOT(&controls[k - 1 - bit], target);
+    }
+}
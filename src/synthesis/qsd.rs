@@ -0,0 +1,232 @@
+//!
+//! A module for the synthesis of unitaries on any number of qubits by the quantum Shannon
+//! decomposition (QSD), following Shende, Bullock and Markov, IEEE Trans. Comput.-Aided Des.
+//! Integr. Circuits Syst. 25, 1000 (2006).
+//!
+//! A unitary on $n$ qubits is split by the cosine-sine decomposition into two block-diagonal
+//! unitaries and a multiplexed $R_y$ on the first qubit. Each block-diagonal unitary is
+//! further split into two unitaries on the other qubits and a multiplexed $R_z$, until the
+//! unitaries act on two qubits and are given by the [KAK decomposition](../kak/index.html).
+//! The decomposition uses at most $\frac{9}{16} 4^n - \frac{3}{2} 2^n$ CNOTs for $n \geq 2$.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::circuit::Circuit;
+//! use rusq::gates::triple::CCNOT;
+//! use rusq::simulator::unitary;
+//! use rusq::synthesis::qsd;
+//!
+//! let circuit = qsd::decompose(&CCNOT.matrix).unwrap();
+//!
+//! let mut toffoli = Circuit::new(3, 0);
+//! let qubits = toffoli.get_qubits();
+//! toffoli.CCNOT(&qubits[0], &qubits[1], &qubits[2]);
+//! assert!(unitary::circuits_equivalent(&circuit, &toffoli, 1e-8));
+//! ```
+//!
+
+use super::kak::KakDecomposition;
+use super::multiplexor;
+use super::zyz::ZyzDecomposition;
+use circuit::Circuit;
+use error::RusqError;
+use gates::double::DoubleGateApplicator;
+use gates::single::SingleGateApplicator;
+use linalg::{adjoint, hermitian_eigen, unitary_eigen};
+use ndarray::prelude::*;
+use num::complex::Complex;
+use simulator::simulator::check_matrix;
+use Qubit;
+
+///
+/// Returns a circuit of CNOTs and single-qubit rotations equal to the unitary `matrix` up to
+/// the global phase.
+///
+/// As for [apply_multi](../../gates/multi/trait.MultiGateApplicator.html), the qubit `0` of
+/// the circuit is the most significant bit of the row and column indices of `matrix`.
+///
+/// # Errors
+///
+/// Returns an error if `matrix` is not a $2^n \times 2^n$ unitary matrix within `1e-8`.
+///
+pub fn decompose(matrix: &Array2<Complex<f64>>) -> Result<Circuit, RusqError> {
+    let n = (matrix.rows().max(1) as f64).log2().round() as usize;
+    check_matrix(n, matrix, Some(1e-8))?;
+
+    let mut circuit = Circuit::new(n, 0);
+    let qubits = circuit.get_qubits();
+    synthesize(&mut circuit, matrix, &qubits);
+    Ok(circuit)
+}
+
+///
+/// Applies CNOTs and single-qubit rotations equal to the unitary `matrix` up to the global
+/// phase to `qubits` of `machine`.
+///
+/// # Errors
+///
+/// Returns an error if `matrix` is not a unitary matrix on `qubits` within `1e-8`.
+///
+pub fn apply<M>(
+    machine: &mut M,
+    matrix: &Array2<Complex<f64>>,
+    qubits: &[Qubit],
+) -> Result<(), RusqError>
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    check_matrix(qubits.len(), matrix, Some(1e-8))?;
+    synthesize(machine, matrix, qubits);
+    Ok(())
+}
+
+fn synthesize<M>(machine: &mut M, matrix: &Array2<Complex<f64>>, qubits: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    match qubits.len() {
+        0 => {}
+        1 => ZyzDecomposition::new(matrix)
+            .unwrap()
+            .apply(machine, &qubits[0]),
+        2 => KakDecomposition::new(matrix)
+            .unwrap()
+            .apply(machine, &qubits[0], &qubits[1]),
+        _ => {
+            let (left, angles, right) = cosine_sine(matrix);
+            demultiplex(machine, &right, qubits);
+            multiplexor::ry(machine, &angles, &qubits[1..], &qubits[0]);
+            demultiplex(machine, &left, qubits);
+        }
+    }
+}
+
+///
+/// Applies the block-diagonal unitary $A \oplus B$, where the first qubit selects the block,
+/// as $(I \otimes V)(D \oplus D^\dagger)(I \otimes W)$ with $AB^\dagger = VD^2V^\dagger$.
+///
+fn demultiplex<M>(machine: &mut M, blocks: &[Array2<Complex<f64>>; 2], qubits: &[Qubit])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    let (values, v) = unitary_eigen(&blocks[0].dot(&adjoint(&blocks[1])));
+    let n = values.len();
+    let d = Array2::from_shape_fn((n, n), |(i, j)| {
+        if i == j {
+            Complex::from_polar(&1., &(values[i].arg() / 2.))
+        } else {
+            Complex::new(0., 0.)
+        }
+    });
+    let w = d.dot(&adjoint(&v)).dot(&blocks[1]);
+
+    synthesize(machine, &w, &qubits[1..]);
+    let angles: Vec<_> = values.iter().map(|x| -x.arg()).collect();
+    multiplexor::rz(machine, &angles, &qubits[1..], &qubits[0]);
+    synthesize(machine, &v, &qubits[1..]);
+}
+
+///
+/// Returns the cosine-sine decomposition
+/// $U = (L_0 \oplus L_1) \begin{pmatrix} C & -S \\ S & C \end{pmatrix} (R_0 \oplus R_1)$
+/// as `([L_0, L_1], angles, [R_0, R_1])`, where $C$ and $S$ are diagonal with the cosines
+/// and sines of the halves of `angles`.
+///
+#[allow(clippy::type_complexity)]
+fn cosine_sine(
+    matrix: &Array2<Complex<f64>>,
+) -> (
+    [Array2<Complex<f64>>; 2],
+    Vec<f64>,
+    [Array2<Complex<f64>>; 2],
+) {
+    let h = matrix.rows() / 2;
+    let block = |r: usize, c: usize| {
+        matrix
+            .slice(s![r * h..(r + 1) * h, c * h..(c + 1) * h])
+            .to_owned()
+    };
+    let (u00, u01, u10, u11) = (block(0, 0), block(0, 1), block(1, 0), block(1, 1));
+
+    // The right singular vectors are shared by both blocks of the first column.
+    let w = hermitian_eigen(&adjoint(&u00).dot(&u00)).1;
+    let (x, y) = (u00.dot(&w), u10.dot(&w));
+    let norm = |m: &Array2<Complex<f64>>, j: usize| {
+        m.column(j).iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt()
+    };
+    let halves: Vec<f64> = (0..h).map(|j| norm(&y, j).atan2(norm(&x, j))).collect();
+    let diagonal = |f: fn(f64) -> f64| {
+        Array2::from_shape_fn((h, h), |(i, j)| {
+            Complex::new(if i == j { f(halves[i]) } else { 0. }, 0.)
+        })
+    };
+    let (cos, sin) = (diagonal(f64::cos), diagonal(f64::sin));
+
+    // For the vanishing columns, L_0 = -U_{01} W and L_1 = U_{11} W give R_1 = W^\dagger
+    // if the unitary is block-diagonal or anti-block-diagonal.
+    let l0 = orthonormalize(&x, &-u01.dot(&w));
+    let l1 = orthonormalize(&y, &u11.dot(&w));
+    let r1 = cos.dot(&adjoint(&l1).dot(&u11)) - sin.dot(&adjoint(&l0).dot(&u01));
+    let angles = halves.iter().map(|x| 2. * x).collect();
+    ([l0, l1], angles, [adjoint(&w), r1])
+}
+
+///
+/// Normalizes the orthogonal columns of `matrix` in the descending order of the norms, and
+/// replaces the vanishing ones by the vectors orthogonal to the others, preferably the
+/// columns of `fallback` at the same positions.
+///
+fn orthonormalize(
+    matrix: &Array2<Complex<f64>>,
+    fallback: &Array2<Complex<f64>>,
+) -> Array2<Complex<f64>> {
+    let n = matrix.rows();
+    let norm = |v: &Array1<Complex<f64>>| v.iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| {
+        norm(&matrix.column(j).to_owned())
+            .partial_cmp(&norm(&matrix.column(i).to_owned()))
+            .unwrap()
+    });
+
+    let mut result = Array2::zeros((n, n));
+    for (k, &j) in order.iter().enumerate() {
+        let project = |mut v: Array1<Complex<f64>>, result: &Array2<Complex<f64>>| {
+            // Twice for the numerical stability
+            for _ in 0..2 {
+                for &i in &order[..k] {
+                    let column = result.column(i);
+                    let overlap = column
+                        .iter()
+                        .zip(v.iter())
+                        .fold(Complex::new(0., 0.), |acc, (x, y)| acc + x.conj() * y);
+                    v -= &column.mapv(|x| x * overlap);
+                }
+            }
+            v
+        };
+
+        let mut v = project(matrix.column(j).to_owned(), &result);
+        if norm(&v) <= 1e-12 {
+            v = project(fallback.column(j).to_owned(), &result);
+            if norm(&v) <= 0.5 {
+                v = (0..n)
+                    .map(|e| {
+                        project(
+                            Array1::from_shape_fn(n, |i| {
+                                Complex::new(if i == e { 1. } else { 0. }, 0.)
+                            }),
+                            &result,
+                        )
+                    })
+                    .max_by(|a, b| norm(a).partial_cmp(&norm(b)).unwrap())
+                    .unwrap();
+            }
+        }
+        let length = norm(&v);
+        result.column_mut(j).assign(&v.mapv(|x| x / length));
+    }
+    result
+}
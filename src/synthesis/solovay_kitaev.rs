@@ -0,0 +1,439 @@
+//!
+//! A module for the approximation of single-qubit unitaries by the Clifford+T gates.
+//!
+//! The unitaries are approximated by the Solovay-Kitaev algorithm as in Dawson and Nielsen,
+//! Quantum Inf. Comput. 6, 81 (2006). The initial approximations are taken from an
+//! $\epsilon$-net of all the Clifford+T unitaries up to a given T-count, enumerated by the
+//! normal form of Matsumoto and Amano, arXiv:0806.3834. Each recursion corrects the previous
+//! approximation by a balanced group commutator of approximations, so that the error
+//! decreases as $\epsilon_{n+1} \sim \epsilon_n^{3/2}$ while the length grows five times.
+//!
+//! The error of an approximation $V$ of $U$ is the distance in the operator norm up to the
+//! global phase, $\min_\phi \|U - e^{i\phi} V\| = \sqrt{2 - |\mathrm{tr}\, U^\dagger V|}$.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusq::prelude::*;
+//! use rusq::circuit::Circuit;
+//! use rusq::simulator::unitary;
+//! use rusq::synthesis::solovay_kitaev::SolovayKitaev;
+//!
+//! let mut rotation = Circuit::new(1, 0);
+//! let qubit = rotation.get_qubits()[0];
+//! rotation.rz(0.3, &qubit);
+//! let matrix = unitary::circuit_unitary(&rotation, 1);
+//!
+//! let sk = SolovayKitaev::new(6);
+//! let approximation = sk.approximate(&matrix, 1e-2).unwrap();
+//! assert!(approximation.error <= 1e-2);
+//!
+//! let mut sim = UnitarySimulator::new(1);
+//! let qubit = sim.get_qubits()[0];
+//! approximation.apply(&mut sim, &qubit);
+//! assert!(sim.unitary()[[1, 0]].norm() <= 1e-2);
+//! ```
+//!
+
+use circuit::Circuit;
+use error::RusqError;
+use gates::single::SingleGateApplicator;
+use ndarray::prelude::*;
+use num::complex::Complex;
+use simulator::simulator::check_matrix;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+use Qubit;
+
+/// The maximum number of the recursions
+const MAX_DEPTH: usize = 6;
+
+///
+/// A type for the gates of the Clifford+T basis.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum CliffordT {
+    /// The Hadamard gate
+    H,
+    /// The phase gate $\mathrm{diag}(1, i)$
+    S,
+    /// The inverse of the phase gate
+    Sdg,
+    /// The gate $\mathrm{diag}(1, e^{i\pi/4})$
+    T,
+    /// The inverse of the T gate
+    Tdg,
+}
+
+impl CliffordT {
+    /// Returns the inverse of the gate.
+    pub fn inverse(self) -> CliffordT {
+        match self {
+            CliffordT::H => CliffordT::H,
+            CliffordT::S => CliffordT::Sdg,
+            CliffordT::Sdg => CliffordT::S,
+            CliffordT::T => CliffordT::Tdg,
+            CliffordT::Tdg => CliffordT::T,
+        }
+    }
+
+    /// Applies the gate to `qubit` of `machine`.
+    pub fn apply<M: SingleGateApplicator>(self, machine: &mut M, qubit: &Qubit) {
+        match self {
+            CliffordT::H => machine.H(qubit),
+            CliffordT::S => machine.phase(FRAC_PI_2, qubit),
+            CliffordT::Sdg => machine.phase(-FRAC_PI_2, qubit),
+            CliffordT::T => machine.phase(FRAC_PI_4, qubit),
+            CliffordT::Tdg => machine.phase(-FRAC_PI_4, qubit),
+        }
+    }
+
+    fn quaternion(self) -> Quaternion {
+        let r = 0.5f64.sqrt();
+        match self {
+            CliffordT::H => Quaternion([0., r, 0., r]),
+            CliffordT::S => Quaternion::rotation([0., 0., 1.], FRAC_PI_2),
+            CliffordT::Sdg => Quaternion::rotation([0., 0., 1.], -FRAC_PI_2),
+            CliffordT::T => Quaternion::rotation([0., 0., 1.], FRAC_PI_4),
+            CliffordT::Tdg => Quaternion::rotation([0., 0., 1.], -FRAC_PI_4),
+        }
+    }
+}
+
+///
+/// A type for an approximation by the Clifford+T gates.
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct Approximation {
+    /// The gates in the order of the application
+    pub gates: Vec<CliffordT>,
+    /// The distance from the approximated unitary up to the global phase
+    pub error: f64,
+}
+
+impl Approximation {
+    /// Returns the number of the T gates and their inverses.
+    pub fn t_count(&self) -> usize {
+        self.gates
+            .iter()
+            .filter(|&&gate| gate == CliffordT::T || gate == CliffordT::Tdg)
+            .count()
+    }
+
+    /// Applies the gates to `qubit` of `machine`.
+    pub fn apply<M: SingleGateApplicator>(&self, machine: &mut M, qubit: &Qubit) {
+        for gate in &self.gates {
+            gate.apply(machine, qubit);
+        }
+    }
+
+    /// Returns the gates as a circuit on a single qubit.
+    pub fn to_circuit(&self) -> Circuit {
+        let mut circuit = Circuit::new(1, 0);
+        let qubit = circuit.get_qubits()[0];
+        self.apply(&mut circuit, &qubit);
+        circuit
+    }
+}
+
+///
+/// A type for the Solovay-Kitaev algorithm with an $\epsilon$-net of the Clifford+T
+/// unitaries.
+///
+pub struct SolovayKitaev {
+    net: Vec<(Quaternion, Vec<CliffordT>)>,
+}
+
+impl SolovayKitaev {
+    ///
+    /// Creates the $\epsilon$-net of the $24 (3 \cdot 2^k - 2)$ Clifford+T unitaries up to
+    /// the T-count `k`.
+    ///
+    /// The finer net gives shorter approximations, while it takes the memory and the time
+    /// exponentially in `k`. The T-count 10 gives the initial errors of at most about 0.05.
+    ///
+    pub fn new(t_count: usize) -> SolovayKitaev {
+        // The 24 Clifford unitaries up to the phase by the products of H and S
+        let mut cliffords = vec![(Quaternion::identity(), vec![])];
+        let mut k = 0;
+        while k < cliffords.len() {
+            for &gate in &[CliffordT::H, CliffordT::S] {
+                let (ref quaternion, ref gates) = cliffords[k].clone();
+                let next = gate.quaternion().mul(quaternion);
+                if cliffords.iter().all(|c| c.0.fidelity(&next) < 1. - 1e-9) {
+                    let mut gates = gates.clone();
+                    gates.push(gate);
+                    cliffords.push((next, gates));
+                }
+            }
+            k += 1;
+        }
+
+        // The normal form (T | 1)(HT | SHT)^* C, where the Clifford C is applied first
+        let syllables = [
+            vec![CliffordT::T, CliffordT::H],
+            vec![CliffordT::T, CliffordT::H, CliffordT::S],
+        ];
+        let mut net = vec![];
+        let mut words = cliffords;
+        for count in 0..t_count + 1 {
+            for (quaternion, gates) in &words {
+                net.push((quaternion.clone(), gates.clone()));
+                if count < t_count {
+                    let mut gates = gates.clone();
+                    gates.push(CliffordT::T);
+                    net.push((CliffordT::T.quaternion().mul(quaternion), gates));
+                }
+            }
+            words = words
+                .iter()
+                .flat_map(|word| syllables.iter().map(move |syllable| extend(word, syllable)))
+                .collect();
+        }
+
+        SolovayKitaev { net }
+    }
+
+    /// Returns the number of the unitaries in the $\epsilon$-net.
+    pub fn net_len(&self) -> usize {
+        self.net.len()
+    }
+
+    ///
+    /// Approximates the 2x2 unitary `matrix` by the Clifford+T gates.
+    ///
+    /// The recursion stops when the error is at most `precision`, or after six levels, when
+    /// the best approximation is returned even if its error exceeds `precision`. The error
+    /// may not decrease at the first levels if the net is too coarse.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `matrix` is not a 2x2 unitary matrix within `1e-8`.
+    ///
+    pub fn approximate(
+        &self,
+        matrix: &Array2<Complex<f64>>,
+        precision: f64,
+    ) -> Result<Approximation, RusqError> {
+        check_matrix(1, matrix, Some(1e-8))?;
+        let target = Quaternion::from_matrix(matrix);
+
+        let mut best = Approximation {
+            gates: vec![],
+            error: f64::INFINITY,
+        };
+        for depth in 0..MAX_DEPTH + 1 {
+            let gates = simplify(&self.recurse(&target, depth).1);
+            let error = target.distance(&product(&gates));
+            if error < best.error {
+                best = Approximation { gates, error };
+            }
+            if error <= precision {
+                break;
+            }
+        }
+        Ok(best)
+    }
+
+    fn recurse(&self, target: &Quaternion, depth: usize) -> (Quaternion, Vec<CliffordT>) {
+        if depth == 0 {
+            let nearest = self
+                .net
+                .iter()
+                .max_by(|x, y| {
+                    let (x, y) = (x.0.fidelity(target), y.0.fidelity(target));
+                    x.partial_cmp(&y).unwrap()
+                })
+                .unwrap();
+            return nearest.clone();
+        }
+
+        let (previous, gates) = self.recurse(target, depth - 1);
+        let (v, w) = balanced_commutator(&target.mul(&previous.conj()));
+        let (v, v_gates) = self.recurse(&v, depth - 1);
+        let (w, w_gates) = self.recurse(&w, depth - 1);
+
+        // V W V^dagger W^dagger U_{n-1}, where U_{n-1} is applied first
+        let mut result = gates;
+        result.extend(inverse(&w_gates));
+        result.extend(inverse(&v_gates));
+        result.extend(w_gates);
+        result.extend(v_gates);
+        let quaternion = v.mul(&w).mul(&v.conj()).mul(&w.conj()).mul(&previous);
+        (quaternion, result)
+    }
+}
+
+///
+/// Returns `V` and `W` such that $V W V^\dagger W^\dagger$ is `u`, where `V` and `W` are the
+/// rotations by the same angle.
+///
+fn balanced_commutator(u: &Quaternion) -> (Quaternion, Quaternion) {
+    // The sign is irrelevant, and the rotation by a small angle is taken.
+    let u = if u.0[0] < 0. { u.neg() } else { u.clone() };
+    let half = u.0[0].min(1.).acos();
+    let axis = [u.0[1], u.0[2], u.0[3]];
+    if half <= 1e-12 {
+        return (Quaternion::identity(), Quaternion::identity());
+    }
+
+    // The commutator of the rotations by phi around X and Y is a rotation by theta with
+    // sin(theta / 2) = 2 sin^2(phi / 2) sqrt(1 - sin^4(phi / 2)).
+    let phi = 2. * ((1. - half.cos()) / 2.).sqrt().sqrt().asin();
+    let v = Quaternion::rotation([1., 0., 0.], phi);
+    let w = Quaternion::rotation([0., 1., 0.], phi);
+    let commutator = v.mul(&w).mul(&v.conj()).mul(&w.conj());
+
+    // Rotates the axis of the commutator to the one of u.
+    let similarity = Quaternion::between(&commutator.axis(), &normalize(axis));
+    (
+        similarity.mul(&v).mul(&similarity.conj()),
+        similarity.mul(&w).mul(&similarity.conj()),
+    )
+}
+
+/// Appends `syllable` to the gates of `word`.
+fn extend(
+    word: &(Quaternion, Vec<CliffordT>),
+    syllable: &[CliffordT],
+) -> (Quaternion, Vec<CliffordT>) {
+    let mut result = word.clone();
+    for &gate in syllable {
+        result.0 = gate.quaternion().mul(&result.0);
+        result.1.push(gate);
+    }
+    result
+}
+
+/// Returns the inverse of the gates.
+fn inverse(gates: &[CliffordT]) -> Vec<CliffordT> {
+    gates.iter().rev().map(|gate| gate.inverse()).collect()
+}
+
+/// Returns the product of the gates applied in order.
+fn product(gates: &[CliffordT]) -> Quaternion {
+    gates
+        .iter()
+        .fold(Quaternion::identity(), |q, gate| gate.quaternion().mul(&q))
+}
+
+/// Cancels the adjacent inverses, and merges T T into S.
+fn simplify(gates: &[CliffordT]) -> Vec<CliffordT> {
+    let mut result: Vec<CliffordT> = vec![];
+    for &gate in gates {
+        match (result.last().cloned(), gate) {
+            (Some(last), gate) if last == gate.inverse() => {
+                result.pop();
+            }
+            (Some(CliffordT::T), CliffordT::T) => {
+                result.pop();
+                result.push(CliffordT::S);
+            }
+            (Some(CliffordT::Tdg), CliffordT::Tdg) => {
+                result.pop();
+                result.push(CliffordT::Sdg);
+            }
+            _ => result.push(gate),
+        }
+    }
+    result
+}
+
+fn normalize(v: [f64; 3]) -> [f64; 3] {
+    let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+    [v[0] / norm, v[1] / norm, v[2] / norm]
+}
+
+///
+/// A type for the unit quaternion $(w, x, y, z)$ representing the special unitary
+/// $w - i(xX + yY + zZ)$.
+///
+#[derive(Debug, PartialEq, Clone)]
+struct Quaternion([f64; 4]);
+
+impl Quaternion {
+    fn identity() -> Quaternion {
+        Quaternion([1., 0., 0., 0.])
+    }
+
+    /// The rotation $e^{-i \theta (n \cdot \sigma) / 2}$ around the unit vector `axis`
+    fn rotation(axis: [f64; 3], theta: f64) -> Quaternion {
+        let (c, s) = ((theta / 2.).cos(), (theta / 2.).sin());
+        Quaternion([c, s * axis[0], s * axis[1], s * axis[2]])
+    }
+
+    /// The rotation moving the unit vector `from` to the unit vector `to` by conjugation
+    fn between(from: &[f64; 3], to: &[f64; 3]) -> Quaternion {
+        let dot = from[0] * to[0] + from[1] * to[1] + from[2] * to[2];
+        let cross = [
+            from[1] * to[2] - from[2] * to[1],
+            from[2] * to[0] - from[0] * to[2],
+            from[0] * to[1] - from[1] * to[0],
+        ];
+        if 1. + dot <= 1e-12 {
+            // Any axis perpendicular to `from`
+            let other = if from[0].abs() < 0.9 {
+                [1., 0., 0.]
+            } else {
+                [0., 1., 0.]
+            };
+            let axis = normalize([
+                from[1] * other[2] - from[2] * other[1],
+                from[2] * other[0] - from[0] * other[2],
+                from[0] * other[1] - from[1] * other[0],
+            ]);
+            return Quaternion([0., axis[0], axis[1], axis[2]]);
+        }
+        let q = [1. + dot, cross[0], cross[1], cross[2]];
+        let norm = q.iter().map(|x| x * x).sum::<f64>().sqrt();
+        Quaternion([q[0] / norm, q[1] / norm, q[2] / norm, q[3] / norm])
+    }
+
+    /// The special unitary for the unitary `matrix` up to the phase
+    fn from_matrix(matrix: &Array2<Complex<f64>>) -> Quaternion {
+        let det = matrix[[0, 0]] * matrix[[1, 1]] - matrix[[0, 1]] * matrix[[1, 0]];
+        let m = matrix.mapv(|x| x / det.sqrt());
+        let (a, b, c, d) = (m[[0, 0]], m[[0, 1]], m[[1, 0]], m[[1, 1]]);
+        Quaternion([
+            (a + d).re / 2.,
+            -(b + c).im / 2.,
+            (c - b).re / 2.,
+            (d - a).im / 2.,
+        ])
+    }
+
+    fn mul(&self, other: &Quaternion) -> Quaternion {
+        let ([w1, x1, y1, z1], [w2, x2, y2, z2]) = (self.0, other.0);
+        Quaternion([
+            w1 * w2 - x1 * x2 - y1 * y2 - z1 * z2,
+            w1 * x2 + x1 * w2 + y1 * z2 - z1 * y2,
+            w1 * y2 - x1 * z2 + y1 * w2 + z1 * x2,
+            w1 * z2 + x1 * y2 - y1 * x2 + z1 * w2,
+        ])
+    }
+
+    fn conj(&self) -> Quaternion {
+        Quaternion([self.0[0], -self.0[1], -self.0[2], -self.0[3]])
+    }
+
+    fn neg(&self) -> Quaternion {
+        Quaternion([-self.0[0], -self.0[1], -self.0[2], -self.0[3]])
+    }
+
+    fn axis(&self) -> [f64; 3] {
+        normalize([self.0[1], self.0[2], self.0[3]])
+    }
+
+    /// $|\mathrm{tr}\, U^\dagger V| / 2$
+    fn fidelity(&self, other: &Quaternion) -> f64 {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .map(|(x, y)| x * y)
+            .sum::<f64>()
+            .abs()
+    }
+
+    fn distance(&self, other: &Quaternion) -> f64 {
+        (2. - 2. * self.fidelity(other)).max(0.).sqrt()
+    }
+}
@@ -0,0 +1,175 @@
+//!
+//! A module for the preparation of arbitrary states from $|0 \cdots 0\rangle$.
+//!
+//! The state is prepared by the multiplexed rotations as in Möttönen et al., Quantum Inf.
+//! Comput. 5, 467 (2005): the magnitudes of the amplitudes are set by $R_y$ from the highest
+//! bit, and then the phases by $R_z$ from the lowest bit.
+//!
+//! The rotations do not depend on the controls for the vanishing amplitudes, so that a
+//! sparse state needs fewer CNOTs: a basis state needs none. The real amplitudes are
+//! prepared only by $R_y$.
+//!
+
+use super::multiplexor;
+use gates::double::DoubleGateApplicator;
+use gates::single::SingleGateApplicator;
+use num::complex::Complex;
+use Qubit;
+
+///
+/// Prepares the state with `amplitudes` on `qubits` of `machine`, which must be in
+/// $|0 \cdots 0\rangle$, up to the global phase.
+///
+/// As the states of [QuantumSimulator](../../simulator/simulator/struct.QuantumSimulator.html),
+/// `qubits[k]` is bit `k` of the index of `amplitudes`.
+///
+/// # Examples
+///
+/// ```
+/// extern crate num;
+/// extern crate rusq;
+///
+/// use num::complex::Complex;
+/// use rusq::prelude::*;
+/// use rusq::state;
+/// use rusq::synthesis::state_preparation::prepare_state;
+///
+/// # fn main() {
+/// let amplitudes = vec![
+///     Complex::new(0.5, 0.),
+///     Complex::new(0., 0.5),
+///     Complex::new(-0.5, 0.),
+///     Complex::new(0., -0.5),
+/// ];
+///
+/// let mut sim = QuantumSimulator::new(2);
+/// let qubits = sim.get_qubits();
+/// prepare_state(&mut sim, &qubits, &amplitudes);
+/// assert!((state::fidelity(&sim, &amplitudes) - 1.).abs() < 1e-10);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the length of `amplitudes` is not $2^n$ for $n$ qubits, or `amplitudes` are not
+/// normalized within `1e-8`.
+///
+pub fn prepare_state<M>(machine: &mut M, qubits: &[Qubit], amplitudes: &[Complex<f64>])
+where
+    M: SingleGateApplicator + DoubleGateApplicator,
+{
+    let n = qubits.len();
+    assert_eq!(
+        amplitudes.len(),
+        1 << n,
+        "{} amplitudes are needed for {} qubits",
+        1 << n,
+        n
+    );
+    let norm = amplitudes.iter().map(|x| x.norm_sqr()).sum::<f64>();
+    assert!(
+        (norm - 1.).abs() <= 1e-8,
+        "the amplitudes are not normalized"
+    );
+
+    // The phase of the largest amplitude is global, so that it does not spoil the real ones.
+    let largest = amplitudes
+        .iter()
+        .cloned()
+        .max_by(|x, y| x.norm().partial_cmp(&y.norm()).unwrap())
+        .unwrap();
+    let rotation = largest.conj() / largest.norm();
+    let amplitudes: Vec<_> = amplitudes.iter().map(|x| x * rotation).collect();
+    let is_real = amplitudes.iter().all(|x| x.im.abs() <= 1e-12);
+
+    // The controls of the rotation on bit t are the higher bits, from the highest one.
+    let controls = |t: usize| qubits[t + 1..].iter().rev().cloned().collect::<Vec<_>>();
+
+    for t in (0..n).rev() {
+        let weight = |p: usize, bit: usize| {
+            let offset = (p << (t + 1)) | (bit << t);
+            (offset..offset + (1 << t))
+                .map(|x| amplitudes[x].norm_sqr())
+                .sum::<f64>()
+                .sqrt()
+        };
+        let angles = (0..1 << (n - 1 - t))
+            .map(|p| {
+                let (zero, one) = if is_real && t == 0 {
+                    (amplitudes[2 * p].re, amplitudes[2 * p + 1].re)
+                } else {
+                    (weight(p, 0), weight(p, 1))
+                };
+                if zero.abs() <= 1e-12 && one.abs() <= 1e-12 {
+                    None
+                } else {
+                    Some(2. * one.atan2(zero))
+                }
+            })
+            .collect();
+        multiplex(machine, angles, &controls(t), &qubits[t], multiplexor::ry);
+    }
+
+    if is_real {
+        return;
+    }
+
+    // diag(e^{i w_0}, e^{i w_1}) = e^{i(w_0 + w_1)/2} Rz(w_1 - w_0), where the phases of the
+    // vanishing amplitudes are arbitrary.
+    let mut phases: Vec<_> = amplitudes
+        .iter()
+        .map(|x| {
+            if x.norm() <= 1e-12 {
+                None
+            } else {
+                Some(x.arg())
+            }
+        })
+        .collect();
+    for (t, qubit) in qubits.iter().enumerate() {
+        let (angles, rest) = phases
+            .chunks(2)
+            .map(|pair| match (pair[0], pair[1]) {
+                (Some(w0), Some(w1)) => (Some(w1 - w0), Some((w0 + w1) / 2.)),
+                (w0, w1) => (None, w0.or(w1)),
+            })
+            .unzip();
+        multiplex(machine, angles, &controls(t), qubit, multiplexor::rz);
+        phases = rest;
+    }
+}
+
+///
+/// Applies the multiplexed rotation by `angles`, where `None` is an arbitrary angle, after
+/// removing the controls on which the angles do not depend.
+///
+fn multiplex<M, F>(
+    machine: &mut M,
+    mut angles: Vec<Option<f64>>,
+    controls: &[Qubit],
+    target: &Qubit,
+    rotate: F,
+) where
+    F: Fn(&mut M, &[f64], &[Qubit], &Qubit),
+{
+    let same = |x: Option<f64>, y: Option<f64>| match (x, y) {
+        (Some(x), Some(y)) => (x - y).abs() <= 1e-12,
+        _ => true,
+    };
+
+    let mut controls = controls.to_vec();
+    let mut j = 0;
+    while j < controls.len() {
+        let bit = 1 << (controls.len() - 1 - j);
+        let lower = (0..angles.len()).filter(|p| p & bit == 0);
+        if lower.clone().all(|p| same(angles[p], angles[p | bit])) {
+            angles = lower.map(|p| angles[p].or(angles[p | bit])).collect();
+            controls.remove(j);
+        } else {
+            j += 1;
+        }
+    }
+
+    let angles: Vec<_> = angles.iter().map(|x| x.unwrap_or(0.)).collect();
+    rotate(machine, &angles, &controls, target);
+}
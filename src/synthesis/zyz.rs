@@ -0,0 +1,122 @@
+//!
+//! A module for the decomposition of single-qubit unitaries into rotations.
+//!
+
+use circuit::Circuit;
+use error::RusqError;
+use gates::single::SingleGateApplicator;
+use ndarray::prelude::*;
+use num::complex::Complex;
+use simulator::simulator::check_matrix;
+use std::f64::consts::PI;
+use Qubit;
+
+///
+/// A type for the Euler angles of a single-qubit unitary,
+/// $U = e^{i\alpha} R_z(\phi) R_y(\theta) R_z(\lambda)$.
+///
+/// # Examples
+///
+/// ```
+/// use rusq::prelude::*;
+/// use rusq::circuit::Circuit;
+/// use rusq::gates::single::H;
+/// use rusq::simulator::unitary;
+/// use rusq::synthesis::zyz::ZyzDecomposition;
+///
+/// let zyz = ZyzDecomposition::new(&H.matrix).unwrap();
+/// assert!((zyz.theta - std::f64::consts::FRAC_PI_2).abs() < 1e-10);
+///
+/// let mut h = Circuit::new(1, 0);
+/// let qubit = h.get_qubits()[0];
+/// h.H(&qubit);
+/// assert!(unitary::circuits_equivalent(&zyz.to_circuit(), &h, 1e-10));
+/// ```
+///
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ZyzDecomposition {
+    /// The global phase $\alpha$
+    pub phase: f64,
+    /// The angle $\phi$ of the last rotation around the Z axis
+    pub phi: f64,
+    /// The angle $\theta$ of the rotation around the Y axis
+    pub theta: f64,
+    /// The angle $\lambda$ of the first rotation around the Z axis
+    pub lambda: f64,
+}
+
+impl ZyzDecomposition {
+    ///
+    /// Decomposes the 2x2 unitary `matrix`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `matrix` is not a 2x2 unitary matrix within `1e-8`.
+    ///
+    pub fn new(matrix: &Array2<Complex<f64>>) -> Result<ZyzDecomposition, RusqError> {
+        check_matrix(1, matrix, Some(1e-8))?;
+
+        let det = matrix[[0, 0]] * matrix[[1, 1]] - matrix[[0, 1]] * matrix[[1, 0]];
+        let phase = det.arg() / 2.;
+        // The special unitary [[a*, -b*], [b, a]]
+        let rotation = Complex::from_polar(&1., &-phase);
+        let (a, b) = (matrix[[1, 1]] * rotation, matrix[[1, 0]] * rotation);
+
+        let theta = 2. * b.norm().atan2(a.norm());
+        let (sum, difference) = (2. * a.arg(), 2. * b.arg());
+        // Only the sum or the difference of the angles matters if an entry vanishes.
+        let (phi, lambda) = if b.norm() <= 1e-12 {
+            (sum, 0.)
+        } else if a.norm() <= 1e-12 {
+            (difference, 0.)
+        } else {
+            ((sum + difference) / 2., (sum - difference) / 2.)
+        };
+
+        // Rz(x + 2 pi) = -Rz(x) moves the angles into (-pi, pi].
+        let turns = |x: f64| ((x - PI) / (2. * PI)).ceil();
+        let (phi_turns, lambda_turns) = (turns(phi), turns(lambda));
+        Ok(ZyzDecomposition {
+            phase: phase + PI * (phi_turns + lambda_turns),
+            phi: phi - 2. * PI * phi_turns,
+            theta,
+            lambda: lambda - 2. * PI * lambda_turns,
+        })
+    }
+
+    ///
+    /// Returns the global phase $\alpha'$ such that
+    /// $U = e^{i\alpha'} U_3(\theta, \phi, \lambda)$, where
+    /// $U_3(\theta, \phi, \lambda) = e^{i(\phi + \lambda)/2} R_z(\phi) R_y(\theta) R_z(\lambda)$.
+    ///
+    pub fn u3_phase(&self) -> f64 {
+        self.phase - (self.phi + self.lambda) / 2.
+    }
+
+    ///
+    /// Applies the rotations to `qubit` of `machine`, which equal the unitary up to the
+    /// global phase.
+    ///
+    /// The rotations with vanishing angles are omitted.
+    ///
+    pub fn apply<M: SingleGateApplicator>(&self, machine: &mut M, qubit: &Qubit) {
+        for &(angle, is_z) in &[(self.lambda, true), (self.theta, false), (self.phi, true)] {
+            if angle.abs() <= 1e-12 {
+                continue;
+            }
+            if is_z {
+                machine.rz(angle, qubit);
+            } else {
+                machine.ry(angle, qubit);
+            }
+        }
+    }
+
+    /// Returns the rotations as a circuit on a single qubit.
+    pub fn to_circuit(&self) -> Circuit {
+        let mut circuit = Circuit::new(1, 0);
+        let qubit = circuit.get_qubits()[0];
+        self.apply(&mut circuit, &qubit);
+        circuit
+    }
+}
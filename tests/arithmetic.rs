@@ -0,0 +1,243 @@
+extern crate rusq;
+
+use rusq::arithmetic::{adder, comparator, multiplier};
+use rusq::prelude::*;
+
+// Runs the operation for all the integers in the input registers and checks the outputs,
+// like `logic!` in lib.rs for the registers.
+macro_rules! truth_table {
+    ( $sim: ident, ($($input: ident in $register: ident),+) => $op: block => { $($output: ident == $expected: expr),+ } ) => {
+        {
+            let total = 0 $(+ $register.len())+;
+
+            for case in 0..(1usize << total) {
+                let mut shift = 0;
+                $(
+                    let $input = (case >> shift) & ((1 << $register.len()) - 1);
+                    shift += $register.len();
+                )+
+                let _ = shift;
+                let inputs = ($($input),+);
+
+                $sim.get_register().set_integer(&mut $sim, 0, Endianness::Little);
+                $($register.set_integer(&mut $sim, $input, Endianness::Little);)+
+
+                $op
+
+                $(
+                    assert_eq!(
+                        $output.measure_integer(&mut $sim, Endianness::Little),
+                        $expected,
+                        "{} for {:?}",
+                        stringify!($output),
+                        inputs
+                    );
+                )+
+            }
+        }
+    };
+}
+
+fn registers(sim: &QuantumSimulator, lengths: &[usize]) -> Vec<QubitRegister> {
+    let register = sim.get_register();
+    let mut start = 0;
+    lengths
+        .iter()
+        .map(|&len| {
+            start += len;
+            register.slice(start - len..start)
+        })
+        .collect()
+}
+
+#[test]
+fn ripple_add() {
+    let mut sim = QuantumSimulator::new(8);
+    let r = registers(&sim, &[3, 3, 1, 1]);
+    let (a, b, ancilla, carry) = (&r[0], &r[1], &r[2], &r[3]);
+
+    truth_table!(sim, (x in a, y in b) => {
+        adder::ripple_add(&mut sim, a, b, &ancilla[0], Some(&carry[0]));
+    } => {
+        a == x,
+        b == (x + y) % 8,
+        carry == (x + y) / 8,
+        ancilla == 0
+    });
+
+    truth_table!(sim, (x in a, y in b) => {
+        adder::ripple_add(&mut sim, a, b, &ancilla[0], None);
+    } => {
+        b == (x + y) % 8,
+        carry == 0
+    });
+}
+
+#[test]
+fn ripple_subtract() {
+    let mut sim = QuantumSimulator::new(8);
+    let r = registers(&sim, &[3, 3, 1, 1]);
+    let (a, b, ancilla, borrow) = (&r[0], &r[1], &r[2], &r[3]);
+
+    truth_table!(sim, (x in a, y in b) => {
+        adder::ripple_subtract(&mut sim, a, b, &ancilla[0], Some(&borrow[0]));
+    } => {
+        a == x,
+        b == (8 + y - x) % 8,
+        borrow == (x > y) as usize,
+        ancilla == 0
+    });
+}
+
+#[test]
+fn draper_add() {
+    let mut sim = QuantumSimulator::new(6);
+    let r = registers(&sim, &[2, 4]);
+    let (a, b) = (&r[0], &r[1]);
+
+    truth_table!(sim, (x in a, y in b) => {
+        adder::draper_add(&mut sim, a, b);
+    } => {
+        a == x,
+        b == (x + y) % 16
+    });
+
+    truth_table!(sim, (x in a, y in b) => {
+        adder::draper_subtract(&mut sim, a, b);
+    } => {
+        a == x,
+        b == (16 + y - x) % 16
+    });
+}
+
+#[test]
+fn add_constant() {
+    let mut sim = QuantumSimulator::new(6);
+    let r = registers(&sim, &[3, 3]);
+    let (b, controls) = (&r[0], &r[1]);
+
+    for value in -9i64..10 {
+        for n_controls in 0..4 {
+            let controls = controls.slice(0..n_controls);
+            truth_table!(sim, (y in b, c in controls) => {
+                adder::add_constant(&mut sim, value, b, &controls);
+            } => {
+                b == if c + 1 == 1 << n_controls {
+                    (y as i64 + value).rem_euclid(8) as usize
+                } else {
+                    y
+                },
+                controls == c
+            });
+        }
+    }
+}
+
+#[test]
+fn increment() {
+    let mut sim = QuantumSimulator::new(6);
+    let r = registers(&sim, &[4, 2]);
+    let (register, controls) = (&r[0], &r[1]);
+
+    truth_table!(sim, (x in register) => {
+        adder::increment(&mut sim, register, &[]);
+    } => {
+        register == (x + 1) % 16
+    });
+
+    truth_table!(sim, (x in register, c in controls) => {
+        adder::increment(&mut sim, register, controls);
+    } => {
+        register == if c == 3 { (x + 1) % 16 } else { x },
+        controls == c
+    });
+
+    truth_table!(sim, (x in register, c in controls) => {
+        adder::decrement(&mut sim, register, controls);
+    } => {
+        register == if c == 3 { (x + 15) % 16 } else { x },
+        controls == c
+    });
+}
+
+#[test]
+fn compare() {
+    let mut sim = QuantumSimulator::new(8);
+    let r = registers(&sim, &[3, 3, 1, 1]);
+    let (a, b, result, ancilla) = (&r[0], &r[1], &r[2], &r[3]);
+
+    truth_table!(sim, (x in a, y in b, z in result) => {
+        comparator::greater_than(&mut sim, a, b, &result[0], &ancilla[0]);
+    } => {
+        a == x,
+        b == y,
+        result == z ^ (x > y) as usize,
+        ancilla == 0
+    });
+
+    truth_table!(sim, (x in a, y in b) => {
+        comparator::less_than(&mut sim, a, b, &result[0], &ancilla[0]);
+    } => {
+        a == x,
+        b == y,
+        result == (x < y) as usize,
+        ancilla == 0
+    });
+}
+
+#[test]
+fn less_than_constant() {
+    let mut sim = QuantumSimulator::new(5);
+    let r = registers(&sim, &[3, 1, 1]);
+    let (a, result, ancilla) = (&r[0], &r[1], &r[2]);
+
+    for value in 0..9 {
+        truth_table!(sim, (x in a) => {
+            comparator::less_than_constant(&mut sim, a, value as u64, &result[0], &ancilla[0]);
+        } => {
+            a == x,
+            result == (x < value) as usize,
+            ancilla == 0
+        });
+    }
+}
+
+#[test]
+#[should_panic]
+fn less_than_too_large_constant() {
+    let mut sim = QuantumSimulator::new(5);
+    let r = registers(&sim, &[3, 1, 1]);
+    comparator::less_than_constant(&mut sim, &r[0], 9, &r[1][0], &r[2][0]);
+}
+
+#[test]
+fn multiply() {
+    let mut sim = QuantumSimulator::new(8);
+    let r = registers(&sim, &[2, 2, 4]);
+    let (a, b, product) = (&r[0], &r[1], &r[2]);
+
+    truth_table!(sim, (x in a, y in b, z in product) => {
+        multiplier::multiply(&mut sim, a, b, product);
+    } => {
+        a == x,
+        b == y,
+        product == (z + x * y) % 16
+    });
+}
+
+#[test]
+fn multiply_constant() {
+    let mut sim = QuantumSimulator::new(7);
+    let r = registers(&sim, &[3, 3, 1]);
+    let (x, product, control) = (&r[0], &r[1], &r[2]);
+
+    for value in 0..8 {
+        truth_table!(sim, (y in x, c in control) => {
+            multiplier::multiply_constant(&mut sim, value as u64, x, product, control);
+        } => {
+            x == y,
+            product == c * value * y % 8,
+            control == c
+        });
+    }
+}
@@ -0,0 +1,155 @@
+extern crate rusq;
+
+use rusq::prelude::*;
+use rusq::simulator::Snapshot;
+use std::fs;
+use std::io::{ErrorKind, Read};
+
+fn set(sim: &mut QuantumSimulator, qubit: &Qubit, r: MeasuredResult) {
+    if sim.measure(qubit) != r {
+        sim.X(qubit);
+    }
+}
+
+#[test]
+fn snapshot_and_restore() {
+    let mut sim = QuantumSimulator::new(2);
+    let qubits = sim.get_qubits();
+    let measure_count = 1000;
+    let mut count_zero = 0;
+
+    set(&mut sim, &qubits[0], MeasuredResult::Zero);
+    set(&mut sim, &qubits[1], MeasuredResult::One);
+    sim.H(&qubits[0]);
+    let snapshot = sim.snapshot();
+    assert_eq!(snapshot.qubits_len(), 2);
+
+    for _ in 0..measure_count {
+        sim.restore(&snapshot);
+        assert_eq!(sim.measure(&qubits[1]), MeasuredResult::One);
+        if sim.measure(&qubits[0]) == MeasuredResult::Zero {
+            count_zero += 1;
+        }
+    }
+
+    // 5 sigma
+    assert!(count_zero > 420 && 580 > count_zero);
+}
+
+#[test]
+#[should_panic]
+fn restore_wrong_size() {
+    let snapshot = QuantumSimulator::new(2).snapshot();
+    let mut sim = QuantumSimulator::new(3);
+    sim.restore(&snapshot);
+}
+
+#[test]
+fn save_and_load() {
+    let path = std::env::temp_dir().join("rusq_test_save_and_load.rusq");
+    let mut sim = QuantumSimulator::new(3);
+    let qubits = sim.get_qubits();
+
+    sim.H(&qubits[0]);
+    sim.CNOT(&qubits[0], &qubits[2]);
+    sim.X(&qubits[1]);
+    sim.save(&path).unwrap();
+
+    let loaded = QuantumSimulator::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.snapshot(), sim.snapshot());
+
+    let mut loaded = loaded;
+    let qubits = loaded.get_qubits();
+    assert_eq!(qubits.len(), 3);
+    assert_eq!(loaded.measure(&qubits[1]), MeasuredResult::One);
+    assert_eq!(loaded.measure(&qubits[0]), loaded.measure(&qubits[2]));
+}
+
+#[test]
+fn load_single_precision() {
+    let mut bytes = b"RUSQ\x01\x00\x04\x00".to_vec();
+    bytes.extend_from_slice(&1u64.to_le_bytes());
+    bytes.extend_from_slice(&0f32.to_le_bytes());
+    bytes.extend_from_slice(&0f32.to_le_bytes());
+    bytes.extend_from_slice(&1f32.to_le_bytes());
+    bytes.extend_from_slice(&0f32.to_le_bytes());
+
+    let snapshot = Snapshot::read_from(&bytes[..]).unwrap();
+    let mut sim = QuantumSimulator::from_snapshot(snapshot);
+    let qubit = &sim.get_qubits()[0];
+    assert_eq!(sim.measure(qubit), MeasuredResult::One);
+}
+
+#[test]
+fn load_invalid() {
+    assert!(Snapshot::read_from(&b"NOTRUSQ_________"[..]).is_err());
+    assert!(
+        Snapshot::read_from(&b"RUSQ\x02\x00\x08\x00\x01\x00\x00\x00\x00\x00\x00\x00"[..]).is_err()
+    );
+    assert!(
+        Snapshot::read_from(&b"RUSQ\x01\x00\x02\x00\x01\x00\x00\x00\x00\x00\x00\x00"[..]).is_err()
+    );
+    // Truncated amplitudes
+    assert!(
+        Snapshot::read_from(&b"RUSQ\x01\x00\x08\x00\x01\x00\x00\x00\x00\x00\x00\x00"[..]).is_err()
+    );
+
+    // Non-zero reserved byte
+    let mut bytes = b"RUSQ\x01\x00\x08\x01".to_vec();
+    bytes.extend_from_slice(&0u64.to_le_bytes());
+    bytes.extend_from_slice(&1f64.to_le_bytes());
+    bytes.extend_from_slice(&0f64.to_le_bytes());
+    assert_eq!(
+        Snapshot::read_from(&bytes[..]).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+
+    // Too many qubits are rejected before allocating the amplitudes.
+    for &n in &[33u64, 63, u64::MAX] {
+        let mut bytes = b"RUSQ\x01\x00\x08\x00".to_vec();
+        bytes.extend_from_slice(&n.to_le_bytes());
+        assert_eq!(
+            Snapshot::read_from(&bytes[..]).unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+    }
+
+    // A truncated file with many qubits fails without allocating all of them.
+    let mut bytes = b"RUSQ\x01\x00\x08\x00".to_vec();
+    bytes.extend_from_slice(&32u64.to_le_bytes());
+    assert_eq!(
+        Snapshot::read_from(&bytes[..]).unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+}
+
+#[test]
+fn export_npy() {
+    let path = std::env::temp_dir().join("rusq_test_export.npy");
+    let mut sim = QuantumSimulator::new(2);
+    let qubits = sim.get_qubits();
+    sim.X(&qubits[0]);
+    sim.export_npy(&path).unwrap();
+
+    let mut bytes = vec![];
+    fs::File::open(&path)
+        .unwrap()
+        .read_to_end(&mut bytes)
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(&bytes[0..8], b"\x93NUMPY\x01\x00");
+    let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+    assert_eq!((10 + header_len) % 64, 0);
+
+    let header = std::str::from_utf8(&bytes[10..10 + header_len]).unwrap();
+    assert!(header.starts_with("{'descr': '<c16', 'fortran_order': False, 'shape': (4,), }"));
+    assert!(header.ends_with('\n'));
+
+    let data = &bytes[10 + header_len..];
+    assert_eq!(data.len(), 4 * 16);
+    let mut re = [0u8; 8];
+    re.copy_from_slice(&data[16..24]);
+    assert_eq!(f64::from_le_bytes(re), 1.);
+}
@@ -0,0 +1,389 @@
+extern crate rusq;
+
+use rusq::circuit::{latex, svg, text, Circuit, Condition, Gate, Operation};
+use rusq::gates::double::CNOT;
+use rusq::gates::single::SQNOT;
+use rusq::prelude::*;
+use std::f64::consts::PI;
+
+fn set(sim: &mut QuantumSimulator, qubit: &Qubit, r: MeasuredResult) {
+    if sim.measure(qubit) != r {
+        sim.X(qubit);
+    }
+}
+
+fn teleportation() -> Circuit {
+    let mut circuit = Circuit::new(3, 2);
+    let qubits = circuit.get_qubits();
+    let clbits = circuit.get_clbits();
+
+    circuit.H(&qubits[1]);
+    circuit.CNOT(&qubits[1], &qubits[2]);
+    circuit.CNOT(&qubits[0], &qubits[1]);
+    circuit.H(&qubits[0]);
+    circuit.measure(&qubits[0], &clbits[0]);
+    circuit.measure(&qubits[1], &clbits[1]);
+    circuit.c_if(Condition::Bit(clbits[1], MeasuredResult::One), |c| {
+        c.X(&qubits[2])
+    });
+    circuit.c_if(Condition::Bit(clbits[0], MeasuredResult::One), |c| {
+        c.Z(&qubits[2])
+    });
+
+    circuit
+}
+
+#[test]
+fn record_gates() {
+    let mut circuit = Circuit::new(3, 0);
+    let qubits = circuit.get_qubits();
+
+    circuit.H(&qubits[0]);
+    circuit.phase(0.5, &qubits[1]);
+    circuit.CNOT(&qubits[0], &qubits[2]);
+    circuit.CCNOT(&qubits[2], &qubits[1], &qubits[0]);
+
+    let gates = circuit
+        .instructions()
+        .iter()
+        .map(|i| {
+            assert_eq!(i.condition, None);
+            match i.operation {
+                Operation::Gate(ref g) => g.clone(),
+                _ => panic!("unexpected operation"),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        gates,
+        vec![
+            Gate::H(qubits[0]),
+            Gate::Phase(0.5, qubits[1]),
+            Gate::CNOT(qubits[0], qubits[2]),
+            Gate::CCNOT(qubits[2], qubits[1], qubits[0]),
+        ]
+    );
+}
+
+#[test]
+fn run_teleportation() {
+    let circuit = teleportation();
+    let mut sim = QuantumSimulator::new(3);
+    let qubits = sim.get_qubits();
+    let phi = 1.2;
+
+    for _ in 0..100 {
+        for q in &qubits {
+            set(&mut sim, q, MeasuredResult::Zero);
+        }
+
+        // Prepares an arbitrary state, teleports it and undoes the preparation.
+        sim.H(&qubits[0]);
+        sim.phase(phi, &qubits[0]);
+        sim.H(&qubits[0]);
+
+        circuit.run(&mut sim);
+
+        sim.H(&qubits[2]);
+        sim.phase(-phi, &qubits[2]);
+        sim.H(&qubits[2]);
+        assert_eq!(sim.measure(&qubits[2]), MeasuredResult::Zero);
+    }
+}
+
+#[test]
+fn run_on_given_qubits() {
+    let mut circuit = Circuit::new(2, 2);
+    let qubits = circuit.get_qubits();
+    let clbits = circuit.get_clbits();
+    circuit.X(&qubits[1]);
+    circuit.CNOT(&qubits[1], &qubits[0]);
+    circuit.measure(&qubits[0], &clbits[0]);
+    circuit.measure(&qubits[1], &clbits[1]);
+
+    let mut sim = QuantumSimulator::new(5);
+    let targets = sim.get_qubits();
+    for q in &targets {
+        set(&mut sim, q, MeasuredResult::Zero);
+    }
+
+    let bits = circuit.run_on(&mut sim, &[targets[4], targets[2]]);
+    assert_eq!(bits.as_slice(), &[MeasuredResult::One, MeasuredResult::One]);
+    assert_eq!(bits.value(&clbits, Endianness::Little), 3);
+    assert_eq!(sim.measure(&targets[0]), MeasuredResult::Zero);
+    assert_eq!(sim.measure(&targets[4]), MeasuredResult::One);
+    assert_eq!(sim.measure(&targets[2]), MeasuredResult::One);
+}
+
+#[test]
+fn register_condition() {
+    let mut circuit = Circuit::new(3, 2);
+    let qubits = circuit.get_qubits();
+    let clbits = circuit.get_clbits();
+
+    circuit.X(&qubits[1]);
+    circuit.measure(&qubits[0], &clbits[0]);
+    circuit.measure(&qubits[1], &clbits[1]);
+    circuit.c_if(Condition::Register(clbits.clone(), 0b10), |c| {
+        c.X(&qubits[2]);
+    });
+    circuit.c_if(Condition::Register(clbits.clone(), 0b01), |c| {
+        c.X(&qubits[2]);
+        c.X(&qubits[2]);
+        c.X(&qubits[2]);
+    });
+    assert!(circuit.instructions()[4..]
+        .iter()
+        .all(|i| i.condition.is_some()));
+
+    let mut sim = QuantumSimulator::new(3);
+    let targets = sim.get_qubits();
+    for q in &targets {
+        set(&mut sim, q, MeasuredResult::Zero);
+    }
+
+    let bits = circuit.run(&mut sim);
+    assert_eq!(bits.get(&clbits[0]), MeasuredResult::Zero);
+    assert_eq!(bits.get(&clbits[1]), MeasuredResult::One);
+    assert_eq!(sim.measure(&targets[2]), MeasuredResult::One);
+}
+
+#[test]
+#[should_panic(expected = "belongs to another machine")]
+fn reject_foreign_qubit() {
+    let mut circuit = Circuit::new(1, 0);
+    let qubits = QuantumSimulator::new(1).get_qubits();
+    circuit.H(&qubits[0]);
+}
+
+#[test]
+#[should_panic(expected = "belongs to another circuit")]
+fn reject_foreign_clbit() {
+    let mut circuit = Circuit::new(1, 1);
+    let qubits = circuit.get_qubits();
+    let clbits = Circuit::new(1, 1).get_clbits();
+    circuit.measure(&qubits[0], &clbits[0]);
+}
+
+#[test]
+fn draw_teleportation() {
+    let circuit = teleportation();
+
+    assert_eq!(
+        circuit.to_string(),
+        [
+            "q0: ──────────●──┤H├──┤M├─────────────────",
+            "              │        ║",
+            "q1: ──┤H├──●──⊕────────╫───┤M├────────────",
+            "           │           ║    ║",
+            "q2: ───────⊕───────────╫────╫───┤X├──┤Z├──",
+            "                       ║    ║    ║    ║",
+            "c0: ═══════════════════╩════╬════╬════■═══",
+            "                            ║    ║",
+            "c1: ════════════════════════╩════■════════",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn draw_gates() {
+    let mut circuit = Circuit::new(3, 0);
+    let qubits = circuit.get_qubits();
+
+    circuit.phase(PI / 4., &qubits[0]);
+    circuit.CCNOT(&qubits[2], &qubits[0], &qubits[1]);
+    circuit.CSWAP(&qubits[1], &qubits[0], &qubits[2]);
+    circuit.cphase(-PI / 2., &qubits[0], &qubits[2]);
+
+    assert_eq!(
+        circuit.to_string(),
+        [
+            "q0: ──┤P(π/4)├───●──×──────●──────",
+            "                 │  │      │",
+            "q1: ─────────────⊕──●──────┼──────",
+            "                 │  │      │",
+            "q2: ─────────────●──×──┤P(-π/2)├──",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn draw_rotations() {
+    let mut circuit = Circuit::new(2, 0);
+    let qubits = circuit.get_qubits();
+
+    circuit.rx(PI / 2., &qubits[0]);
+    circuit.ry(-PI, &qubits[1]);
+    circuit.rz(0.5, &qubits[0]);
+
+    assert_eq!(
+        circuit.to_string(),
+        [
+            "q0: ──┤Rx(π/2)├──┤Rz(0.5000)├───",
+            "",
+            "q1: ──┤Ry(-π)├──────────────────",
+        ]
+        .join("\n")
+    );
+    assert_eq!(
+        circuit.instructions()[1].operation,
+        Operation::Gate(Gate::RY(-PI, qubits[1]))
+    );
+    assert!(latex::quantikz(&circuit).contains(r"\gate{R_x(\pi/2)}"));
+}
+
+#[test]
+fn draw_with_width() {
+    let mut circuit = Circuit::new(2, 0);
+    let qubits = circuit.get_qubits();
+
+    for _ in 0..3 {
+        circuit.H(&qubits[0]);
+        circuit.SWAP(&qubits[0], &qubits[1]);
+    }
+
+    assert_eq!(
+        text::draw(&circuit, Some(22)),
+        [
+            "q0: ──┤H├──×──┤H├──×─»",
+            "           │       │",
+            "q1: ───────×───────×─»",
+            "",
+            "q0: «─┤H├──×──",
+            "           │",
+            "q1: «──────×──",
+        ]
+        .join("\n")
+    );
+    assert_eq!(text::draw(&circuit, None), circuit.to_string());
+}
+
+#[test]
+fn draw_unitaries() {
+    let mut circuit = Circuit::new(3, 0);
+    let qubits = circuit.get_qubits();
+
+    circuit.apply_double(&CNOT.matrix, &qubits[2], &qubits[0]);
+
+    assert_eq!(
+        circuit.to_string(),
+        [
+            "q0: ──┤U[1]├───",
+            "         │",
+            "q1: ─────┼─────",
+            "         │",
+            "q2: ──┤U[0]├───",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn quantikz_teleportation() {
+    let circuit = teleportation();
+
+    assert_eq!(
+        latex::quantikz(&circuit),
+        [
+            r"\begin{quantikz}",
+            r"\lstick{$q_{0}$} & & & \ctrl{1} & \gate{H} & \meter{} \wire[d][3]{c} & & & & \\",
+            r"\lstick{$q_{1}$} & \gate{H} & \ctrl{1} & \targ{} & & & \meter{} \wire[d][3]{c} & & & \\",
+            r"\lstick{$q_{2}$} & & \targ{} & & & & & \gate{X} \wire[d][2]{c} & \gate{Z} \wire[d][1]{c} & \\",
+            r"\lstick{$c_{0}$} \setwiretype{c} & & & & & & & & \control{} & \\",
+            r"\lstick{$c_{1}$} \setwiretype{c} & & & & & & & \control{} & &",
+            r"\end{quantikz}",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn quantikz_gates() {
+    let mut circuit = Circuit::new(3, 0);
+    let qubits = circuit.get_qubits();
+
+    circuit.cphase(PI / 2., &qubits[2], &qubits[0]);
+    circuit.SQSWAP(&qubits[0], &qubits[1]);
+    circuit.CSWAP(&qubits[2], &qubits[0], &qubits[1]);
+    circuit.push_gate(Gate::Single(SQNOT.matrix.clone(), qubits[2]));
+
+    assert_eq!(
+        latex::quantikz(&circuit),
+        [
+            r"\begin{quantikz}",
+            r"\lstick{$q_{0}$} & \gate{P(\pi/2)} \wire[d][2]{q} & \gate{\sqrt{\mathrm{SWAP}}} \wire[d][1]{q} & \swap{2} & & \\",
+            r"\lstick{$q_{1}$} & & \gate{\sqrt{\mathrm{SWAP}}} & \targX{} & & \\",
+            r"\lstick{$q_{2}$} & \control{} & & \control{} & \gate{\sqrt{\mathrm{NOT}}} &",
+            r"\end{quantikz}",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn svg_teleportation() {
+    let image = svg::svg(&teleportation());
+
+    assert!(image.starts_with("<svg"));
+    assert!(image.ends_with("</svg>\n"));
+    for label in &["q0", "q2", "c1", ">H<", ">X<", ">Z<"] {
+        assert!(image.contains(label), "missing {}", label);
+    }
+    // Two meters and two written bits.
+    assert_eq!(image.matches("<path").count(), 2);
+    assert_eq!(image.matches("<polygon").count(), 2);
+}
+
+#[test]
+fn inverse() {
+    let mut circuit = Circuit::new(3, 0);
+    let qubits = circuit.get_qubits();
+    circuit.H(&qubits[0]);
+    circuit.phase(0.4, &qubits[0]);
+    circuit.SQSWAP(&qubits[0], &qubits[1]);
+    circuit.cphase(1.1, &qubits[1], &qubits[2]);
+    circuit.push_gate(Gate::Single(SQNOT.matrix.clone(), qubits[2]));
+    circuit.CSWAP(&qubits[2], &qubits[0], &qubits[1]);
+    circuit.rx(0.7, &qubits[0]);
+    circuit.ry(-1.3, &qubits[1]);
+    circuit.rz(2.1, &qubits[2]);
+
+    let inverse = circuit.inverse();
+    assert_eq!(inverse.instructions().len(), circuit.instructions().len());
+    assert_eq!(
+        inverse.instructions()[0].operation,
+        Operation::Gate(Gate::RZ(-2.1, qubits[2]))
+    );
+    assert_eq!(
+        inverse.instructions()[7].operation,
+        Operation::Gate(Gate::Phase(-0.4, qubits[0]))
+    );
+
+    let mut sim = QuantumSimulator::new(3);
+    let targets = sim.get_qubits();
+    sim.H(&targets[2]);
+    let initial = sim.snapshot();
+    circuit.run(&mut sim);
+    inverse.run(&mut sim);
+    for (a, b) in sim
+        .snapshot()
+        .amplitudes()
+        .iter()
+        .zip(initial.amplitudes())
+    {
+        assert!((a - b).norm() < 1e-10);
+    }
+}
+
+#[test]
+#[should_panic(expected = "cannot be inverted")]
+fn inverse_with_measurement() {
+    let mut circuit = Circuit::new(1, 1);
+    let qubits = circuit.get_qubits();
+    let clbits = circuit.get_clbits();
+    circuit.measure(&qubits[0], &clbits[0]);
+    circuit.inverse();
+}
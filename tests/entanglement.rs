@@ -0,0 +1,199 @@
+#![cfg(feature = "entanglement")]
+
+extern crate num;
+extern crate rusq;
+
+use num::complex::Complex;
+use rusq::entanglement::{self, SchmidtDecomposition};
+use rusq::prelude::*;
+use std::f64::consts::PI;
+
+const EPS: f64 = 1e-10;
+
+fn bell() -> (QuantumSimulator, Vec<Qubit>) {
+    let mut sim = QuantumSimulator::new(2);
+    let qubits = sim.get_qubits();
+    sim.H(&qubits[0]);
+    sim.CNOT(&qubits[0], &qubits[1]);
+    (sim, qubits)
+}
+
+#[test]
+fn partial_trace() {
+    // |0> on the qubit 0, |+> on the qubit 1 and |1> on the qubit 2
+    let mut sim = QuantumSimulator::new(3);
+    let qubits = sim.get_qubits();
+    sim.H(&qubits[1]);
+    sim.X(&qubits[2]);
+
+    let rho = entanglement::reduced_density_matrix(&sim, &[qubits[2], qubits[1]]);
+    // The qubit 2 is bit 0 and the qubit 1 is bit 1.
+    for i in 0..4 {
+        for j in 0..4 {
+            let expected = if i & 1 == 1 && j & 1 == 1 { 0.5 } else { 0. };
+            assert!((rho[[i, j]] - Complex::new(expected, 0.)).norm() < EPS);
+        }
+    }
+    assert!((entanglement::purity(&rho) - 1.).abs() < EPS);
+    assert!(entanglement::von_neumann_entropy(&rho).abs() < EPS);
+
+    let (sim, qubits) = bell();
+    let rho = entanglement::reduced_density_matrix(&sim, &qubits[1..]);
+    assert!((rho[[0, 0]].re - 0.5).abs() < EPS && (rho[[1, 1]].re - 0.5).abs() < EPS);
+    assert!(rho[[0, 1]].norm() < EPS);
+}
+
+#[test]
+fn entropies() {
+    let mut sim = QuantumSimulator::new(3);
+    let qubits = sim.get_qubits();
+    sim.ry(1., &qubits[0]);
+    sim.CNOT(&qubits[0], &qubits[1]);
+    sim.H(&qubits[2]);
+
+    // The reduced state has the eigenvalues cos²(1/2) and sin²(1/2).
+    let rho = entanglement::reduced_density_matrix(&sim, &qubits[..1]);
+    let (p, q) = (0.5f64.cos().powi(2), 0.5f64.sin().powi(2));
+    let entropy = -p * p.log2() - q * q.log2();
+    assert!((entanglement::von_neumann_entropy(&rho) - entropy).abs() < EPS);
+    assert!((entanglement::entanglement_entropy(&sim, &qubits[1..2]) - entropy).abs() < EPS);
+    assert!((entanglement::purity(&rho) - (p * p + q * q)).abs() < EPS);
+
+    assert!((entanglement::renyi_entropy(&rho, 1.) - entropy).abs() < EPS);
+    let collision = -entanglement::purity(&rho).log2();
+    assert!((entanglement::renyi_entropy(&rho, 2.) - collision).abs() < EPS);
+    assert!((entanglement::renyi_entropy(&rho, 0.) - 1.).abs() < EPS);
+    let min = -p.max(q).log2();
+    assert!((entanglement::renyi_entropy(&rho, f64::INFINITY) - min).abs() < EPS);
+
+    // The qubit 2 is not entangled.
+    assert!(entanglement::entanglement_entropy(&sim, &qubits[2..]).abs() < EPS);
+}
+
+#[test]
+fn concurrence() {
+    let (sim, qubits) = bell();
+    let rho = entanglement::reduced_density_matrix(&sim, &qubits);
+    assert!((entanglement::concurrence(&rho) - 1.).abs() < EPS);
+
+    // cos(θ/2)|00> + sin(θ/2)|11> has the concurrence sin θ.
+    let mut sim = QuantumSimulator::new(2);
+    let qubits = sim.get_qubits();
+    sim.ry(0.7, &qubits[0]);
+    sim.CNOT(&qubits[0], &qubits[1]);
+    sim.rz(0.3, &qubits[1]);
+    let rho = entanglement::reduced_density_matrix(&sim, &qubits);
+    assert!((entanglement::concurrence(&rho) - 0.7f64.sin()).abs() < 1e-8);
+
+    // The pairs in the W state have the concurrence 2/3, and the ones in GHZ are separable.
+    let mut sim = QuantumSimulator::new(3);
+    let qubits = sim.get_qubits();
+    sim.ry(2. * (1f64 / 3.).sqrt().acos(), &qubits[0]);
+    // The controlled Ry(π/2) from the qubit 0 to 1
+    sim.ry(PI / 4., &qubits[1]);
+    sim.CNOT(&qubits[0], &qubits[1]);
+    sim.ry(-PI / 4., &qubits[1]);
+    sim.CNOT(&qubits[0], &qubits[1]);
+    // |00> -> |001>, |10> -> |100> and |11> -> |010>
+    sim.X(&qubits[0]);
+    sim.CNOT(&qubits[0], &qubits[2]);
+    sim.X(&qubits[0]);
+    sim.CNOT(&qubits[1], &qubits[0]);
+    let rho = entanglement::reduced_density_matrix(&sim, &qubits[..2]);
+    assert!((entanglement::concurrence(&rho) - 2. / 3.).abs() < 1e-8);
+
+    let mut sim = QuantumSimulator::new(3);
+    let qubits = sim.get_qubits();
+    sim.H(&qubits[0]);
+    sim.CNOT(&qubits[0], &qubits[1]);
+    sim.CNOT(&qubits[1], &qubits[2]);
+    let rho = entanglement::reduced_density_matrix(&sim, &qubits[1..]);
+    assert!(entanglement::concurrence(&rho).abs() < 1e-8);
+}
+
+fn reconstruct(schmidt: &SchmidtDecomposition, i: usize, j: usize) -> Complex<f64> {
+    schmidt
+        .coefficients
+        .iter()
+        .enumerate()
+        .fold(Complex::new(0., 0.), |sum, (k, &s)| {
+            sum + schmidt.left[[i, k]] * schmidt.right[[j, k]] * s
+        })
+}
+
+#[test]
+fn schmidt_decomposition() {
+    let mut sim = QuantumSimulator::new(3);
+    let qubits = sim.get_qubits();
+    sim.ry(0.4, &qubits[0]);
+    sim.H(&qubits[1]);
+    sim.CNOT(&qubits[0], &qubits[2]);
+    sim.rx(1.1, &qubits[2]);
+    sim.cphase(0.9, &qubits[1], &qubits[2]);
+    let amplitudes = sim.snapshot().amplitudes().to_vec();
+
+    let a = [qubits[2]];
+    let b = [qubits[1], qubits[0]];
+    let schmidt = entanglement::schmidt_decomposition(&sim, &a, &b);
+    assert_eq!(schmidt.coefficients.len(), 2);
+    assert!(schmidt.coefficients[0] >= schmidt.coefficients[1]);
+    let norm: f64 = schmidt.coefficients.iter().map(|s| s * s).sum();
+    assert!((norm - 1.).abs() < EPS);
+
+    for (index, amplitude) in amplitudes.iter().enumerate() {
+        let i = index >> 2 & 1;
+        let j = (index >> 1 & 1) | (index & 1) << 1;
+        assert!((reconstruct(&schmidt, i, j) - amplitude).norm() < EPS);
+    }
+
+    // The squared coefficients are the spectrum of the reduced state.
+    let rho = entanglement::reduced_density_matrix(&sim, &a);
+    let entropy = -schmidt
+        .coefficients
+        .iter()
+        .map(|s| s * s)
+        .filter(|&p| p > 0.)
+        .map(|p| p * p.log2())
+        .sum::<f64>();
+    assert!((entanglement::von_neumann_entropy(&rho) - entropy).abs() < EPS);
+
+    // A product state has the rank 1, and its states are still orthonormal.
+    let mut sim = QuantumSimulator::new(3);
+    let qubits = sim.get_qubits();
+    sim.H(&qubits[0]);
+    sim.ry(0.3, &qubits[2]);
+    let schmidt = entanglement::schmidt_decomposition(&sim, &qubits[..1], &qubits[1..]);
+    assert_eq!(schmidt.rank(1e-8), 1);
+    let gram = schmidt.right.t().mapv(|x| x.conj()).dot(&schmidt.right);
+    for i in 0..2 {
+        for j in 0..2 {
+            let expected = if i == j { 1. } else { 0. };
+            assert!((gram[[i, j]] - Complex::new(expected, 0.)).norm() < 1e-8);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn not_bipartition() {
+    let (sim, qubits) = bell();
+    entanglement::schmidt_decomposition(&sim, &qubits[..1], &qubits[..1]);
+}
+
+#[test]
+fn mutual_information() {
+    let (sim, qubits) = bell();
+    assert!((entanglement::mutual_information(&sim, &qubits[..1], &qubits[1..]) - 2.).abs() < EPS);
+
+    // The GHZ state has one bit of classical correlation between two qubits.
+    let mut sim = QuantumSimulator::new(4);
+    let qubits = sim.get_qubits();
+    sim.H(&qubits[0]);
+    sim.CNOT(&qubits[0], &qubits[1]);
+    sim.CNOT(&qubits[1], &qubits[2]);
+    sim.H(&qubits[3]);
+    let information = entanglement::mutual_information(&sim, &qubits[..1], &qubits[2..3]);
+    assert!((information - 1.).abs() < EPS);
+    let information = entanglement::mutual_information(&sim, &qubits[..2], &qubits[3..]);
+    assert!(information.abs() < EPS);
+}
@@ -0,0 +1,130 @@
+extern crate ndarray;
+extern crate num;
+extern crate rusq;
+
+use ndarray::prelude::*;
+use num::complex::Complex;
+use rusq::gates::single::X;
+use rusq::prelude::*;
+use rusq::RusqError;
+
+fn identity(size: usize) -> Array2<Complex<f64>> {
+    Array2::from_shape_fn((size, size), |(i, j)| {
+        Complex::new(if i == j { 1. } else { 0. }, 0.)
+    })
+}
+
+#[test]
+fn foreign_qubit() {
+    let mut sim = QuantumSimulator::new(3);
+    let other = QuantumSimulator::new(5);
+    let foreign = other.get_qubits();
+
+    assert_eq!(
+        sim.try_apply_single(&identity(2), &foreign[4]),
+        Err(RusqError::ForeignQubit { index: 4 })
+    );
+    assert_eq!(
+        sim.try_measure(&foreign[0]),
+        Err(RusqError::ForeignQubit { index: 0 })
+    );
+
+    let qubits = sim.get_qubits();
+    assert_eq!(
+        sim.try_apply_double(&identity(4), &qubits[0], &foreign[1]),
+        Err(RusqError::ForeignQubit { index: 1 })
+    );
+}
+
+#[test]
+fn loaded_simulator_is_another_machine() {
+    let sim = QuantumSimulator::new(1);
+    let qubit = &sim.get_qubits()[0];
+    let mut restored = QuantumSimulator::from_snapshot(sim.snapshot());
+
+    assert_eq!(
+        restored.try_measure(qubit),
+        Err(RusqError::ForeignQubit { index: 0 })
+    );
+}
+
+#[test]
+fn duplicate_qubit() {
+    let mut sim = QuantumSimulator::new(3);
+    let qubits = sim.get_qubits();
+
+    assert_eq!(
+        sim.try_apply_double(&identity(4), &qubits[1], &qubits[1]),
+        Err(RusqError::DuplicateQubit { index: 1 })
+    );
+    assert_eq!(
+        sim.try_apply_triple(&identity(8), &qubits[0], &qubits[2], &qubits[0]),
+        Err(RusqError::DuplicateQubit { index: 0 })
+    );
+}
+
+#[test]
+fn invalid_matrix_shape() {
+    let mut sim = QuantumSimulator::new(2);
+    let qubits = sim.get_qubits();
+
+    assert_eq!(
+        sim.try_apply_single(&identity(3), &qubits[0]),
+        Err(RusqError::InvalidMatrixShape {
+            expected: (2, 2),
+            actual: (3, 3),
+        })
+    );
+    assert_eq!(
+        sim.try_apply_double(&identity(2), &qubits[0], &qubits[1]),
+        Err(RusqError::InvalidMatrixShape {
+            expected: (4, 4),
+            actual: (2, 2),
+        })
+    );
+}
+
+#[test]
+fn unitarity() {
+    let mut sim = QuantumSimulator::new(1);
+    let qubit = &sim.get_qubits()[0];
+    let matrix = identity(2) * Complex::new(2., 0.);
+
+    assert_eq!(sim.try_apply_single(&matrix, qubit), Ok(()));
+
+    sim.set_unitarity_check(Some(1e-10));
+    assert_eq!(
+        sim.try_apply_single(&matrix, qubit),
+        Err(RusqError::NonUnitaryMatrix)
+    );
+    assert_eq!(sim.try_apply_single(&X.matrix, qubit), Ok(()));
+
+    sim.set_unitarity_check(None);
+    assert_eq!(sim.try_apply_single(&matrix, qubit), Ok(()));
+}
+
+#[test]
+fn valid_operations() {
+    let mut sim = QuantumSimulator::new(2);
+    let qubits = sim.get_qubits();
+
+    assert_eq!(sim.try_apply_single(&X.matrix, &qubits[0]), Ok(()));
+    assert_eq!(sim.try_measure(&qubits[0]), Ok(MeasuredResult::One));
+    assert_eq!(sim.try_measure(&qubits[1]), Ok(MeasuredResult::Zero));
+}
+
+#[test]
+#[should_panic(expected = "qubit index 1 is given more than once")]
+fn panic_on_duplicate_qubit() {
+    let mut sim = QuantumSimulator::new(2);
+    let qubits = sim.get_qubits();
+    sim.CNOT(&qubits[1], &qubits[1]);
+}
+
+#[test]
+#[should_panic(expected = "belongs to another machine")]
+fn panic_on_foreign_qubit() {
+    let mut sim = QuantumSimulator::new(2);
+    let qubits = QuantumSimulator::new(3).get_qubits();
+    sim.measure(&qubits[2]);
+}
@@ -0,0 +1,122 @@
+extern crate rusq;
+
+use rusq::circuit::parameterized::{Parameter, ParameterExpr, ParameterizedCircuit};
+use rusq::gradient;
+use rusq::observable::Hamiltonian;
+use rusq::prelude::*;
+
+fn example() -> ParameterizedCircuit {
+    let a = Parameter::new("a");
+    let b = Parameter::new("b");
+    let c = Parameter::new("c");
+
+    let mut circuit = ParameterizedCircuit::new(3);
+    let qubits = circuit.get_qubits();
+    circuit.H(&qubits[0]);
+    circuit.rx(&a, &qubits[0]);
+    circuit.ry(2. * &b + 0.3, &qubits[1]);
+    circuit.CNOT(&qubits[0], &qubits[2]);
+    circuit.rzz(ParameterExpr::from(&a) - &c, &qubits[1], &qubits[2]);
+    circuit.cphase(&c, &qubits[2], &qubits[0]);
+    circuit.rz(0.7, &qubits[1]);
+    circuit.H(&qubits[2]);
+    circuit.phase(-0.5 * &b, &qubits[2]);
+    circuit.ry(&a, &qubits[2]);
+    circuit.rx(&c, &qubits[1]);
+    circuit
+}
+
+fn hamiltonian() -> Hamiltonian {
+    Hamiltonian::from_terms(&[(1., "ZIZ"), (0.5, "XYI"), (-0.8, "IXY"), (0.3, "YII")])
+}
+
+fn expectation(sim: &mut QuantumSimulator, circuit: &ParameterizedCircuit, values: &[f64]) -> f64 {
+    sim.reset();
+    circuit.run(sim, values);
+    hamiltonian().expectation(sim, &sim.get_qubits())
+}
+
+fn finite_difference(circuit: &ParameterizedCircuit, values: &[f64]) -> Vec<f64> {
+    let mut sim = QuantumSimulator::new(3);
+    let h = 1e-6;
+    (0..values.len())
+        .map(|i| {
+            let mut plus = values.to_vec();
+            let mut minus = values.to_vec();
+            plus[i] += h;
+            minus[i] -= h;
+            (expectation(&mut sim, circuit, &plus) - expectation(&mut sim, circuit, &minus))
+                / (2. * h)
+        })
+        .collect()
+}
+
+fn assert_close(actual: &[f64], expected: &[f64], tolerance: f64) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < tolerance, "{:?} vs {:?}", actual, expected);
+    }
+}
+
+#[test]
+fn parameter_shift() {
+    let circuit = example();
+    let hamiltonian = hamiltonian();
+    let mut sim = QuantumSimulator::new(3);
+
+    for values in &[[0.1, 0.2, 0.3], [1.2, -0.7, 2.5]] {
+        let mut evaluations = 0;
+        let gradient = gradient::parameter_shift(&circuit, values, |shifted| {
+            evaluations += 1;
+            sim.reset();
+            shifted.run(&mut sim);
+            hamiltonian.expectation(&sim, &sim.get_qubits())
+        });
+
+        assert_close(&gradient, &finite_difference(&circuit, values), 1e-6);
+        // Two evaluations for each of the seven parameterized gates
+        assert_eq!(evaluations, 14);
+    }
+}
+
+#[test]
+fn parameter_shift_with_shots() {
+    let circuit = example();
+    let hamiltonian = hamiltonian();
+    let mut sim = QuantumSimulator::with_seed(3, 11);
+    let values = [0.4, -0.3, 1.1];
+
+    let gradient = gradient::parameter_shift(&circuit, &values, |shifted| {
+        sim.reset();
+        shifted.run(&mut sim);
+        let qubits = sim.get_qubits();
+        hamiltonian.sample_expectation(&mut sim, &qubits, 20000)
+    });
+
+    assert_close(&gradient, &finite_difference(&circuit, &values), 0.1);
+}
+
+#[test]
+fn adjoint() {
+    let circuit = example();
+    let hamiltonian = hamiltonian();
+    let mut sim = QuantumSimulator::new(3);
+
+    for values in &[[0.1, 0.2, 0.3], [1.2, -0.7, 2.5]] {
+        sim.reset();
+        let gradient = gradient::adjoint(&mut sim, &circuit, values, &hamiltonian);
+        assert_close(&gradient, &finite_difference(&circuit, values), 1e-6);
+
+        // The simulator is left in the final state.
+        let mut expected = QuantumSimulator::new(3);
+        let value = expectation(&mut expected, &circuit, values);
+        assert!((hamiltonian.expectation(&sim, &sim.get_qubits()) - value).abs() < 1e-10);
+    }
+}
+
+#[test]
+#[should_panic(expected = "3 parameters but 2 values")]
+fn adjoint_with_wrong_values() {
+    let mut sim = QuantumSimulator::new(3);
+    gradient::adjoint(&mut sim, &example(), &[0.1, 0.2], &hamiltonian());
+}
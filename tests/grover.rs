@@ -0,0 +1,122 @@
+extern crate num;
+extern crate rusq;
+
+use num::complex::Complex;
+use rusq::algorithms::grover::{self, Uniform};
+use rusq::circuit::Circuit;
+use rusq::gates::multi;
+use rusq::prelude::*;
+
+const EPS: f64 = 1e-10;
+
+fn probability(sim: &QuantumSimulator, x: usize) -> f64 {
+    sim.snapshot().amplitudes()[x].norm_sqr()
+}
+
+#[test]
+fn multi_controlled_gates() {
+    for n in 1..6 {
+        let mut sim = QuantumSimulator::new(n);
+        let qubits = sim.get_qubits();
+        for q in &qubits {
+            sim.H(q);
+        }
+        multi::mcphase(&mut sim, 0.7, &qubits);
+
+        let amplitudes = sim.snapshot().amplitudes().to_vec();
+        let norm = 1. / ((1 << n) as f64).sqrt();
+        for (x, a) in amplitudes.iter().enumerate() {
+            let phase = if x == (1 << n) - 1 { 0.7 } else { 0. };
+            assert!((a - Complex::from_polar(&norm, &phase)).norm() < EPS);
+        }
+    }
+
+    let mut sim = QuantumSimulator::new(4);
+    let register = sim.get_register();
+    for x in 0..16 {
+        register.set_integer(&mut sim, x, Endianness::Little);
+        multi::mcx(&mut sim, &register[..3], &register[3]);
+        let expected = if x & 0b111 == 0b111 { x ^ 0b1000 } else { x };
+        assert!((probability(&sim, expected) - 1.).abs() < EPS);
+    }
+}
+
+#[test]
+fn optimal_iterations() {
+    assert_eq!(grover::optimal_iterations(2, 1), 1);
+    assert_eq!(grover::optimal_iterations(4, 1), 3);
+    assert_eq!(grover::optimal_iterations(4, 4), 1);
+    assert_eq!(grover::optimal_iterations(4, 16), 0);
+}
+
+#[test]
+fn search_with_closure() {
+    let n = 6;
+    let mut sim = QuantumSimulator::new(n);
+    let qubits = sim.get_qubits();
+    let oracle = |bits: &[bool]| bits.iter().filter(|&&b| b).count() == 5;
+
+    // Six marked states out of 64.
+    let iterations = grover::optimal_iterations(n, 6);
+    grover::amplify(&mut sim, &qubits, &Uniform, &oracle, iterations);
+    let success = (0..1 << n)
+        .filter(|&x: &usize| x.count_ones() == 5)
+        .map(|x| probability(&sim, x))
+        .sum::<f64>();
+    let theta = (6f64 / 64.).sqrt().asin();
+    let expected = ((2 * iterations + 1) as f64 * theta).sin().powi(2);
+    assert!((success - expected).abs() < EPS);
+    assert!(success > 0.99);
+}
+
+#[test]
+fn search_with_circuit() {
+    let n = 4;
+    let mut oracle = Circuit::new(n, 0);
+    let qubits = oracle.get_qubits();
+    // Marks 0b1001.
+    oracle.X(&qubits[1]);
+    oracle.X(&qubits[2]);
+    multi::mcz(&mut oracle, &qubits);
+    oracle.X(&qubits[1]);
+    oracle.X(&qubits[2]);
+
+    let mut found = 0;
+    for _ in 0..20 {
+        let mut sim = QuantumSimulator::new(n);
+        let qubits = sim.get_qubits();
+        if grover::search(&mut sim, &qubits, &oracle, 1) == 0b1001 {
+            found += 1;
+        }
+    }
+    // The success probability is about 96%.
+    assert!(found >= 15);
+}
+
+#[test]
+fn amplify_with_preparation() {
+    let n = 3;
+    let mut preparation = Circuit::new(n, 0);
+    let qubits = preparation.get_qubits();
+    preparation.H(&qubits[0]);
+    preparation.CNOT(&qubits[0], &qubits[1]);
+    preparation.H(&qubits[2]);
+    preparation.phase(0.3, &qubits[2]);
+    preparation.SQSWAP(&qubits[1], &qubits[2]);
+
+    let oracle = |bits: &[bool]| bits == [true, true, false];
+    let mut sim = QuantumSimulator::new(n);
+    let qubits = sim.get_qubits();
+    grover::amplify(&mut sim, &qubits, &preparation, &oracle, 0);
+    let initial = probability(&sim, 0b011);
+    assert!(initial > 0.01 && initial < 0.5);
+
+    let theta = initial.sqrt().asin();
+    for k in 1..4 {
+        let mut sim = QuantumSimulator::new(n);
+        let qubits = sim.get_qubits();
+        grover::amplify(&mut sim, &qubits, &preparation, &oracle, k);
+        let expected = ((2 * k + 1) as f64 * theta).sin().powi(2);
+        assert!((probability(&sim, 0b011) - expected).abs() < EPS);
+    }
+}
@@ -1,4 +1,5 @@
 extern crate permutohedron;
+#[macro_use]
 extern crate rusq;
 
 use rusq::prelude::*;
@@ -222,3 +223,71 @@ fn operate_cswap() {
         1, 1, 1 => 1, 1, 1;
     });
 }
+
+#[test]
+fn operate_h_twice() {
+    let mut sim = rusq::simulator::QuantumSimulator::new(1);
+    let qubit = &sim.get_qubits()[0];
+
+    // H|0> = |+> and H|1> = |->, so applying H twice gives back the basis state.
+    for &r in &[MeasuredResult::Zero, MeasuredResult::One] {
+        set(&mut sim, qubit, r);
+        sim.H(qubit);
+        sim.H(qubit);
+        assert_eq!(sim.measure(qubit), r);
+    }
+}
+
+#[test]
+fn operate_cnot_with_spectators() {
+    let mut sim = rusq::simulator::QuantumSimulator::new(6);
+    let qubits = sim.get_qubits();
+    let ref targets = [&qubits[1], &qubits[4]];
+    let spectators = [&qubits[0], &qubits[2], &qubits[3], &qubits[5]];
+
+    for spectator in &spectators {
+        set(&mut sim, spectator, MeasuredResult::One);
+    }
+
+    logic!((sim, targets, CNOT) => {
+        0, 0 => 0, 0;
+        0, 1 => 0, 1;
+        1, 0 => 1, 1;
+        1, 1 => 1, 0;
+    });
+
+    for spectator in &spectators {
+        assert_eq!(sim.measure(spectator), MeasuredResult::One);
+    }
+}
+
+#[test]
+fn operate_on_superposition() {
+    let mut sim = QuantumSimulator::new(3);
+    let mut expected = QuantumSimulator::new(3);
+    let qubits = sim.get_qubits();
+    let others = expected.get_qubits();
+    for (k, (q, p)) in qubits.iter().zip(&others).enumerate() {
+        let theta = 0.3 + k as f64;
+        sim.ry(theta, q);
+        expected.ry(theta, p);
+    }
+
+    // SWAP is three CNOTs, and CSWAP is equal to itself with the targets exchanged.
+    sim.CNOT(&qubits[0], &qubits[1]);
+    sim.CNOT(&qubits[1], &qubits[0]);
+    sim.CNOT(&qubits[0], &qubits[1]);
+    sim.CSWAP(&qubits[2], &qubits[0], &qubits[1]);
+    expected.SWAP(&others[0], &others[1]);
+    expected.CSWAP(&others[2], &others[1], &others[0]);
+    assert_state_approx_eq!(sim, expected);
+
+    // HZH = X up to the global phase, but not Z.
+    sim.H(&qubits[2]);
+    sim.Z(&qubits[2]);
+    sim.H(&qubits[2]);
+    expected.X(&others[2]);
+    assert_state_approx_eq!(sim, expected);
+    expected.Z(&others[2]);
+    assert_state_approx_ne!(sim, expected);
+}
@@ -0,0 +1,187 @@
+extern crate rusq;
+
+use rusq::optimize::{
+    Adam, Cobyla, Differentiation, GradientDescent, Iteration, NelderMead, Optimizer, Spsa,
+};
+
+fn rosenbrock(x: &[f64]) -> f64 {
+    (1. - x[0]).powi(2) + 100. * (x[1] - x[0] * x[0]).powi(2)
+}
+
+fn quadratic(x: &[f64]) -> f64 {
+    (x[0] - 1.).powi(2) + 2. * (x[1] + 0.5).powi(2) + 0.5 * (x[0] - 1.) * (x[1] + 0.5)
+}
+
+fn assert_minimum<O: Optimizer>(mut optimizer: O, tolerance: f64) {
+    let mut iterations = vec![];
+    let minimum = optimizer.minimize_with_callback(quadratic, &[3., 2.], |it: &Iteration| {
+        iterations.push((it.iteration, it.value, it.evaluations))
+    });
+
+    assert!((minimum.parameters[0] - 1.).abs() < tolerance);
+    assert!((minimum.parameters[1] + 0.5).abs() < tolerance);
+    assert!((minimum.value - quadratic(&minimum.parameters)).abs() < 1e-12);
+
+    // The callback is called after each iteration with the progress.
+    assert!(iterations.len() > 1);
+    for pair in iterations.windows(2) {
+        assert_eq!(pair[1].0, pair[0].0 + 1);
+        assert!(pair[1].2 > pair[0].2);
+    }
+    let last = iterations[iterations.len() - 1];
+    assert!(last.1 < iterations[0].1);
+    assert!(last.2 <= minimum.evaluations);
+}
+
+#[test]
+fn nelder_mead() {
+    let mut optimizer = NelderMead::default();
+    let minimum = optimizer.minimize(rosenbrock, &[-1.2, 1.]);
+
+    assert!((minimum.parameters[0] - 1.).abs() < 1e-4);
+    assert!((minimum.parameters[1] - 1.).abs() < 1e-4);
+    assert!(minimum.value < 1e-8);
+    assert!(minimum.evaluations > 0);
+}
+
+#[test]
+fn nelder_mead_max_iterations() {
+    let mut evaluations = 0;
+    let mut optimizer = NelderMead {
+        max_iterations: 5,
+        ..NelderMead::default()
+    };
+    let minimum = optimizer.minimize(
+        |x: &[f64]| {
+            evaluations += 1;
+            rosenbrock(x)
+        },
+        &[-1.2, 1.],
+    );
+
+    assert_eq!(minimum.evaluations, evaluations);
+    assert!(minimum.value <= rosenbrock(&[-1.2, 1.]));
+    assert!(minimum.value > 1e-3);
+}
+
+#[test]
+fn nelder_mead_callback() {
+    assert_minimum(NelderMead::default(), 1e-4);
+}
+
+#[test]
+fn gradient_descent() {
+    assert_minimum(GradientDescent::default(), 1e-6);
+}
+
+#[test]
+fn gradient_descent_parameter_shift() {
+    // The parameter-shift rule is exact for the sums of sinusoids with unit frequency.
+    let objective = |x: &[f64]| x[0].cos() + (x[1] - 0.3).sin();
+    let mut optimizer = GradientDescent {
+        learning_rate: 0.5,
+        differentiation: Differentiation::ParameterShift,
+        ..GradientDescent::default()
+    };
+    let minimum = optimizer.minimize(objective, &[2., 0.]);
+
+    assert!((minimum.value + 2.).abs() < 1e-10);
+    assert!((minimum.parameters[0] - std::f64::consts::PI).abs() < 1e-5);
+}
+
+#[test]
+fn differentiation() {
+    let mut objective = |x: &[f64]| x[0].sin() * x[1].cos();
+    let x = [0.4f64, 1.1];
+    let expected = [x[0].cos() * x[1].cos(), -x[0].sin() * x[1].sin()];
+
+    for &method in &[Differentiation::default(), Differentiation::ParameterShift] {
+        let gradient = method.gradient(&mut objective, &x);
+        assert!((gradient[0] - expected[0]).abs() < 1e-8);
+        assert!((gradient[1] - expected[1]).abs() < 1e-8);
+    }
+}
+
+#[test]
+fn adam() {
+    assert_minimum(
+        Adam {
+            learning_rate: 0.05,
+            max_iterations: 3000,
+            ..Adam::default()
+        },
+        1e-4,
+    );
+}
+
+#[test]
+fn spsa() {
+    assert_minimum(Spsa::default(), 1e-2);
+}
+
+#[test]
+fn spsa_with_noise() {
+    let mut noise = 0.;
+    let objective = |x: &[f64]| {
+        // A deterministic sequence of small errors
+        noise = (noise * 7. + 0.3) % 1.;
+        quadratic(x) + 0.01 * (noise - 0.5)
+    };
+    let minimum = Spsa::default().minimize(objective, &[3., 2.]);
+
+    assert!((minimum.parameters[0] - 1.).abs() < 0.1);
+    assert!((minimum.parameters[1] + 0.5).abs() < 0.1);
+}
+
+#[test]
+fn spsa_is_reproducible() {
+    let first = Spsa::default().minimize(rosenbrock, &[-1.2, 1.]);
+    let second = Spsa::default().minimize(rosenbrock, &[-1.2, 1.]);
+    let other = Spsa {
+        seed: 1,
+        ..Spsa::default()
+    }
+    .minimize(rosenbrock, &[-1.2, 1.]);
+
+    assert_eq!(first, second);
+    assert_ne!(first, other);
+    // Two evaluations for each iteration and one at the end
+    assert_eq!(first.evaluations, 2 * Spsa::default().max_iterations + 1);
+}
+
+#[test]
+fn cobyla() {
+    assert_minimum(Cobyla::default(), 1e-5);
+}
+
+#[test]
+fn cobyla_constrained() {
+    // The minimum of x + y in the unit disk
+    let mut optimizer = Cobyla::default();
+    let minimum = optimizer.minimize_constrained(
+        |x: &[f64]| x[0] + x[1],
+        |x: &[f64]| vec![1. - x[0] * x[0] - x[1] * x[1]],
+        &[0.5, 0.],
+        |_: &Iteration| {},
+    );
+
+    let expected = -1. / 2f64.sqrt();
+    assert!((minimum.parameters[0] - expected).abs() < 1e-4);
+    assert!((minimum.parameters[1] - expected).abs() < 1e-4);
+    assert!((minimum.value - 2. * expected).abs() < 1e-4);
+}
+
+#[test]
+fn cobyla_bounds() {
+    // The Rosenbrock function restricted to x <= 0.5 and y >= 0.3
+    let mut optimizer = Cobyla::default();
+    let minimum = optimizer.minimize_constrained(
+        rosenbrock,
+        |x: &[f64]| vec![0.5 - x[0], x[1] - 0.3],
+        &[0., 1.],
+        |_: &Iteration| {},
+    );
+
+    assert!((minimum.parameters[0] - 0.5).abs() < 1e-3);
+    assert!((minimum.parameters[1] - 0.3).abs() < 1e-3);
+}
@@ -0,0 +1,197 @@
+extern crate rusq;
+
+use rusq::algorithms::vqe::{self, Estimation};
+use rusq::circuit::parameterized::{
+    Parameter, ParameterExpr, ParameterizedCircuit, ParameterizedGate,
+};
+use rusq::circuit::{Circuit, Gate, Operation};
+use rusq::observable::Hamiltonian;
+use rusq::optimize::NelderMead;
+use rusq::prelude::*;
+use rusq::RusqError;
+use std::collections::HashMap;
+
+const EPS: f64 = 1e-10;
+
+fn values(pairs: &[(&Parameter, f64)]) -> HashMap<Parameter, f64> {
+    pairs.iter().map(|&(p, v)| (p.clone(), v)).collect()
+}
+
+fn assert_same_state(a: &QuantumSimulator, b: &QuantumSimulator) {
+    for (x, y) in a
+        .snapshot()
+        .amplitudes()
+        .iter()
+        .zip(b.snapshot().amplitudes())
+    {
+        assert!((x - y).norm() < EPS);
+    }
+}
+
+#[test]
+fn expressions() {
+    let theta = Parameter::new("θ");
+    let phi = Parameter::new("φ");
+    assert_ne!(theta, Parameter::new("θ"));
+    assert_eq!(theta.to_string(), "θ");
+
+    let expr = 2. * &theta + &phi - 0.5 + ParameterExpr::from(&theta);
+    assert_eq!(expr.terms(), &[(theta.clone(), 3.), (phi.clone(), 1.)]);
+    assert_eq!(expr.constant(), -0.5);
+    assert_eq!(expr.evaluate(&values(&[(&theta, 1.), (&phi, 2.)])), Ok(4.5));
+
+    let negated = -expr * 2.;
+    assert_eq!(
+        negated.evaluate(&values(&[(&theta, 1.), (&phi, 2.)])),
+        Ok(-9.)
+    );
+    assert_eq!((-&phi).evaluate(&values(&[(&phi, 2.)])), Ok(-2.));
+    assert_eq!(
+        negated.evaluate(&values(&[(&theta, 1.)])),
+        Err(RusqError::UnboundParameter {
+            name: "φ".to_owned()
+        })
+    );
+}
+
+fn example(theta: &Parameter, phi: &Parameter) -> ParameterizedCircuit {
+    let mut circuit = ParameterizedCircuit::new(3);
+    let qubits = circuit.get_qubits();
+    circuit.H(&qubits[0]);
+    circuit.rx(theta, &qubits[1]);
+    circuit.cphase(phi, &qubits[0], &qubits[1]);
+    circuit.ry(0.3, &qubits[2]);
+    circuit.rzz(ParameterExpr::from(theta) - phi, &qubits[1], &qubits[2]);
+    circuit.CNOT(&qubits[2], &qubits[0]);
+    circuit.rz(0.5 * phi, &qubits[0]);
+    circuit.phase(theta, &qubits[2]);
+    circuit
+}
+
+#[test]
+fn bind() {
+    let theta = Parameter::new("θ");
+    let phi = Parameter::new("φ");
+    let circuit = example(&phi, &theta);
+    let qubits = circuit.get_qubits();
+
+    // The order of the first appearance
+    assert_eq!(circuit.parameters(), &[phi.clone(), theta.clone()]);
+    assert_eq!(circuit.gates().len(), 8);
+    assert_eq!(
+        circuit.gates()[0],
+        ParameterizedGate::Fixed(Gate::H(qubits[0]))
+    );
+
+    let bound = circuit.bind(&values(&[(&theta, 0.2), (&phi, 0.7)]));
+    let gates = bound
+        .instructions()
+        .iter()
+        .map(|instruction| instruction.operation.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(gates.len(), 8);
+    assert_eq!(gates[1], Operation::Gate(Gate::RX(0.7, qubits[1])));
+    assert_eq!(gates[3], Operation::Gate(Gate::RY(0.3, qubits[2])));
+    assert_eq!(
+        gates[4],
+        Operation::Gate(Gate::RZZ(0.7 - 0.2, qubits[1], qubits[2]))
+    );
+    assert_eq!(gates[6], Operation::Gate(Gate::RZ(0.1, qubits[0])));
+
+    assert_eq!(
+        circuit.try_bind(&values(&[(&theta, 0.2)])).unwrap_err(),
+        RusqError::UnboundParameter {
+            name: "φ".to_owned()
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "no value is given to parameter θ")]
+fn bind_unbound() {
+    let theta = Parameter::new("θ");
+    let mut circuit = ParameterizedCircuit::new(1);
+    let qubits = circuit.get_qubits();
+    circuit.rx(&theta, &qubits[0]);
+    circuit.bind(&HashMap::new());
+}
+
+#[test]
+fn run() {
+    let theta = Parameter::new("θ");
+    let phi = Parameter::new("φ");
+    let circuit = example(&theta, &phi);
+
+    let mut direct = QuantumSimulator::new(3);
+    let mut bound = QuantumSimulator::new(3);
+    for &(t, p) in &[(0.1, 0.2), (1.3, -0.4), (3., 2.)] {
+        direct.reset();
+        circuit.run(&mut direct, &[t, p]);
+
+        bound.reset();
+        circuit
+            .bind(&values(&[(&theta, t), (&phi, p)]))
+            .run(&mut bound);
+
+        assert_same_state(&direct, &bound);
+    }
+}
+
+#[test]
+fn run_on_given_qubits() {
+    let theta = Parameter::new("θ");
+    let mut circuit = ParameterizedCircuit::new(2);
+    let qubits = circuit.get_qubits();
+    circuit.ry(&theta, &qubits[0]);
+    circuit.CNOT(&qubits[0], &qubits[1]);
+
+    let mut sim = QuantumSimulator::new(3);
+    let targets = sim.get_qubits();
+    circuit.run_on(&mut sim, &[targets[2], targets[0]], &[std::f64::consts::PI]);
+    assert_eq!(
+        sim.get_register()
+            .measure_integer(&mut sim, Endianness::Little),
+        5
+    );
+}
+
+#[test]
+#[should_panic(expected = "1 parameters but 2 values")]
+fn run_with_wrong_values() {
+    let theta = Parameter::new("θ");
+    let mut circuit = ParameterizedCircuit::new(1);
+    let qubits = circuit.get_qubits();
+    circuit.rx(&theta, &qubits[0]);
+
+    let mut sim = QuantumSimulator::new(1);
+    circuit.run(&mut sim, &[0.1, 0.2]);
+}
+
+#[test]
+#[should_panic(expected = "another machine")]
+fn reject_foreign_qubit() {
+    let mut circuit = ParameterizedCircuit::new(1);
+    let other = Circuit::new(1, 0);
+    circuit.rx(Parameter::new("θ"), &other.get_qubits()[0]);
+}
+
+#[test]
+fn ansatz() {
+    let theta = Parameter::new("θ");
+    let mut circuit = ParameterizedCircuit::new(1);
+    let qubits = circuit.get_qubits();
+    circuit.ry(&theta, &qubits[0]);
+
+    let hamiltonian = Hamiltonian::from_terms(&[(1., "Z"), (1., "X")]);
+    let mut sim = QuantumSimulator::new(1);
+    let result = vqe::solve(
+        &mut sim,
+        &circuit,
+        &hamiltonian,
+        &mut NelderMead::default(),
+        &[0.],
+        Estimation::Exact,
+    );
+
+    assert!((result.energy + 2f64.sqrt()).abs() < 1e-6);
+}
@@ -0,0 +1,124 @@
+extern crate ndarray;
+extern crate num;
+extern crate rusq;
+
+use ndarray::prelude::*;
+use num::complex::Complex;
+use rusq::algorithms::phase_estimation::{self, UnitaryMatrix};
+use rusq::circuit::{Circuit, Gate, Operation};
+use rusq::gates::triple::CCNOT;
+use rusq::prelude::*;
+use rusq::RusqError;
+use std::f64::consts::PI;
+
+/// A diagonal unitary whose eigenvalue for `|i>` is `exp(2 pi i phases[i])`.
+fn diagonal(phases: &[f64]) -> UnitaryMatrix {
+    let mut matrix = Array2::zeros((phases.len(), phases.len()));
+    for (i, &phase) in phases.iter().enumerate() {
+        matrix[[i, i]] = Complex::from_polar(&1., &(2. * PI * phase));
+    }
+    UnitaryMatrix::new(matrix)
+}
+
+#[test]
+fn exact_phases() {
+    let t = 4;
+    for targets_len in 1..4 {
+        let size = 1 << targets_len;
+        let phases = (0..size)
+            .map(|i| (3 * i + 1) as f64 / 16.)
+            .collect::<Vec<_>>();
+        let unitary = diagonal(&phases);
+
+        for (i, &expected) in phases.iter().enumerate() {
+            let mut sim = QuantumSimulator::new(t + targets_len);
+            let qubits = sim.get_qubits();
+            let (counting, eigenstate) = qubits.split_at(t);
+            // The first target qubit is the most significant bit for the matrix.
+            for (k, qubit) in eigenstate.iter().enumerate() {
+                if (i >> (targets_len - 1 - k)) & 1 == 1 {
+                    sim.X(qubit);
+                }
+            }
+
+            let phase = phase_estimation::estimate_phase(&mut sim, &unitary, counting, eigenstate);
+            assert_eq!(phase, expected % 1.);
+        }
+    }
+}
+
+#[test]
+fn inexact_phase() {
+    let t = 5;
+    let phi = 0.3;
+    let mut sim = QuantumSimulator::new(t + 1);
+    let qubits = sim.get_qubits();
+    let (counting, eigenstate) = qubits.split_at(t);
+    let unitary = |sim: &mut QuantumSimulator, control: &Qubit, targets: &[Qubit]| {
+        sim.cphase(2. * PI * phi, control, &targets[0]);
+    };
+    sim.X(&eigenstate[0]);
+
+    phase_estimation::phase_estimation(&mut sim, &unitary, counting, eigenstate);
+
+    // 0.3 * 32 = 9.6 lies between 9 and 10.
+    let amplitudes = sim.snapshot().amplitudes().to_vec();
+    let probability = |m: usize| amplitudes[m | (1 << t)].norm_sqr();
+    assert!(probability(10) > probability(9));
+    assert!(probability(10) + probability(9) > 8. / (PI * PI));
+}
+
+#[test]
+fn apply_multi() {
+    let mut sim1 = QuantumSimulator::new(4);
+    let mut sim2 = QuantumSimulator::new(4);
+    let qubits1 = sim1.get_qubits();
+    let qubits2 = sim2.get_qubits();
+    for (q1, q2) in qubits1.iter().zip(&qubits2) {
+        sim1.H(q1);
+        sim2.H(q2);
+    }
+    sim1.phase(0.5, &qubits1[0]);
+    sim2.phase(0.5, &qubits2[0]);
+
+    sim1.CCNOT(&qubits1[3], &qubits1[0], &qubits1[2]);
+    sim2.apply_multi(&CCNOT.matrix, &[qubits2[3], qubits2[0], qubits2[2]]);
+    assert_eq!(sim1.snapshot(), sim2.snapshot());
+
+    assert_eq!(
+        sim2.try_apply_multi(&CCNOT.matrix, &qubits2),
+        Err(RusqError::InvalidMatrixShape {
+            expected: (16, 16),
+            actual: (8, 8)
+        })
+    );
+}
+
+#[test]
+fn record_multi() {
+    let mut circuit = Circuit::new(4, 0);
+    let qubits = circuit.get_qubits();
+    let unitary = diagonal(&[0., 0.25, 0.5, 0.75]);
+    phase_estimation::phase_estimation(&mut circuit, &unitary, &qubits[..1], &qubits[1..3]);
+    circuit.apply_multi(&Array2::eye(8), &qubits[1..]);
+
+    match circuit.instructions()[1].operation {
+        Operation::Gate(Gate::Triple(_, q1, q2, q3)) => {
+            assert_eq!([q1, q2, q3], [qubits[0], qubits[1], qubits[2]])
+        }
+        ref operation => panic!("unexpected operation {:?}", operation),
+    }
+    assert_eq!(
+        circuit.to_string(),
+        [
+            "q0: ──┤H├──┤U[0]├─────┤H├────",
+            "              │",
+            "q1: ───────┤U[1]├───┤U[0]├───",
+            "              │        │",
+            "q2: ───────┤U[2]├───┤U[1]├───",
+            "                       │",
+            "q3: ────────────────┤U[2]├───",
+        ]
+        .join("\n")
+    );
+}
@@ -0,0 +1,156 @@
+extern crate num;
+extern crate rusq;
+
+use num::complex::Complex;
+use rusq::algorithms::qaoa::{self, Ising};
+use rusq::circuit::{Circuit, Gate, Operation};
+use rusq::optimize::NelderMead;
+use rusq::prelude::*;
+
+const EPS: f64 = 1e-10;
+
+fn bits(n: usize, x: usize) -> Vec<bool> {
+    (0..n).map(|i| x >> i & 1 == 1).collect()
+}
+
+fn example() -> Ising {
+    let mut ising = Ising::new(3);
+    ising.add_offset(0.25);
+    ising.add_linear(0, 1.);
+    ising.add_linear(2, -0.5);
+    ising.add_quadratic(0, 1, 2.);
+    ising.add_quadratic(1, 2, -1.5);
+    ising
+}
+
+#[test]
+fn hamiltonian() {
+    let ising = example();
+    assert_eq!(
+        ising.energy(&[false, false, false]),
+        0.25 + 1. - 0.5 + 2. - 1.5
+    );
+    assert_eq!(
+        ising.energy(&[true, false, true]),
+        0.25 - 1. + 0.5 - 2. + 1.5
+    );
+
+    let hamiltonian = ising.hamiltonian();
+    let mut sim = QuantumSimulator::new(3);
+    let register = sim.get_register();
+    for x in 0..8 {
+        sim.reset();
+        register.set_integer(&mut sim, x, Endianness::Little);
+        let expected = ising.energy(&bits(3, x));
+        assert!((hamiltonian.expectation(&sim, &register) - expected).abs() < EPS);
+    }
+}
+
+#[test]
+fn cost_layer() {
+    let ising = example();
+    let gamma = 0.37;
+
+    let mut sim = QuantumSimulator::new(3);
+    let qubits = sim.get_qubits();
+    for qubit in &qubits {
+        sim.H(qubit);
+    }
+    qaoa::cost_layer(&mut sim, &ising, gamma, &qubits);
+
+    // Each amplitude gets the phase e^{-iγC} up to the global phase.
+    let snapshot = sim.snapshot();
+    let amplitudes = snapshot.amplitudes();
+    let reference = ising.energy(&bits(3, 0));
+    for (x, amplitude) in amplitudes.iter().enumerate() {
+        let phase = -gamma * (ising.energy(&bits(3, x)) - reference);
+        let expected = amplitudes[0] * Complex::new(phase.cos(), phase.sin());
+        assert!((amplitude - expected).norm() < EPS);
+    }
+}
+
+#[test]
+fn expected_cut() {
+    let edges = [(0, 1, 1.), (1, 2, 2.), (0, 2, 3.)];
+    let mut sim = QuantumSimulator::new(3);
+
+    // Without the layers, each edge is cut with the probability of 1/2.
+    assert!((qaoa::expected_cut(&mut sim, 3, &edges, &[]) - 3.).abs() < EPS);
+    assert!((qaoa::expected_cut(&mut sim, 3, &edges, &[0.3, 0.]) - 3.).abs() < EPS);
+    assert!((qaoa::expected_cut(&mut sim, 3, &edges, &[0.3, 0.4]) - 3.).abs() > 1e-3);
+}
+
+#[test]
+fn weighted_maxcut() {
+    let edges = [(0, 1, 1.), (1, 2, 2.), (0, 2, 3.)];
+    let problem = Ising::maxcut(3, &edges);
+
+    let mut sim = QuantumSimulator::new(3);
+    let result = qaoa::solve(
+        &mut sim,
+        &problem,
+        &mut NelderMead::default(),
+        &[0.2, 0.3, 0.4, 0.5],
+    );
+
+    // The maximum cut separates the vertex 2.
+    assert_eq!(problem.energy(&result.solution), -5.);
+    assert!(result.solution[0] == result.solution[1]);
+    assert!(result.energy < -4., "{}", result.energy);
+    assert!(
+        (qaoa::expected_cut(&mut sim, 3, &edges, &result.parameters) + result.energy).abs() < EPS
+    );
+}
+
+#[test]
+fn fields() {
+    let mut problem = Ising::new(3);
+    problem.add_linear(0, 1.);
+    problem.add_linear(1, -1.);
+    problem.add_linear(2, 0.5);
+
+    let mut sim = QuantumSimulator::new(3);
+    let result = qaoa::solve(&mut sim, &problem, &mut NelderMead::default(), &[0.5, 0.5]);
+
+    assert_eq!(result.solution, vec![true, false, true]);
+    assert!(result.energy < -2., "{}", result.energy);
+}
+
+#[test]
+fn prepare_on_circuit() {
+    let problem = example();
+    let parameters = [0.3, 0.7, -0.2, 0.1];
+
+    let mut circuit = Circuit::new(3, 0);
+    let qubits = circuit.get_qubits();
+    qaoa::prepare(&mut circuit, &problem, &parameters, &qubits);
+
+    // The Hadamards, and two layers of two Rz, two Rzz and three Rx.
+    assert_eq!(circuit.instructions().len(), 3 + 2 * 7);
+    assert_eq!(
+        circuit.instructions()[5].operation,
+        Operation::Gate(Gate::RZZ(2. * 0.3 * 2., qubits[0], qubits[1]))
+    );
+
+    let mut sim = QuantumSimulator::new(3);
+    let targets = sim.get_qubits();
+    qaoa::prepare(&mut sim, &problem, &parameters, &targets);
+    let expected = sim.snapshot();
+    sim.reset();
+    circuit.run(&mut sim);
+    for (a, b) in sim
+        .snapshot()
+        .amplitudes()
+        .iter()
+        .zip(expected.amplitudes())
+    {
+        assert!((a - b).norm() < EPS);
+    }
+}
+
+#[test]
+#[should_panic(expected = "pairs of gamma and beta")]
+fn odd_parameters() {
+    let mut sim = QuantumSimulator::new(3);
+    qaoa::expectation(&mut sim, &example(), &[0.1, 0.2, 0.3]);
+}
@@ -0,0 +1,179 @@
+extern crate rusq;
+
+use rusq::observable::Pauli;
+use rusq::prelude::*;
+use rusq::qec::repetition::{BitFlipCode, PhaseFlipCode};
+use rusq::qec::shor::ShorCode;
+use rusq::qec::steane::SteaneCode;
+use rusq::qec::{self, StabilizerCode};
+
+fn inject<M: SingleGateApplicator>(machine: &mut M, qubits: &[Qubit], error: &[(usize, Pauli)]) {
+    for &(k, pauli) in error {
+        match pauli {
+            Pauli::I => {}
+            Pauli::X => machine.X(&qubits[k]),
+            Pauli::Y => machine.Y(&qubits[k]),
+            Pauli::Z => machine.Z(&qubits[k]),
+        }
+    }
+}
+
+///
+/// Encodes a state, injects `error`, corrects it, decodes, and returns the syndrome and
+/// the probability that the state is recovered with the ancillas reset.
+///
+fn recover<C: StabilizerCode>(
+    code: &C,
+    error: &[(usize, Pauli)],
+    ancillas_len: usize,
+) -> (usize, f64) {
+    let n = code.qubits_len();
+    let mut sim = QuantumSimulator::with_seed(n + ancillas_len, 5);
+    let qubits = sim.get_qubits();
+    let (data, ancillas) = qubits.split_at(n);
+
+    sim.ry(0.7, &data[0]);
+    sim.rz(0.3, &data[0]);
+    code.encode(&mut sim, data);
+    inject(&mut sim, data, error);
+
+    let syndrome = code.correct(&mut sim, data, ancillas);
+    code.decode(&mut sim, data);
+    sim.rz(-0.3, &data[0]);
+    sim.ry(-0.7, &data[0]);
+
+    (syndrome, sim.snapshot().amplitudes()[0].norm_sqr())
+}
+
+fn single_errors(qubits_len: usize, paulis: &[Pauli]) -> Vec<Vec<(usize, Pauli)>> {
+    let mut errors = vec![vec![]];
+    for &pauli in paulis {
+        errors.extend((0..qubits_len).map(|k| vec![(k, pauli)]));
+    }
+    errors
+}
+
+fn assert_corrects<C: StabilizerCode>(code: &C, paulis: &[Pauli], ancillas_len: usize) {
+    let stabilizers = code.stabilizers();
+    for error in single_errors(code.qubits_len(), paulis) {
+        let (syndrome, probability) = recover(code, &error, ancillas_len);
+        assert_eq!(syndrome, qec::syndrome(&stabilizers, &error), "{:?}", error);
+        assert_eq!(syndrome == 0, error.is_empty(), "{:?}", error);
+        assert!(
+            (probability - 1.).abs() < 1e-10,
+            "{:?}: {}",
+            error,
+            probability
+        );
+    }
+}
+
+#[test]
+fn bit_flip() {
+    assert_corrects(&BitFlipCode, &[Pauli::X], 1);
+    assert_corrects(&BitFlipCode, &[Pauli::X], 2);
+
+    // A phase flip is not detected and changes the logical state.
+    let (syndrome, probability) = recover(&BitFlipCode, &[(1, Pauli::Z)], 1);
+    assert_eq!(syndrome, 0);
+    assert!(probability < 0.9);
+}
+
+#[test]
+fn phase_flip() {
+    assert_corrects(&PhaseFlipCode, &[Pauli::Z], 1);
+
+    let (syndrome, probability) = recover(&PhaseFlipCode, &[(0, Pauli::X)], 1);
+    assert_eq!(syndrome, 0);
+    assert!(probability < 0.9);
+}
+
+#[test]
+fn shor() {
+    assert_corrects(&ShorCode, &[Pauli::X, Pauli::Z, Pauli::Y], 1);
+
+    // The Z errors in a block have the same syndrome.
+    let stabilizers = ShorCode.stabilizers();
+    assert_eq!(
+        qec::syndrome(&stabilizers, &[(3, Pauli::Z)]),
+        qec::syndrome(&stabilizers, &[(5, Pauli::Z)])
+    );
+    assert_eq!(ShorCode.lookup_table().len(), 1 + 9 + 3 + 9);
+}
+
+#[test]
+fn steane() {
+    assert_corrects(&SteaneCode, &[Pauli::X, Pauli::Z, Pauli::Y], 1);
+    assert_corrects(&SteaneCode, &[Pauli::X, Pauli::Z, Pauli::Y], 6);
+
+    // The X errors flip the Z stabilizers in the lower bits, and vice versa.
+    let table = SteaneCode.lookup_table();
+    assert_eq!(table.len(), 1 + 3 * 7);
+    assert_eq!(table.correction(0), Some(&[][..]));
+    assert_eq!(table.correction(0b000_011), Some(&[(0, Pauli::X)][..]));
+    assert_eq!(table.correction(0b011_000), Some(&[(0, Pauli::Z)][..]));
+    assert_eq!(table.correction(0b111_111), Some(&[(3, Pauli::Y)][..]));
+}
+
+#[test]
+fn two_errors_are_not_corrected() {
+    // Two X errors on the Steane code look like another single error.
+    let (syndrome, probability) = recover(&SteaneCode, &[(0, Pauli::X), (1, Pauli::X)], 1);
+    assert_eq!(syndrome, 0b000_110);
+    assert!(probability < 0.9);
+}
+
+fn assert_stabilized<C: StabilizerCode>(code: &C) {
+    let mut sim = QuantumSimulator::new(code.qubits_len());
+    let qubits = sim.get_qubits();
+    sim.H(&qubits[0]);
+    code.encode(&mut sim, &qubits);
+
+    for stabilizer in code.stabilizers() {
+        assert!((stabilizer.expectation(&sim, &qubits) - 1.).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn stabilizers_of_codewords() {
+    // The encoded states are the +1 eigenstates of the stabilizers.
+    assert_stabilized(&BitFlipCode);
+    assert_stabilized(&PhaseFlipCode);
+    assert_stabilized(&ShorCode);
+    assert_stabilized(&SteaneCode);
+}
+
+#[test]
+fn correction_circuit() {
+    let code = SteaneCode;
+    let circuit = code.correction_circuit(1);
+    assert_eq!(circuit.qubits_len(), 8);
+    assert_eq!(circuit.clbits_len(), 6);
+
+    for error in single_errors(7, &[Pauli::X, Pauli::Z, Pauli::Y]) {
+        let mut sim = QuantumSimulator::with_seed(8, 3);
+        let qubits = sim.get_qubits();
+        let data = &qubits[..7];
+        sim.ry(1.1, &data[0]);
+        code.encode(&mut sim, data);
+        inject(&mut sim, data, &error);
+
+        let bits = circuit.run(&mut sim);
+        assert_eq!(
+            bits.value(&circuit.get_clbits(), Endianness::Little),
+            qec::syndrome(&code.stabilizers(), &error)
+        );
+
+        code.decode(&mut sim, data);
+        sim.ry(-1.1, &data[0]);
+        assert!((sim.snapshot().amplitudes()[0].norm_sqr() - 1.).abs() < 1e-10);
+    }
+}
+
+#[test]
+#[should_panic(expected = "at least one ancilla is needed")]
+fn no_ancillas() {
+    let mut sim = QuantumSimulator::new(3);
+    let qubits = sim.get_qubits();
+    BitFlipCode.measure_syndrome(&mut sim, &qubits, &[]);
+}
@@ -0,0 +1,141 @@
+extern crate num;
+extern crate rusq;
+
+use num::complex::Complex;
+use rusq::algorithms::qft::{self, QftOptions};
+use rusq::circuit::Circuit;
+use rusq::prelude::*;
+use std::f64::consts::PI;
+
+const EPS: f64 = 1e-10;
+
+/// Prepares `|x>` on a new simulator and returns the state after `f`.
+fn transform<F>(n: usize, x: usize, f: F) -> Vec<Complex<f64>>
+where
+    F: Fn(&mut QuantumSimulator, &[Qubit]),
+{
+    let mut sim = QuantumSimulator::new(n);
+    let register = sim.get_register();
+    register.set_integer(&mut sim, x, Endianness::Little);
+    f(&mut sim, &register);
+    sim.snapshot().amplitudes().to_vec()
+}
+
+fn assert_close(actual: &[Complex<f64>], expected: &[Complex<f64>]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).norm() < EPS, "{:?} != {:?}", actual, expected);
+    }
+}
+
+fn reverse_bits(y: usize, n: usize) -> usize {
+    (0..n).fold(0, |acc, k| acc | (((y >> k) & 1) << (n - 1 - k)))
+}
+
+///
+/// The expected result of the (approximate) QFT of `|x>`, which is a product state.
+/// The qubit `j` before the swaps carries the phase of `x / 2^(j + 1)`
+/// with the bits of `x` farther than `m` from `j` dropped.
+///
+fn expected(n: usize, x: usize, options: &QftOptions) -> Vec<Complex<f64>> {
+    let m = options.approximation.unwrap_or(n);
+    let phases = (0..n)
+        .map(|j| {
+            (0..=j)
+                .filter(|&k| j - k < m && (x >> k) & 1 == 1)
+                .map(|k| 2. * PI / (1u64 << (j - k + 1)) as f64)
+                .sum::<f64>()
+        })
+        .collect::<Vec<_>>();
+
+    let norm = 1. / ((1 << n) as f64).sqrt();
+    (0..1 << n)
+        .map(|y| {
+            let y = if options.swaps { reverse_bits(y, n) } else { y };
+            let phase = (0..n)
+                .filter(|&j| (y >> j) & 1 == 1)
+                .map(|j| phases[j])
+                .sum::<f64>();
+            Complex::from_polar(&norm, &phase)
+        })
+        .collect()
+}
+
+#[test]
+fn qft_is_dft() {
+    for n in 1..5 {
+        let size = 1 << n;
+        for x in 0..size {
+            let dft = (0..size)
+                .map(|y| {
+                    let phase = 2. * PI * (x * y) as f64 / size as f64;
+                    Complex::from_polar(&(1. / (size as f64).sqrt()), &phase)
+                })
+                .collect::<Vec<_>>();
+
+            assert_close(&transform(n, x, qft::qft), &dft);
+        }
+    }
+}
+
+#[test]
+fn iqft_is_inverse_dft() {
+    let n = 4;
+    let size = 1 << n;
+    for x in 0..size {
+        let dft = (0..size)
+            .map(|y| {
+                let phase = -2. * PI * (x * y) as f64 / size as f64;
+                Complex::from_polar(&(1. / (size as f64).sqrt()), &phase)
+            })
+            .collect::<Vec<_>>();
+
+        assert_close(&transform(n, x, qft::iqft), &dft);
+    }
+}
+
+#[test]
+fn qft_options() {
+    let n = 4;
+    for &approximation in &[None, Some(1), Some(2), Some(3)] {
+        for &swaps in &[true, false] {
+            let options = QftOptions {
+                approximation,
+                swaps,
+            };
+            for x in 0..1 << n {
+                assert_close(
+                    &transform(n, x, |sim, q| qft::qft_with(sim, q, &options)),
+                    &expected(n, x, &options),
+                );
+
+                let mut identity = vec![Complex::new(0., 0.); 1 << n];
+                identity[x] = Complex::new(1., 0.);
+                assert_close(
+                    &transform(n, x, |sim, q| {
+                        qft::qft_with(sim, q, &options);
+                        qft::iqft_with(sim, q, &options);
+                    }),
+                    &identity,
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn qft_on_circuit() {
+    let mut circuit = Circuit::new(3, 0);
+    let qubits = circuit.get_qubits();
+    qft::qft(&mut circuit, &qubits);
+    assert_eq!(circuit.instructions().len(), 3 + 3 + 1);
+
+    for x in 0..8 {
+        assert_close(
+            &transform(3, x, |sim, q| {
+                circuit.run_on(sim, q);
+            }),
+            &transform(3, x, qft::qft),
+        );
+    }
+}
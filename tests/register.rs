@@ -0,0 +1,83 @@
+extern crate rusq;
+
+use rusq::prelude::*;
+
+#[test]
+fn qubits_are_bound_to_machine() {
+    let sim1 = QuantumSimulator::new(2);
+    let sim2 = QuantumSimulator::new(2);
+
+    let qubits1 = sim1.get_qubits();
+    let qubits2 = sim2.get_qubits();
+
+    assert_eq!(qubits1[0].index(), qubits2[0].index());
+    assert_ne!(qubits1[0], qubits2[0]);
+    assert_eq!(qubits1, sim1.get_qubits());
+}
+
+#[test]
+fn register_slice_and_iterate() {
+    let sim = QuantumSimulator::new(5);
+    let register = sim.get_register();
+
+    assert_eq!(register.len(), 5);
+    assert_eq!(register.as_slice(), &sim.get_qubits()[..]);
+
+    let middle = register.slice(1..4);
+    assert_eq!(middle.len(), 3);
+    assert_eq!(
+        middle.iter().map(|q| q.index()).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(middle[0], register[1]);
+    assert_eq!(&register[3..], &sim.get_qubits()[3..]);
+    assert!(register.slice(2..2).is_empty());
+
+    let mut indices = vec![];
+    for qubit in &register {
+        indices.push(qubit.index());
+    }
+    assert_eq!(indices, vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn register_as_integer() {
+    let mut sim = QuantumSimulator::new(6);
+    let register = sim.get_register().slice(1..5);
+
+    for value in 0..16 {
+        register.set_integer(&mut sim, value, Endianness::Little);
+        assert_eq!(
+            register.measure_integer(&mut sim, Endianness::Little),
+            value
+        );
+        assert_eq!(
+            sim.measure(register.bit(0, Endianness::Little)),
+            if value & 1 == 1 {
+                MeasuredResult::One
+            } else {
+                MeasuredResult::Zero
+            }
+        );
+
+        register.set_integer(&mut sim, value, Endianness::Big);
+        assert_eq!(register.measure_integer(&mut sim, Endianness::Big), value);
+        assert_eq!(register.bit(0, Endianness::Big), &register[3]);
+    }
+
+    register.set_integer(&mut sim, 0b0011, Endianness::Little);
+    assert_eq!(register.measure_integer(&mut sim, Endianness::Big), 0b1100);
+}
+
+#[test]
+fn register_to_integer() {
+    let register = QuantumSimulator::new(3).get_register();
+    let results = [
+        MeasuredResult::One,
+        MeasuredResult::Zero,
+        MeasuredResult::Zero,
+    ];
+
+    assert_eq!(register.to_integer(&results, Endianness::Little), 1);
+    assert_eq!(register.to_integer(&results, Endianness::Big), 4);
+}
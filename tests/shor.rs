@@ -0,0 +1,140 @@
+extern crate rusq;
+
+use rusq::algorithms::shor;
+use rusq::prelude::*;
+
+#[test]
+fn modular_multiplication() {
+    let (a, modulus) = (7, 15);
+    for &controlled in &[false, true] {
+        let mut sim = QuantumSimulator::new(11);
+        let register = sim.get_register();
+        let control = &register[0];
+        let xs = register.slice(1..5);
+        let ancillas = register.slice(5..);
+        if controlled {
+            sim.X(control);
+        }
+        // The superposition of x in 0..8 with different phases
+        for (k, qubit) in xs.iter().take(3).enumerate() {
+            sim.H(qubit);
+            sim.phase(0.1 * (k + 1) as f64, qubit);
+        }
+        let before = sim.snapshot();
+
+        shor::modular_multiplication(&mut sim, a, modulus, control, &xs, &ancillas);
+
+        let after = sim.snapshot();
+        for (index, amplitude) in before.amplitudes().iter().enumerate() {
+            let x = (index >> 1) & 0b1111;
+            if x >= modulus as usize {
+                continue;
+            }
+            let y = if controlled { a as usize * x % 15 } else { x };
+            let moved = (index & !0b11110) | (y << 1);
+            assert!((after.amplitudes()[moved] - amplitude).norm() < 1e-10);
+        }
+    }
+}
+
+#[test]
+fn modular_exponentiation() {
+    let (a, modulus) = (2, 15);
+    let mut sim = QuantumSimulator::new(3 + 4 + 6);
+    let register = sim.get_register();
+    let exponent = register.slice(..3);
+    let xs = register.slice(3..7);
+    let ancillas = register.slice(7..);
+    for qubit in exponent.iter() {
+        sim.H(qubit);
+    }
+    sim.X(&xs[0]);
+
+    shor::modular_exponentiation(&mut sim, a, modulus, &exponent, &xs, &ancillas);
+
+    // The state is the uniform superposition of |e>|2^e mod 15>|0>.
+    let snapshot = sim.snapshot();
+    for (index, amplitude) in snapshot.amplitudes().iter().enumerate() {
+        let e = index & 0b111;
+        let expected = if index >> 3 == (1 << e) % 15 {
+            1. / 8.
+        } else {
+            0.
+        };
+        assert!((amplitude.norm_sqr() - expected).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn order_from_phase() {
+    // 7 has the order 4 modulo 15, so the phase is a multiple of 1/4.
+    assert_eq!(shor::order_from_phase(192, 8, 7, 15), Some(4));
+    // 2/4 gives the denominator 2, which is a divisor of the order.
+    assert_eq!(shor::order_from_phase(128, 8, 7, 15), Some(4));
+    assert_eq!(shor::order_from_phase(0, 8, 7, 15), None);
+    assert_eq!(shor::order_from_phase(64, 8, 7, 15), Some(4));
+    // 0.33 is close to 1/3, and 4 has the order 3 modulo 21.
+    assert_eq!(shor::order_from_phase(338, 10, 4, 21), Some(3));
+}
+
+#[test]
+fn find_order() {
+    let results = (0..4)
+        .map(|seed| shor::find_order(7, 15, seed))
+        .collect::<Vec<_>>();
+    // The phase is 0 with the probability of 1/4, which tells nothing.
+    assert!(results.iter().all(|r| r.is_none() || *r == Some(4)));
+    assert!(results.contains(&Some(4)));
+    // The measurements are reproducible.
+    assert_eq!(shor::find_order(7, 15, 2), results[2]);
+}
+
+#[test]
+fn factor() {
+    assert_eq!(shor::factor(15, 0), Some((3, 5)));
+    assert_eq!(shor::factor(21, 0), Some((3, 7)));
+    assert_eq!(shor::factor(49, 0), Some((7, 7)));
+    assert_eq!(shor::factor(22, 0), Some((2, 11)));
+    assert_eq!(shor::factor(13, 0), None);
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Returns the factors gcd(a^(r/2) - 1, n) and gcd(a^(r/2) + 1, n) from the order r found on
+/// the simulator, so that the quantum path of the factoring is exercised.
+fn factors_from_order(a: u64, n: u64, seed: u64) -> (u64, u64) {
+    let order = shor::find_order(a, n, seed).unwrap();
+    assert_eq!(order % 2, 0);
+    let half = (0..order / 2).fold(1, |x, _| x * a % n);
+    (gcd(half - 1, n), gcd(half + 1, n))
+}
+
+#[test]
+fn factor_by_order_finding() {
+    // 7 has the order 4 modulo 15, and 7^2 = 4.
+    assert_eq!(factors_from_order(7, 15, 0), (3, 5));
+    // 2 has the order 6 modulo 21, and 2^3 = 8.
+    assert_eq!(factors_from_order(2, 21, 0), (7, 3));
+}
+
+#[test]
+fn factor_35_by_order_finding() {
+    // 6 has the order 2 modulo 35, and 6^1 = 6. Only the first controlled multiplication
+    // is simulated since the larger powers of 6 are one.
+    // The phase is 0 or 1/2, and the seed gives 1/2.
+    assert_eq!(factors_from_order(6, 35, 1), (5, 7));
+}
+
+#[test]
+#[ignore]
+fn factor_35() {
+    // This takes minutes without optimization: cargo test --release -- --ignored
+    assert_eq!(factors_from_order(2, 35, 0), (7, 5));
+    assert_eq!(shor::factor(35, 0), Some((5, 7)));
+}
@@ -0,0 +1,120 @@
+extern crate ndarray;
+extern crate num;
+extern crate rusq;
+
+use ndarray::prelude::*;
+use num::complex::Complex;
+use rusq::circuit::Circuit;
+use rusq::prelude::*;
+use rusq::simulator::unitary;
+use rusq::synthesis::solovay_kitaev::{Approximation, CliffordT, SolovayKitaev};
+use rusq::RusqError;
+
+/// The distance up to the global phase, sqrt(2 - |tr(U^dagger V)|)
+fn distance(u: &Array2<Complex<f64>>, v: &Array2<Complex<f64>>) -> f64 {
+    let trace = u
+        .iter()
+        .zip(v.iter())
+        .fold(Complex::new(0., 0.), |acc, (x, y)| acc + x.conj() * y);
+    (2. - trace.norm()).max(0.).sqrt()
+}
+
+fn rotations(phi: f64, theta: f64, lambda: f64) -> Array2<Complex<f64>> {
+    let mut circuit = Circuit::new(1, 0);
+    let qubit = circuit.get_qubits()[0];
+    circuit.rz(lambda, &qubit);
+    circuit.ry(theta, &qubit);
+    circuit.rz(phi, &qubit);
+    unitary::circuit_unitary(&circuit, 1)
+}
+
+/// Checks the reported error against the unitary of the gates.
+/// The square root amplifies the rounding errors of the long sequences.
+fn check(matrix: &Array2<Complex<f64>>, approximation: &Approximation) {
+    let mut sim = UnitarySimulator::new(1);
+    let qubit = sim.get_qubits()[0];
+    approximation.apply(&mut sim, &qubit);
+    assert!((distance(matrix, &sim.unitary()) - approximation.error).abs() < 1e-5);
+
+    let circuit = unitary::circuit_unitary(&approximation.to_circuit(), 1);
+    assert!(distance(&circuit, &sim.unitary()) < 1e-5);
+}
+
+#[test]
+fn net_len() {
+    for k in 0..5 {
+        assert_eq!(SolovayKitaev::new(k).net_len(), 24 * (3 * (1 << k) - 2));
+    }
+}
+
+#[test]
+fn exact_gates() {
+    let sk = SolovayKitaev::new(4);
+    let mut circuit = Circuit::new(1, 0);
+    let qubit = circuit.get_qubits()[0];
+    circuit.H(&qubit);
+    circuit.phase(std::f64::consts::FRAC_PI_4, &qubit);
+    circuit.H(&qubit);
+    let matrix = unitary::circuit_unitary(&circuit, 1);
+
+    let approximation = sk.approximate(&matrix, 1e-10).unwrap();
+    assert!(approximation.error < 1e-5);
+    assert_eq!(approximation.t_count(), 1);
+    check(&matrix, &approximation);
+
+    let identity = Array2::eye(2);
+    let approximation = sk.approximate(&identity, 1e-10).unwrap();
+    assert_eq!(approximation.gates, vec![]);
+}
+
+#[test]
+fn rotations_within_precision() {
+    let sk = SolovayKitaev::new(8);
+    let angles = [
+        (0.3, 0., 0.),
+        (0.1, 1.2, -0.7),
+        (2.5, 0.4, 1.9),
+        (-1.3, 2.9, 0.2),
+    ];
+    for &(phi, theta, lambda) in &angles {
+        let matrix = rotations(phi, theta, lambda);
+        let approximation = sk.approximate(&matrix, 1e-3).unwrap();
+        assert!(approximation.error <= 1e-3, "{}", approximation.error);
+        check(&matrix, &approximation);
+    }
+}
+
+#[test]
+fn errors_decrease() {
+    let sk = SolovayKitaev::new(6);
+    let matrix = rotations(0.7, 0.2, 0.);
+    let coarse = sk.approximate(&matrix, 1e-1).unwrap();
+    let fine = sk.approximate(&matrix, 1e-4).unwrap();
+    assert!(coarse.error <= 1e-1);
+    assert!(fine.error <= 1e-4);
+    assert!(fine.gates.len() > coarse.gates.len());
+    check(&matrix, &fine);
+
+    // The adjacent inverses are cancelled.
+    for pair in fine.gates.windows(2) {
+        assert_ne!(pair[0], pair[1].inverse());
+    }
+}
+
+#[test]
+fn invalid_matrices() {
+    let sk = SolovayKitaev::new(0);
+    let matrix = Array2::from_elem((2, 2), Complex::new(1., 0.));
+    assert_eq!(
+        sk.approximate(&matrix, 1e-2),
+        Err(RusqError::NonUnitaryMatrix)
+    );
+    assert_eq!(
+        sk.approximate(&Array2::eye(4), 1e-2),
+        Err(RusqError::InvalidMatrixShape {
+            expected: (2, 2),
+            actual: (4, 4),
+        })
+    );
+    assert_eq!(CliffordT::T.inverse(), CliffordT::Tdg);
+}
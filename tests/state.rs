@@ -0,0 +1,116 @@
+#[macro_use]
+extern crate rusq;
+extern crate ndarray;
+extern crate num;
+
+use ndarray::prelude::*;
+use num::complex::Complex;
+use rusq::prelude::*;
+use rusq::state::{self, State};
+
+const EPS: f64 = 1e-10;
+
+fn c(re: f64, im: f64) -> Complex<f64> {
+    Complex::new(re, im)
+}
+
+fn diagonal(p: f64, q: f64) -> Array2<Complex<f64>> {
+    arr2(&[[c(p, 0.), c(0., 0.)], [c(0., 0.), c(q, 0.)]])
+}
+
+#[test]
+fn pure_states() {
+    let mut sim = QuantumSimulator::new(2);
+    let qubits = sim.get_qubits();
+    sim.H(&qubits[1]);
+
+    let zero = vec![c(1., 0.), c(0., 0.), c(0., 0.), c(0., 0.)];
+    let plus = vec![
+        c(0.5f64.sqrt(), 0.),
+        c(0., 0.),
+        c(0., 0.5f64.sqrt()),
+        c(0., 0.),
+    ];
+    assert!((state::fidelity(&sim, &zero) - 0.5).abs() < EPS);
+    assert!((state::trace_distance(&sim, &zero) - 0.5f64.sqrt()).abs() < EPS);
+    // The relative phase i is not the global one.
+    assert!((state::fidelity(&sim, &plus) - 0.5).abs() < EPS);
+    assert!(!state::equal_up_to_global_phase(&sim, &plus, 1e-6));
+
+    let shifted: Vec<_> = sim
+        .snapshot()
+        .amplitudes()
+        .iter()
+        .map(|x| x * Complex::from_polar(&1., &0.7))
+        .collect();
+    assert!((state::fidelity(&sim, &shifted[..]) - 1.).abs() < EPS);
+    assert!(state::trace_distance(&sim, &shifted).abs() < EPS);
+    assert!(state::equal_up_to_global_phase(&sim, &shifted, EPS));
+    assert!(state::equal_up_to_global_phase(
+        &Array1::from_vec(shifted),
+        &sim.snapshot(),
+        EPS
+    ));
+
+    sim.X(&qubits[0]);
+    assert!(state::fidelity(&sim, &zero).abs() < EPS);
+    assert!((state::trace_distance(&zero, &sim) - 1.).abs() < EPS);
+}
+
+#[test]
+fn mixed_states() {
+    let mixed = diagonal(0.5, 0.5);
+    let zero = vec![c(1., 0.), c(0., 0.)];
+    let plus = State::Pure(vec![c(0.5f64.sqrt(), 0.); 2]);
+
+    assert!((state::fidelity(&mixed, &zero) - 0.5).abs() < EPS);
+    assert!((state::fidelity(&zero, &mixed) - 0.5).abs() < EPS);
+    assert!((state::trace_distance(&mixed, &zero) - 0.5).abs() < EPS);
+    assert!((state::trace_distance(&plus, &mixed) - 0.5).abs() < EPS);
+
+    // The pure states as the density matrices give the same values.
+    let rho = State::Mixed(State::Pure(zero.clone()).density_matrix());
+    let sigma = State::Mixed(plus.density_matrix());
+    assert!((state::fidelity(&rho, &sigma) - 0.5).abs() < 1e-8);
+    assert!((state::trace_distance(&rho, &sigma) - 0.5f64.sqrt()).abs() < 1e-8);
+    assert!((state::fidelity(&rho, &mixed) - 0.5).abs() < 1e-8);
+    assert!(state::equal_up_to_global_phase(&rho, &zero, EPS));
+    assert!(!state::equal_up_to_global_phase(&rho, &mixed, 0.1));
+
+    // The fidelity between diagonal states is the squared Bhattacharyya coefficient.
+    let a = diagonal(0.9, 0.1);
+    let b = diagonal(0.4, 0.6);
+    let expected = ((0.36f64).sqrt() + (0.06f64).sqrt()).powi(2);
+    assert!((state::fidelity(&a, &b) - expected).abs() < 1e-8);
+    assert!((state::trace_distance(&a, &b) - 0.5).abs() < 1e-8);
+}
+
+#[test]
+#[should_panic]
+fn different_dimensions() {
+    let sim = QuantumSimulator::new(2);
+    state::fidelity(&sim, &vec![c(1., 0.), c(0., 0.)]);
+}
+
+#[test]
+fn assertions() {
+    let mut sim = QuantumSimulator::new(1);
+    let qubit = &sim.get_qubits()[0];
+    let initial = sim.snapshot();
+    sim.rx(1e-5, qubit);
+
+    assert_state_approx_eq!(sim, initial, 1e-4);
+    assert_state_approx_ne!(sim, initial);
+    assert_state_approx_ne!(&sim, &initial, 1e-6);
+}
+
+#[test]
+#[should_panic(expected = "the states are not approximately equal")]
+fn assertion_failure() {
+    let mut sim = QuantumSimulator::new(1);
+    let qubit = &sim.get_qubits()[0];
+    let initial = sim.snapshot();
+    sim.rx(1e-5, qubit);
+
+    assert_state_approx_eq!(sim, initial);
+}
@@ -0,0 +1,166 @@
+extern crate num;
+extern crate rusq;
+
+use num::complex::Complex;
+use rusq::circuit::{Circuit, Gate, Operation};
+use rusq::prelude::*;
+use rusq::state;
+use rusq::synthesis::state_preparation::prepare_state;
+
+const EPS: f64 = 1e-10;
+
+fn random_state(n: usize, seed: u64, is_real: bool) -> Vec<Complex<f64>> {
+    let mut state = seed;
+    let mut random = || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+    };
+    let amplitudes: Vec<_> = (0..1 << n)
+        .map(|_| {
+            let re = random();
+            Complex::new(re, if is_real { 0. } else { random() })
+        })
+        .collect();
+    let norm = amplitudes.iter().map(|x| x.norm_sqr()).sum::<f64>().sqrt();
+    amplitudes.iter().map(|x| x / norm).collect()
+}
+
+fn prepared(amplitudes: &[Complex<f64>]) -> (QuantumSimulator, Circuit) {
+    let n = amplitudes.len().trailing_zeros() as usize;
+    let mut circuit = Circuit::new(n, 0);
+    let qubits = circuit.get_qubits();
+    prepare_state(&mut circuit, &qubits, amplitudes);
+
+    let mut sim = QuantumSimulator::new(n);
+    circuit.run(&mut sim);
+    assert!((state::fidelity(&sim, &amplitudes.to_vec()) - 1.).abs() < EPS);
+    (sim, circuit)
+}
+
+fn count<F: Fn(&Gate) -> bool>(circuit: &Circuit, f: F) -> usize {
+    circuit
+        .instructions()
+        .iter()
+        .filter(|instruction| match instruction.operation {
+            Operation::Gate(ref gate) => f(gate),
+            _ => false,
+        })
+        .count()
+}
+
+fn cnots(circuit: &Circuit) -> usize {
+    count(circuit, |gate| matches!(*gate, Gate::CNOT(..)))
+}
+
+#[test]
+fn random_states() {
+    for n in 0..6 {
+        for seed in 0..5 {
+            let (_, circuit) = prepared(&random_state(n, seed, false));
+            // 2^n - 2 CNOTs for each of the magnitudes and the phases
+            assert!(cnots(&circuit) <= 2 * (1usize << n).saturating_sub(2));
+        }
+    }
+}
+
+#[test]
+fn real_states() {
+    for n in 1..6 {
+        let amplitudes = random_state(n, 10 + n as u64, true);
+        let (_, circuit) = prepared(&amplitudes);
+        assert_eq!(count(&circuit, |gate| matches!(*gate, Gate::RZ(..))), 0);
+        assert!(cnots(&circuit) <= (1 << n) - 2);
+
+        // A global phase does not matter.
+        let phase = Complex::from_polar(&1., &0.7);
+        let rotated: Vec<_> = amplitudes.iter().map(|x| x * phase).collect();
+        let (_, circuit) = prepared(&rotated);
+        assert_eq!(count(&circuit, |gate| matches!(*gate, Gate::RZ(..))), 0);
+    }
+}
+
+#[test]
+fn sparse_states() {
+    let zero = Complex::new(0., 0.);
+    let basis = |n: usize, x: usize| {
+        let mut amplitudes = vec![zero; 1 << n];
+        amplitudes[x] = Complex::new(1., 0.);
+        amplitudes
+    };
+
+    let (mut sim, circuit) = prepared(&basis(4, 0b1011));
+    assert_eq!(cnots(&circuit), 0);
+    assert_eq!(
+        sim.get_register()
+            .measure_integer(&mut sim, Endianness::Little),
+        0b1011
+    );
+
+    // GHZ
+    let mut ghz = vec![zero; 8];
+    ghz[0] = Complex::new(0.5f64.sqrt(), 0.);
+    ghz[7] = Complex::new(0., 0.5f64.sqrt());
+    let (_, circuit) = prepared(&ghz);
+    assert!(cnots(&circuit) <= 4);
+
+    // W
+    let mut w = vec![zero; 8];
+    for &x in &[1, 2, 4] {
+        w[x] = Complex::new((1. / 3f64).sqrt(), 0.);
+    }
+    let (_, circuit) = prepared(&w);
+    assert!(cnots(&circuit) <= 6);
+
+    // A product state on the lower qubits with the highest one |1>
+    let plus = Complex::new(0.5, 0.);
+    let product: Vec<_> = (0..8).map(|x| if x >= 4 { plus } else { zero }).collect();
+    let (_, circuit) = prepared(&product);
+    assert_eq!(cnots(&circuit), 0);
+}
+
+#[test]
+fn subset_of_qubits() {
+    let amplitudes = random_state(3, 3, false);
+    let mut sim = QuantumSimulator::new(4);
+    let qubits = sim.get_qubits();
+    prepare_state(&mut sim, &[qubits[2], qubits[0], qubits[3]], &amplitudes);
+
+    // Bit k of the amplitudes is qubits[order[k]] of the simulator.
+    let order = [2, 0, 3];
+    let expected: Vec<_> = (0..16)
+        .map(|i| {
+            if i & 0b10 != 0 {
+                return Complex::new(0., 0.);
+            }
+            let x = (0..3).fold(0, |x, k| x | ((i >> order[k]) & 1) << k);
+            amplitudes[x]
+        })
+        .collect();
+    assert!((state::fidelity(&sim, &expected) - 1.).abs() < EPS);
+}
+
+#[test]
+#[should_panic(expected = "not normalized")]
+fn unnormalized() {
+    let mut sim = QuantumSimulator::new(1);
+    let qubits = sim.get_qubits();
+    prepare_state(
+        &mut sim,
+        &qubits,
+        &[Complex::new(1., 0.), Complex::new(1., 0.)],
+    );
+}
+
+#[test]
+#[should_panic(expected = "4 amplitudes are needed for 2 qubits")]
+fn wrong_length() {
+    let mut sim = QuantumSimulator::new(2);
+    let qubits = sim.get_qubits();
+    prepare_state(
+        &mut sim,
+        &qubits,
+        &[Complex::new(1., 0.), Complex::new(0., 0.)],
+    );
+}
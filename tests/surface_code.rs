@@ -0,0 +1,248 @@
+extern crate rusq;
+
+use rusq::prelude::*;
+use rusq::qec::dem::DetectorErrorModel;
+use rusq::qec::frame::{Instruction, NoisyCircuit, PauliChannel};
+use rusq::qec::matching::{minimum_weight_perfect_matching, MatchingDecoder};
+use rusq::qec::surface::{Basis, NoiseModel, SurfaceCode};
+
+#[test]
+fn layout() {
+    for d in 2..8 {
+        let code = SurfaceCode::new(d);
+        assert_eq!(code.stabilizers().len(), d * d - 1);
+        assert_eq!(code.qubits_len(), 2 * d * d - 1);
+
+        // The stabilizers commute with each other and with the logical operators.
+        let supports: Vec<_> = code
+            .pauli_strings()
+            .iter()
+            .map(|s| s.paulis.iter().map(|p| p.0).collect::<Vec<_>>())
+            .collect();
+        let overlap = |a: &[usize], b: &[usize]| a.iter().filter(|q| b.contains(q)).count();
+        for (s, a) in code.stabilizers().iter().zip(&supports) {
+            for (t, b) in code.stabilizers().iter().zip(&supports) {
+                if s.basis != t.basis {
+                    assert_eq!(overlap(a, b) % 2, 0);
+                }
+            }
+            let other = match s.basis {
+                Basis::X => Basis::Z,
+                Basis::Z => Basis::X,
+            };
+            assert_eq!(overlap(a, &code.logical(other)) % 2, 0);
+        }
+        assert_eq!(overlap(&code.logical(Basis::X), &code.logical(Basis::Z)), 1);
+    }
+}
+
+/// Runs the circuit on the state vector simulator, and returns the measurement results.
+fn run(circuit: &NoisyCircuit, seed: u64) -> Vec<bool> {
+    let mut sim = QuantumSimulator::with_seed(circuit.qubits_len(), seed);
+    let qubits = sim.get_qubits();
+    let mut results = vec![];
+    for &instruction in circuit.instructions() {
+        match instruction {
+            Instruction::Reset(q) => {
+                if sim.measure(&qubits[q]) == MeasuredResult::One {
+                    sim.X(&qubits[q]);
+                }
+            }
+            Instruction::H(q) => sim.H(&qubits[q]),
+            Instruction::CNOT(c, t) => sim.CNOT(&qubits[c], &qubits[t]),
+            Instruction::Measure(q, _) => {
+                results.push(sim.measure(&qubits[q]) == MeasuredResult::One);
+            }
+            _ => panic!("the circuit is noisy"),
+        }
+    }
+    results
+}
+
+#[test]
+fn deterministic_detectors() {
+    for &basis in &[Basis::Z, Basis::X] {
+        let circuit = SurfaceCode::new(3).memory_experiment(2, basis, &NoiseModel::noiseless());
+        assert_eq!(circuit.detectors().len(), 4 + 8 + 4);
+        for seed in 0..2 {
+            let results = run(&circuit, seed);
+            let parity = |measurements: &[usize]| {
+                measurements.iter().filter(|&&m| results[m]).count() % 2 == 1
+            };
+            for detector in circuit.detectors() {
+                assert!(!parity(detector), "{:?} {:?}", basis, detector);
+            }
+            assert!(!parity(circuit.observable()));
+        }
+    }
+}
+
+#[test]
+fn noiseless_samples() {
+    for &d in &[3, 5] {
+        for &basis in &[Basis::Z, Basis::X] {
+            let noise = NoiseModel::circuit_level(0.01);
+            let circuit = SurfaceCode::new(d)
+                .memory_experiment(d, basis, &noise)
+                .without_noise();
+            for sample in circuit.sample(10, 0) {
+                assert!(sample.detectors.iter().all(|&flip| !flip));
+                assert!(!sample.observable);
+            }
+        }
+    }
+}
+
+#[test]
+fn frame_sampling() {
+    // A bit flip before the measurement is seen by the detector and the observable.
+    let mut circuit = NoisyCircuit::new(2);
+    circuit.push(Instruction::H(0));
+    circuit.push(Instruction::Pauli(0, PauliChannel::phase_flip(1.)));
+    circuit.push(Instruction::H(0));
+    circuit.push(Instruction::CNOT(0, 1));
+    let m0 = circuit.push(Instruction::Measure(0, 0.)).unwrap();
+    let m1 = circuit.push(Instruction::Measure(1, 0.)).unwrap();
+    circuit.add_detector(vec![m0]);
+    circuit.add_detector(vec![m0, m1]);
+    circuit.set_observable(vec![m1]);
+
+    for sample in circuit.sample(5, 1) {
+        assert_eq!(sample.detectors, vec![true, false]);
+        assert!(sample.observable);
+    }
+
+    let model = DetectorErrorModel::from_circuit(&circuit);
+    assert_eq!(model.errors().len(), 1);
+    let symptom = model.errors()[0].symptom();
+    assert_eq!(symptom.detectors, vec![0]);
+    assert!(symptom.observable);
+}
+
+#[test]
+fn graphlike_model() {
+    let code = SurfaceCode::new(3);
+    for &basis in &[Basis::Z, Basis::X] {
+        let circuit = code.memory_experiment(3, basis, &NoiseModel::circuit_level(0.001));
+        let model = DetectorErrorModel::from_circuit(&circuit);
+        assert_eq!(model.detectors_len(), circuit.detectors().len());
+        for error in model.errors() {
+            assert!(error.probability > 0. && error.probability < 0.01);
+            for symptom in &error.symptoms {
+                assert!(!symptom.detectors.is_empty() && symptom.detectors.len() <= 2);
+            }
+        }
+
+        // Any single fault is corrected at the distance 3.
+        let decoder = MatchingDecoder::new(&model);
+        for error in model.errors() {
+            let symptom = error.symptom();
+            let mut detectors = vec![false; model.detectors_len()];
+            for &d in &symptom.detectors {
+                detectors[d] = true;
+            }
+            assert_eq!(
+                decoder.decode(&detectors),
+                symptom.observable,
+                "{:?}",
+                error
+            );
+        }
+    }
+}
+
+fn brute_force(nodes: usize, edges: &[(usize, usize, i64)]) -> Option<i64> {
+    fn search(matched: &mut Vec<bool>, edges: &[(usize, usize, i64)]) -> Option<i64> {
+        let v = match matched.iter().position(|&m| !m) {
+            Some(v) => v,
+            None => return Some(0),
+        };
+        let mut best = None;
+        for &(i, j, w) in edges {
+            let u = if i == v {
+                j
+            } else if j == v {
+                i
+            } else {
+                continue;
+            };
+            if matched[u] {
+                continue;
+            }
+            matched[v] = true;
+            matched[u] = true;
+            if let Some(rest) = search(matched, edges) {
+                if best.is_none_or(|b| w + rest < b) {
+                    best = Some(w + rest);
+                }
+            }
+            matched[v] = false;
+            matched[u] = false;
+        }
+        best
+    }
+    search(&mut vec![false; nodes], edges)
+}
+
+#[test]
+fn blossom() {
+    let mut state = 12345u64;
+    let mut random = |n: u64| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) % n
+    };
+
+    for _ in 0..300 {
+        let nodes = 2 * (1 + random(4) as usize);
+        let mut edges = vec![];
+        for i in 0..nodes {
+            for j in i + 1..nodes {
+                if random(3) > 0 {
+                    edges.push((i, j, random(20) as i64));
+                }
+            }
+        }
+
+        let expected = brute_force(nodes, &edges);
+        let mate = minimum_weight_perfect_matching(nodes, &edges);
+        assert_eq!(mate.is_some(), expected.is_some());
+        if let Some(mate) = mate {
+            let mut weight = 0;
+            for (i, &j) in mate.iter().enumerate() {
+                assert_eq!(mate[j], i);
+                if i < j {
+                    weight += edges
+                        .iter()
+                        .filter(|e| (e.0, e.1) == (i, j))
+                        .map(|e| e.2)
+                        .next()
+                        .expect("not an edge");
+                }
+            }
+            assert_eq!(Some(weight), expected, "{:?}", edges);
+        }
+    }
+}
+
+#[test]
+fn threshold() {
+    // Below the threshold, the larger code has the lower logical error rate.
+    let noise = NoiseModel::circuit_level(0.003);
+    let rates: Vec<_> = [3, 5]
+        .iter()
+        .map(|&d| {
+            let circuit = SurfaceCode::new(d).memory_experiment(d, Basis::Z, &noise);
+            let decoder = MatchingDecoder::new(&DetectorErrorModel::from_circuit(&circuit));
+            decoder.logical_error_rate(&circuit, 4000, 7)
+        })
+        .collect();
+    assert!(rates[0] > 0., "{:?}", rates);
+    assert!(rates[1] < rates[0], "{:?}", rates);
+
+    let circuit =
+        SurfaceCode::new(3).memory_experiment(3, Basis::X, &NoiseModel::phenomenological(0.01));
+    let decoder = MatchingDecoder::new(&DetectorErrorModel::from_circuit(&circuit));
+    assert!(decoder.logical_error_rate(&circuit, 2000, 3) < 0.05);
+}
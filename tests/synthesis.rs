@@ -0,0 +1,391 @@
+#[macro_use]
+extern crate ndarray;
+extern crate num;
+extern crate rusq;
+
+use ndarray::prelude::*;
+use num::complex::Complex;
+use rusq::circuit::{Circuit, Gate, Operation};
+use rusq::gates::double::{CNOT, SQSWAP, SWAP};
+use rusq::gates::single::{H, X};
+use rusq::gates::triple::{CCNOT, CSWAP};
+use rusq::prelude::*;
+use rusq::simulator::unitary;
+use rusq::state;
+use rusq::synthesis::kak::KakDecomposition;
+use rusq::synthesis::zyz::ZyzDecomposition;
+use rusq::synthesis::{multiplexor, qsd};
+use rusq::RusqError;
+
+const EPS: f64 = 1e-8;
+
+/// Returns a random unitary by the Gram-Schmidt process on a random matrix.
+fn random_unitary(n: usize, seed: u64) -> Array2<Complex<f64>> {
+    let mut state = seed;
+    let mut random = || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+    };
+    let mut matrix = Array2::from_shape_fn((n, n), |_| Complex::new(random(), random()));
+    for j in 0..n {
+        for k in 0..j {
+            let overlap = (0..n).fold(Complex::new(0., 0.), |acc, i| {
+                acc + matrix[[i, k]].conj() * matrix[[i, j]]
+            });
+            for i in 0..n {
+                let x = matrix[[i, k]];
+                matrix[[i, j]] -= overlap * x;
+            }
+        }
+        let norm = (0..n)
+            .map(|i| matrix[[i, j]].norm_sqr())
+            .sum::<f64>()
+            .sqrt();
+        matrix.column_mut(j).mapv_inplace(|x| x / norm);
+    }
+    matrix
+}
+
+fn kron(a: &Array2<Complex<f64>>, b: &Array2<Complex<f64>>) -> Array2<Complex<f64>> {
+    Array2::from_shape_fn((4, 4), |(i, j)| a[[i / 2, j / 2]] * b[[i % 2, j % 2]])
+}
+
+fn assert_matrix_eq(a: &Array2<Complex<f64>>, b: &Array2<Complex<f64>>) {
+    for (x, y) in a.iter().zip(b.iter()) {
+        assert!((x - y).norm() < EPS, "{} != {}", a, b);
+    }
+}
+
+/// Returns a circuit applying `matrix` as a single gate.
+fn gate_circuit(matrix: &Array2<Complex<f64>>) -> Circuit {
+    let n = matrix.rows().trailing_zeros() as usize;
+    let mut circuit = Circuit::new(n, 0);
+    let qubits = circuit.get_qubits();
+    circuit.apply_multi(matrix, &qubits);
+    circuit
+}
+
+fn cnots(circuit: &Circuit) -> usize {
+    circuit
+        .instructions()
+        .iter()
+        .filter(|instruction| matches!(instruction.operation, Operation::Gate(Gate::CNOT(..))))
+        .count()
+}
+
+/// Returns e^{i(c1 XX + c2 YY + c3 ZZ)} by the commuting rotations.
+fn interaction(c1: f64, c2: f64, c3: f64) -> Array2<Complex<f64>> {
+    let mut circuit = Circuit::new(2, 0);
+    let qubits = circuit.get_qubits();
+    circuit.H(&qubits[0]);
+    circuit.H(&qubits[1]);
+    circuit.rzz(-2. * c1, &qubits[0], &qubits[1]);
+    circuit.H(&qubits[0]);
+    circuit.H(&qubits[1]);
+    circuit.rx(std::f64::consts::FRAC_PI_2, &qubits[0]);
+    circuit.rx(std::f64::consts::FRAC_PI_2, &qubits[1]);
+    circuit.rzz(-2. * c2, &qubits[0], &qubits[1]);
+    circuit.rx(-std::f64::consts::FRAC_PI_2, &qubits[0]);
+    circuit.rx(-std::f64::consts::FRAC_PI_2, &qubits[1]);
+    circuit.rzz(-2. * c3, &qubits[0], &qubits[1]);
+    // The interaction is symmetric in the qubits.
+    unitary::circuit_unitary(&circuit, 2)
+}
+
+fn check_kak(matrix: &Array2<Complex<f64>>) -> KakDecomposition {
+    let kak = KakDecomposition::new(matrix).unwrap();
+    assert!(kak
+        .interaction
+        .iter()
+        .all(|c| c.abs() <= std::f64::consts::FRAC_PI_4 + EPS));
+
+    let [c1, c2, c3] = kak.interaction;
+    let interaction = interaction(c1, c2, c3);
+    let product = kron(&kak.after[0], &kak.after[1])
+        .dot(&interaction)
+        .dot(&kron(&kak.before[0], &kak.before[1]))
+        .mapv(|x| x * Complex::from_polar(&1., &kak.phase));
+    assert_matrix_eq(&product, matrix);
+
+    let circuit = kak.to_circuit();
+    assert_eq!(cnots(&circuit), kak.cnot_count());
+    assert!(unitary::circuits_equivalent(
+        &circuit,
+        &gate_circuit(matrix),
+        EPS
+    ));
+    kak
+}
+
+#[test]
+fn zyz() {
+    for seed in 0..20 {
+        let matrix = random_unitary(2, seed);
+        let zyz = ZyzDecomposition::new(&matrix).unwrap();
+        let circuit = zyz.to_circuit();
+        let product = unitary::circuit_unitary(&circuit, 1)
+            .mapv(|x| x * Complex::from_polar(&1., &zyz.phase));
+        assert_matrix_eq(&product, &matrix);
+
+        // U3(theta, phi, lambda)
+        let (c, s) = ((zyz.theta / 2.).cos(), (zyz.theta / 2.).sin());
+        let u3 = array![
+            [Complex::new(c, 0.), -Complex::from_polar(&s, &zyz.lambda)],
+            [
+                Complex::from_polar(&s, &zyz.phi),
+                Complex::from_polar(&c, &(zyz.phi + zyz.lambda))
+            ]
+        ];
+        assert_matrix_eq(
+            &u3.mapv(|x| x * Complex::from_polar(&1., &zyz.u3_phase())),
+            &matrix,
+        );
+    }
+
+    for matrix in &[&H.matrix, &X.matrix] {
+        let zyz = ZyzDecomposition::new(matrix).unwrap();
+        let mut circuit = Circuit::new(1, 0);
+        let qubit = circuit.get_qubits()[0];
+        circuit.apply_single(matrix, &qubit);
+        assert!(unitary::circuits_equivalent(
+            &zyz.to_circuit(),
+            &circuit,
+            EPS
+        ));
+    }
+
+    // Diagonal unitaries need a single rotation.
+    let mut circuit = Circuit::new(1, 0);
+    let qubit = circuit.get_qubits()[0];
+    circuit.phase(0.7, &qubit);
+    let matrix = unitary::circuit_unitary(&circuit, 1);
+    let zyz = ZyzDecomposition::new(&matrix).unwrap();
+    assert_eq!(zyz.to_circuit().instructions().len(), 1);
+}
+
+#[test]
+fn invalid_matrices() {
+    let matrix = array![
+        [Complex::new(1., 0.), Complex::new(1., 0.)],
+        [Complex::new(0., 0.), Complex::new(1., 0.)]
+    ];
+    assert_eq!(
+        ZyzDecomposition::new(&matrix),
+        Err(RusqError::NonUnitaryMatrix)
+    );
+    assert_eq!(
+        KakDecomposition::new(&matrix),
+        Err(RusqError::InvalidMatrixShape {
+            expected: (4, 4),
+            actual: (2, 2),
+        })
+    );
+}
+
+#[test]
+fn kak_random() {
+    for seed in 0..30 {
+        let kak = check_kak(&random_unitary(4, seed));
+        assert_eq!(kak.cnot_count(), 3);
+    }
+}
+
+#[test]
+fn kak_gates() {
+    assert_eq!(check_kak(&CNOT.matrix).cnot_count(), 1);
+    assert_eq!(check_kak(&SWAP.matrix).cnot_count(), 3);
+    assert_eq!(check_kak(&SQSWAP.matrix).cnot_count(), 3);
+
+    let mut circuit = Circuit::new(2, 0);
+    let qubits = circuit.get_qubits();
+    circuit.cphase(0.4, &qubits[0], &qubits[1]);
+    circuit.rzz(1.3, &qubits[1], &qubits[0]);
+    assert_eq!(check_kak(&unitary::circuit_unitary(&circuit, 2)).cnot_count(), 2);
+
+    let identity = Array2::eye(4);
+    assert_eq!(check_kak(&identity).cnot_count(), 0);
+}
+
+#[test]
+fn kak_local() {
+    for seed in 0..10 {
+        let (a, b) = (random_unitary(2, seed), random_unitary(2, seed + 100));
+        let kak = check_kak(&kron(&a, &b));
+        assert_eq!(kak.cnot_count(), 0);
+    }
+
+    // XX is local, but its interaction e^{i pi/2 XX} is reduced to the identity.
+    let xx = kron(&X.matrix, &X.matrix);
+    assert_eq!(check_kak(&xx).cnot_count(), 0);
+}
+
+#[test]
+fn kak_cnot_counts() {
+    use std::f64::consts::FRAC_PI_4;
+
+    let cases = [
+        ((FRAC_PI_4, 0., 0.), 1),
+        ((0., FRAC_PI_4, 0.), 1),
+        ((0., 0., FRAC_PI_4), 1),
+        ((0., -FRAC_PI_4 + 1e-12, 0.), 1),
+        ((0.3, -0.2, 0.), 2),
+        ((0.3, 0., 0.1), 2),
+        ((0., 0.7, -0.4), 2),
+        ((0.5, 0., 0.), 2),
+        ((FRAC_PI_4, 0.2, 0.1), 3),
+    ];
+    for (seed, &((c1, c2, c3), count)) in cases.iter().enumerate() {
+        let seed = seed as u64;
+        let (a0, b0) = (random_unitary(2, seed), random_unitary(2, seed + 100));
+        let (a1, b1) = (random_unitary(2, seed + 200), random_unitary(2, seed + 300));
+        let matrix = kron(&a1, &b1)
+            .dot(&interaction(c1, c2, c3))
+            .dot(&kron(&a0, &b0));
+        assert_eq!(check_kak(&matrix).cnot_count(), count, "{:?}", (c1, c2, c3));
+    }
+}
+
+#[test]
+fn kak_on_simulator() {
+    let matrix = random_unitary(4, 42);
+    let kak = KakDecomposition::new(&matrix).unwrap();
+
+    let mut expected = QuantumSimulator::new(3);
+    let mut actual = QuantumSimulator::new(3);
+    for sim in [&mut expected, &mut actual].iter_mut() {
+        let qubits = sim.get_qubits();
+        sim.H(&qubits[0]);
+        sim.ry(0.8, &qubits[1]);
+        sim.CNOT(&qubits[0], &qubits[2]);
+    }
+    let qubits = expected.get_qubits();
+    expected.apply_double(&matrix, &qubits[2], &qubits[0]);
+    let qubits = actual.get_qubits();
+    kak.apply(&mut actual, &qubits[2], &qubits[0]);
+
+    assert!((state::fidelity(&expected, &actual) - 1.).abs() < EPS);
+}
+
+#[test]
+fn multiplexed_rotations() {
+    let angles = [0.3, -1.2, 2.5, 0.7];
+    for &is_y in &[true, false] {
+        let mut circuit = Circuit::new(3, 0);
+        let qubits = circuit.get_qubits();
+        if is_y {
+            multiplexor::ry(&mut circuit, &angles, &qubits[..2], &qubits[2]);
+        } else {
+            multiplexor::rz(&mut circuit, &angles, &qubits[..2], &qubits[2]);
+        }
+        assert_eq!(cnots(&circuit), 4);
+
+        let mut expected = Circuit::new(3, 0);
+        let qubits = expected.get_qubits();
+        for (x, &angle) in angles.iter().enumerate() {
+            let mut rotation = Circuit::new(1, 0);
+            let qubit = rotation.get_qubits()[0];
+            if is_y {
+                rotation.ry(angle, &qubit);
+            } else {
+                rotation.rz(angle, &qubit);
+            }
+            let rotation = unitary::circuit_unitary(&rotation, 1);
+            // The block for the controls in |x>, where qubits[0] is the higher bit
+            let matrix = Array2::from_shape_fn((8, 8), |(i, j)| {
+                if i >> 1 == j >> 1 {
+                    if i >> 1 == x {
+                        rotation[[i & 1, j & 1]]
+                    } else if i == j {
+                        Complex::new(1., 0.)
+                    } else {
+                        Complex::new(0., 0.)
+                    }
+                } else {
+                    Complex::new(0., 0.)
+                }
+            });
+            expected.apply_multi(&matrix, &qubits);
+        }
+        assert!(unitary::circuits_equivalent(&circuit, &expected, EPS));
+    }
+}
+
+#[test]
+fn qsd_random() {
+    for &(n, seeds) in &[(1, 5), (2, 5), (3, 10), (4, 2)] {
+        for seed in 0..seeds {
+            let matrix = random_unitary(1 << n, seed);
+            let circuit = qsd::decompose(&matrix).unwrap();
+            assert!(unitary::circuits_equivalent(
+                &circuit,
+                &gate_circuit(&matrix),
+                EPS
+            ));
+            if n >= 2 {
+                // 9/16 4^n - 3/2 2^n
+                assert!(cnots(&circuit) <= 9 * (1 << (2 * n)) / 16 - 3 * (1 << n) / 2);
+            }
+        }
+    }
+}
+
+#[test]
+fn qsd_gates() {
+    let identity = Array2::eye(8);
+    for matrix in &[&CCNOT.matrix, &CSWAP.matrix, &identity] {
+        let circuit = qsd::decompose(matrix).unwrap();
+        assert!(unitary::circuits_equivalent(
+            &circuit,
+            &gate_circuit(matrix),
+            EPS
+        ));
+    }
+    assert_eq!(qsd::decompose(&identity).unwrap().instructions().len(), 0);
+
+    // A permutation with vanishing blocks in the cosine-sine decomposition
+    let mut circuit = Circuit::new(4, 0);
+    let qubits = circuit.get_qubits();
+    circuit.X(&qubits[3]);
+    circuit.CNOT(&qubits[3], &qubits[0]);
+    circuit.SWAP(&qubits[1], &qubits[2]);
+    circuit.CCNOT(&qubits[0], &qubits[2], &qubits[1]);
+    circuit.H(&qubits[2]);
+    let matrix = unitary::circuit_unitary(&circuit, 4);
+    let circuit = qsd::decompose(&matrix).unwrap();
+    assert!(unitary::circuits_equivalent(
+        &circuit,
+        &gate_circuit(&matrix),
+        EPS
+    ));
+}
+
+#[test]
+fn qsd_on_simulator() {
+    let matrix = random_unitary(8, 7);
+
+    let mut expected = QuantumSimulator::new(4);
+    let mut actual = QuantumSimulator::new(4);
+    for sim in [&mut expected, &mut actual].iter_mut() {
+        let qubits = sim.get_qubits();
+        sim.H(&qubits[1]);
+        sim.CNOT(&qubits[1], &qubits[3]);
+        sim.rx(0.4, &qubits[0]);
+    }
+    let qubits = expected.get_qubits();
+    let order = [qubits[3], qubits[0], qubits[1]];
+    expected.apply_multi(&matrix, &order);
+    let qubits = actual.get_qubits();
+    let order = [qubits[3], qubits[0], qubits[1]];
+    qsd::apply(&mut actual, &matrix, &order).unwrap();
+    assert!((state::fidelity(&expected, &actual) - 1.).abs() < EPS);
+
+    assert_eq!(
+        qsd::apply(&mut actual, &matrix, &order[..2]),
+        Err(RusqError::InvalidMatrixShape {
+            expected: (4, 4),
+            actual: (8, 8),
+        })
+    );
+}
@@ -0,0 +1,155 @@
+extern crate ndarray;
+extern crate num;
+extern crate rusq;
+
+use num::complex::Complex;
+use rusq::circuit::Circuit;
+use rusq::gates::multi;
+use rusq::prelude::*;
+use rusq::simulator::unitary;
+use std::f64::consts::PI;
+
+const EPS: f64 = 1e-10;
+
+fn example(circuit: &mut Circuit) {
+    let qubits = circuit.get_qubits();
+    circuit.H(&qubits[0]);
+    circuit.ry(0.3, &qubits[1]);
+    circuit.CNOT(&qubits[0], &qubits[2]);
+    circuit.cphase(0.7, &qubits[2], &qubits[1]);
+    circuit.CCNOT(&qubits[1], &qubits[2], &qubits[0]);
+    circuit.rx(1.1, &qubits[2]);
+    circuit.SQSWAP(&qubits[0], &qubits[1]);
+}
+
+#[test]
+fn matrix() {
+    let mut sim = UnitarySimulator::new(2);
+    let qubits = sim.get_qubits();
+    let identity = sim.unitary();
+    for ((i, j), x) in identity.indexed_iter() {
+        assert_eq!(*x, Complex::new(if i == j { 1. } else { 0. }, 0.));
+    }
+
+    // The control is bit 0, so that |01> is mapped to |11>.
+    sim.CNOT(&qubits[0], &qubits[1]);
+    let expected = [0, 3, 2, 1];
+    for ((i, j), x) in sim.unitary().indexed_iter() {
+        let one = if expected[j] == i { 1. } else { 0. };
+        assert!((x - Complex::new(one, 0.)).norm() < EPS);
+    }
+
+    sim.reset();
+    assert_eq!(sim.unitary(), identity);
+}
+
+#[test]
+fn columns_are_states() {
+    let mut circuit = Circuit::new(3, 0);
+    example(&mut circuit);
+    let mut sim = UnitarySimulator::new(3);
+    circuit.run(&mut sim);
+    assert_eq!(sim.qubits_len(), 3);
+    let matrix = sim.unitary();
+
+    for input in 0..8 {
+        let mut state = QuantumSimulator::new(3);
+        let qubits = state.get_qubits();
+        for (k, qubit) in qubits.iter().enumerate() {
+            if input >> k & 1 == 1 {
+                state.X(qubit);
+            }
+        }
+        circuit.run(&mut state);
+        for (i, amplitude) in state.snapshot().amplitudes().iter().enumerate() {
+            assert!((matrix[[i, input]] - amplitude).norm() < EPS);
+        }
+    }
+
+    // The inverse gives the adjoint.
+    let mut sim = UnitarySimulator::new(3);
+    circuit.run(&mut sim);
+    circuit.inverse().run(&mut sim);
+    for ((i, j), x) in sim.unitary().indexed_iter() {
+        let one = if i == j { 1. } else { 0. };
+        assert!((x - Complex::new(one, 0.)).norm() < EPS);
+    }
+}
+
+#[test]
+fn equivalence() {
+    let mut a = Circuit::new(2, 0);
+    let mut b = Circuit::new(2, 0);
+    let (p, q) = (a.get_qubits(), b.get_qubits());
+
+    // Rz and the phase gate differ by the global phase.
+    a.rz(0.4, &p[0]);
+    b.phase(0.4, &q[0]);
+    // HZH = X
+    a.H(&p[1]);
+    a.Z(&p[1]);
+    a.H(&p[1]);
+    b.X(&q[1]);
+    assert!(unitary::circuits_equivalent(&a, &b, EPS));
+
+    // CZ is symmetric, and is the controlled phase of π.
+    a.cphase(PI, &p[0], &p[1]);
+    b.H(&q[0]);
+    b.CNOT(&q[1], &q[0]);
+    b.H(&q[0]);
+    assert!(unitary::circuits_equivalent(&a, &b, EPS));
+
+    b.rz(1e-3, &q[1]);
+    assert!(!unitary::circuits_equivalent(&a, &b, EPS));
+    assert!(unitary::circuits_equivalent(&a, &b, 1e-3));
+}
+
+#[test]
+fn decompositions() {
+    // The multi-controlled X decomposed into one- and two-qubit gates is CCCNOT.
+    let mut a = Circuit::new(4, 0);
+    let qubits = a.get_qubits();
+    multi::mcx(&mut a, &qubits[..3], &qubits[3]);
+
+    let mut b = Circuit::new(4, 0);
+    let qubits = b.get_qubits();
+    let mut matrix = ndarray::Array2::zeros((16, 16));
+    for i in 0..16 {
+        let j = if i >> 1 == 0b111 { i ^ 1 } else { i };
+        matrix[[j, i]] = Complex::new(1., 0.);
+    }
+    b.apply_multi(&matrix, &qubits);
+    assert!(unitary::circuits_equivalent(&a, &b, 1e-8));
+
+    // The smaller circuit is extended by the identity.
+    let mut c = Circuit::new(2, 0);
+    let pair = c.get_qubits();
+    c.SWAP(&pair[0], &pair[1]);
+    let mut d = Circuit::new(3, 0);
+    let triple = d.get_qubits();
+    d.CNOT(&triple[0], &triple[1]);
+    d.CNOT(&triple[1], &triple[0]);
+    d.CNOT(&triple[0], &triple[1]);
+    assert!(unitary::circuits_equivalent(&c, &d, EPS));
+    d.X(&triple[2]);
+    assert!(!unitary::circuits_equivalent(&c, &d, EPS));
+}
+
+#[test]
+#[should_panic(expected = "a measurement is not unitary")]
+fn measurement() {
+    let mut circuit = Circuit::new(1, 1);
+    let qubits = circuit.get_qubits();
+    let clbits = circuit.get_clbits();
+    circuit.H(&qubits[0]);
+    circuit.measure(&qubits[0], &clbits[0]);
+    unitary::circuit_unitary(&circuit, 1);
+}
+
+#[test]
+#[should_panic]
+fn foreign_qubit() {
+    let mut sim = UnitarySimulator::new(1);
+    let other = QuantumSimulator::new(1);
+    sim.H(&other.get_qubits()[0]);
+}
@@ -0,0 +1,212 @@
+extern crate rusq;
+
+use rusq::algorithms::vqe::{self, Estimation};
+use rusq::observable::{Hamiltonian, Pauli, PauliString};
+use rusq::optimize::{Adam, Differentiation, NelderMead, Spsa};
+use rusq::prelude::*;
+use std::f64::consts::PI;
+
+const EPS: f64 = 1e-10;
+
+#[test]
+fn rotations() {
+    let mut sim = QuantumSimulator::new(1);
+    let qubit = &sim.get_qubits()[0];
+    let z = Hamiltonian::from_terms(&[(1., "Z")]);
+    let x = Hamiltonian::from_terms(&[(1., "X")]);
+    let y = Hamiltonian::from_terms(&[(1., "Y")]);
+
+    sim.rx(PI / 2., qubit);
+    assert!(z.expectation(&sim, &[*qubit]).abs() < EPS);
+    assert!((y.expectation(&sim, &[*qubit]) + 1.).abs() < EPS);
+
+    sim.reset();
+    sim.ry(PI / 3., qubit);
+    assert!((z.expectation(&sim, &[*qubit]) - 0.5).abs() < EPS);
+    assert!((x.expectation(&sim, &[*qubit]) - 0.75f64.sqrt()).abs() < EPS);
+
+    sim.ry(PI / 2. - PI / 3., qubit);
+    sim.rz(PI / 2., qubit);
+    assert!(x.expectation(&sim, &[*qubit]).abs() < EPS);
+    assert!((y.expectation(&sim, &[*qubit]) - 1.).abs() < EPS);
+}
+
+#[test]
+fn pauli_string() {
+    let term = PauliString::new(2., "XIZY");
+    assert_eq!(term.coefficient, 2.);
+    assert_eq!(term.paulis, vec![(0, Pauli::X), (2, Pauli::Z), (3, Pauli::Y)]);
+}
+
+#[test]
+#[should_panic]
+fn invalid_pauli_string() {
+    PauliString::new(1., "XA");
+}
+
+#[test]
+fn expectation() {
+    let mut sim = QuantumSimulator::new(3);
+    let qubits = sim.get_qubits();
+    // (|000> + |011>) / √2 on the qubits 0 and 1, and |1> on the qubit 2
+    sim.H(&qubits[0]);
+    sim.CNOT(&qubits[0], &qubits[1]);
+    sim.X(&qubits[2]);
+
+    let cases = [
+        ("ZII", 0.),
+        ("ZZI", 1.),
+        ("XXI", 1.),
+        ("YYI", -1.),
+        ("XYI", 0.),
+        ("IIZ", -1.),
+        ("ZZZ", -1.),
+        ("III", 1.),
+    ];
+    for &(paulis, expected) in &cases {
+        let actual = PauliString::new(1., paulis).expectation(&sim, &qubits);
+        assert!((actual - expected).abs() < EPS, "{}: {}", paulis, actual);
+    }
+
+    let hamiltonian = Hamiltonian::from_terms(&[(0.5, "XXI"), (-2., "YYI"), (3., "III")]);
+    assert!((hamiltonian.expectation(&sim, &qubits) - 5.5).abs() < EPS);
+
+    // The qubits can be given in another order.
+    let reordered = [qubits[2], qubits[0]];
+    let hamiltonian = Hamiltonian::from_terms(&[(1., "ZI"), (1., "IZ")]);
+    assert!((hamiltonian.expectation(&sim, &reordered) + 1.).abs() < EPS);
+}
+
+#[test]
+fn sample_expectation() {
+    let mut sim = QuantumSimulator::with_seed(2, 7);
+    let qubits = sim.get_qubits();
+    sim.ry(PI / 3., &qubits[0]);
+    sim.H(&qubits[1]);
+
+    let hamiltonian = Hamiltonian::from_terms(&[(1., "ZI"), (2., "IX"), (1., "II")]);
+    let exact = hamiltonian.expectation(&sim, &qubits);
+    let sampled = hamiltonian.sample_expectation(&mut sim, &qubits, 10000);
+    assert!((sampled - exact).abs() < 0.05, "{} vs {}", sampled, exact);
+
+    // Eigenstates give the exact value.
+    assert!((sampled - 3.5).abs() < 0.05);
+    assert_eq!(
+        Hamiltonian::from_terms(&[(2., "IX")]).sample_expectation(&mut sim, &qubits, 10),
+        2.
+    );
+
+    // The sampling does not disturb the state.
+    assert!((hamiltonian.expectation(&sim, &qubits) - exact).abs() < EPS);
+}
+
+fn ansatz(sim: &mut QuantumSimulator, qubits: &[Qubit], theta: &[f64]) {
+    sim.ry(theta[0], &qubits[0]);
+    sim.ry(theta[1], &qubits[1]);
+    sim.CNOT(&qubits[0], &qubits[1]);
+    sim.ry(theta[2], &qubits[0]);
+    sim.ry(theta[3], &qubits[1]);
+}
+
+#[test]
+fn transverse_field_ising() {
+    // The ground energy of ZZ + h (XI + IX) is -√(1 + 4h²).
+    let h = 0.5;
+    let hamiltonian = Hamiltonian::from_terms(&[(1., "ZZ"), (h, "XI"), (h, "IX")]);
+
+    let mut sim = QuantumSimulator::new(2);
+    let result = vqe::solve(
+        &mut sim,
+        &ansatz,
+        &hamiltonian,
+        &mut NelderMead::default(),
+        &[0.1, 0.2, 0.3, 0.4],
+        Estimation::Exact,
+    );
+
+    let expected = -(1. + 4. * h * h).sqrt();
+    assert!((result.energy - expected).abs() < 1e-6, "{}", result.energy);
+    assert_eq!(
+        vqe::energy(&mut sim, &ansatz, &hamiltonian, &result.parameters, Estimation::Exact),
+        result.energy
+    );
+    assert!(result.evaluations > 1);
+}
+
+#[test]
+fn shots() {
+    let hamiltonian = Hamiltonian::from_terms(&[(1., "Z"), (1., "X")]);
+    let ansatz = |sim: &mut QuantumSimulator, qubits: &[Qubit], theta: &[f64]| {
+        sim.ry(theta[0], &qubits[0]);
+    };
+
+    let mut sim = QuantumSimulator::with_seed(1, 1);
+    let mut optimizer = NelderMead {
+        max_iterations: 50,
+        tolerance: 1e-3,
+        ..NelderMead::default()
+    };
+    let result = vqe::solve(
+        &mut sim,
+        &ansatz,
+        &hamiltonian,
+        &mut optimizer,
+        &[0.],
+        Estimation::Shots(2000),
+    );
+
+    assert!(
+        (result.energy + 2f64.sqrt()).abs() < 0.1,
+        "{}",
+        result.energy
+    );
+    // The optimal angle is -3π/4.
+    let angle = result.parameters[0].rem_euclid(2. * PI);
+    assert!((angle - 5. * PI / 4.).abs() < 0.3, "{}", angle);
+}
+
+#[test]
+fn spsa_with_shots() {
+    let h = 0.5;
+    let hamiltonian = Hamiltonian::from_terms(&[(1., "ZZ"), (h, "XI"), (h, "IX")]);
+
+    let mut sim = QuantumSimulator::with_seed(2, 3);
+    let result = vqe::solve(
+        &mut sim,
+        &ansatz,
+        &hamiltonian,
+        &mut Spsa::default(),
+        &[0.1, 0.2, 0.3, 0.4],
+        Estimation::Shots(1000),
+    );
+
+    let expected = -(1. + 4. * h * h).sqrt();
+    assert!((result.energy - expected).abs() < 0.1, "{}", result.energy);
+    // Two evaluations for each iteration, one at the end, and one in the solver
+    assert_eq!(result.evaluations, 2 * Spsa::default().max_iterations + 2);
+}
+
+#[test]
+fn adam_with_parameter_shift() {
+    let h = 0.5;
+    let hamiltonian = Hamiltonian::from_terms(&[(1., "ZZ"), (h, "XI"), (h, "IX")]);
+
+    let mut sim = QuantumSimulator::new(2);
+    let mut optimizer = Adam {
+        learning_rate: 0.1,
+        max_iterations: 500,
+        differentiation: Differentiation::ParameterShift,
+        ..Adam::default()
+    };
+    let result = vqe::solve(
+        &mut sim,
+        &ansatz,
+        &hamiltonian,
+        &mut optimizer,
+        &[0.1, 0.2, 0.3, 0.4],
+        Estimation::Exact,
+    );
+
+    let expected = -(1. + 4. * h * h).sqrt();
+    assert!((result.energy - expected).abs() < 1e-4, "{}", result.energy);
+}